@@ -2,4 +2,10 @@
 pub struct Config {
     /// Print execution backtraces if transaction execution reverts.
     pub backtrace: bool,
+    /// Execute the transactions of a block that don't conflict with each other on worker threads.
+    pub parallel_execution: bool,
+    /// The number of latest blocks whose undo logs and state history are kept, which bounds how
+    /// far the chain can be rolled back and state read at past heights. Everything is kept if
+    /// not set.
+    pub history_depth: Option<u32>,
 }
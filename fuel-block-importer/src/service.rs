@@ -1,23 +1,26 @@
 use crate::Config;
 use fuel_core_interfaces::block_importer::{ImportBlockBroadcast, ImportBlockMpsc};
 use parking_lot::Mutex;
+use std::sync::Arc;
 use tokio::{
     sync::{broadcast, mpsc},
     task::JoinHandle,
 };
 
 pub struct Service {
-    join: Mutex<Option<JoinHandle<()>>>,
+    join: Mutex<Option<JoinHandle<mpsc::Receiver<ImportBlockMpsc>>>>,
     sender: mpsc::Sender<ImportBlockMpsc>,
+    receiver: Arc<Mutex<Option<mpsc::Receiver<ImportBlockMpsc>>>>,
     broadcast: broadcast::Sender<ImportBlockBroadcast>,
 }
 
 impl Service {
     pub async fn new(_config: &Config, _db: ()) -> Result<Self, anyhow::Error> {
-        let (sender, _receiver) = mpsc::channel(100);
+        let (sender, receiver) = mpsc::channel(100);
         let (broadcast, _receiver) = broadcast::channel(100);
         Ok(Self {
             sender,
+            receiver: Arc::new(Mutex::new(Some(receiver))),
             broadcast,
             join: Mutex::new(None),
         })
@@ -26,16 +29,24 @@ impl Service {
     pub async fn start(&self) {
         let mut join = self.join.lock();
         if join.is_none() {
-            *join = Some(tokio::spawn(async {}));
+            if let Some(receiver) = self.receiver.lock().take() {
+                let broadcast = self.broadcast.clone();
+                *join = Some(tokio::spawn(run(receiver, broadcast)));
+            }
         }
     }
 
     pub async fn stop(&self) -> Option<JoinHandle<()>> {
         let join = self.join.lock().take();
-        if join.is_some() {
-            let _ = self.sender.send(ImportBlockMpsc::Stop);
+        if let Some(join) = join {
+            let _ = self.sender.send(ImportBlockMpsc::Stop).await;
+            let receiver = self.receiver.clone();
+            Some(tokio::spawn(async move {
+                *receiver.lock() = join.await.ok();
+            }))
+        } else {
+            None
         }
-        join
     }
 
     pub fn subscribe(&self) -> broadcast::Receiver<ImportBlockBroadcast> {
@@ -46,3 +57,27 @@ impl Service {
         &self.sender
     }
 }
+
+/// Announce imported blocks to subscribers. Blocks are already committed to the database
+/// by the time they are sent to the importer.
+async fn run(
+    mut receiver: mpsc::Receiver<ImportBlockMpsc>,
+    broadcast: broadcast::Sender<ImportBlockBroadcast>,
+) -> mpsc::Receiver<ImportBlockMpsc> {
+    while let Some(event) = receiver.recv().await {
+        match event {
+            ImportBlockMpsc::ImportFuelBlock { block } => {
+                let _ = broadcast.send(ImportBlockBroadcast::PendingBlockImported { block });
+            }
+            ImportBlockMpsc::ImportSealedFuelBlock { block } => {
+                let _ = broadcast.send(ImportBlockBroadcast::SealedFuelBlockImported {
+                    block,
+                    is_created_by_self: false,
+                });
+            }
+            ImportBlockMpsc::SealFuelBlock { .. } => {}
+            ImportBlockMpsc::Stop => break,
+        }
+    }
+    receiver
+}
@@ -1,2 +1,7 @@
+use fuel_core_interfaces::common::fuel_tx::Address;
+
 #[derive(Default, Debug, Clone)]
-pub struct Config {}
+pub struct Config {
+    /// Address the fees of produced blocks are credited to
+    pub coinbase_recipient: Address,
+}
@@ -1,18 +1,34 @@
-use crate::client::schema::contract::ContractBalanceQueryArgs;
+use crate::client::schema::contract::{
+    ContractBalanceQueryArgs, ContractStorageSlotArgs, ContractStorageSlotProofArgs,
+};
 use anyhow::Context;
-use cynic::{http::SurfExt, Id, MutationBuilder, Operation, QueryBuilder};
+use async_tungstenite::{
+    async_std::{connect_async, ConnectStream},
+    tungstenite::{client::IntoClientRequest, http::HeaderValue, Message},
+    WebSocketStream,
+};
+use cynic::{
+    http::SurfExt, GraphQlError, Id, MutationBuilder, Operation, QueryBuilder, StreamingOperation,
+    SubscriptionBuilder,
+};
 use fuel_vm::prelude::*;
+use futures::{
+    stream::{self, BoxStream},
+    SinkExt, Stream, StreamExt,
+};
 use itertools::Itertools;
 use schema::{
-    balance::BalanceArgs,
-    block::BlockByIdArgs,
-    coin::{Coin, CoinByIdArgs, SpendQueryElementInput},
-    contract::{Contract, ContractByIdArgs},
-    tx::{TxArg, TxIdArgs},
+    balance::{BalanceArgs, BalancesConnectionArgs},
+    block::{BlockByIdArgs, BlockHeaderProofArgs},
+    coin::{Coin, CoinByIdArgs, CoinsConnectionArgs, CoinsReceivedArgs, SpendQueryElementInput},
+    contract::{Contract, ContractByIdArgs, ContractStorageConnectionArgs},
+    tx::{DryRunBundleArg, DryRunOverridesInput, DryRunTraceArg, TxArg, TxIdArgs},
     Bytes, ContinueTx, ContinueTxArgs, ConversionError, HexString, IdArg, MemoryArgs, RegisterArgs,
     RunResult, SetBreakpoint, SetBreakpointArgs, SetSingleStepping, SetSingleSteppingArgs, StartTx,
     StartTxArgs, TransactionId, U64,
 };
+use serde::Deserialize;
+use serde_json::json;
 use std::{
     convert::TryInto,
     io::{self, ErrorKind},
@@ -24,6 +40,7 @@ use types::{TransactionResponse, TransactionStatus};
 use crate::client::schema::tx::DryRunArg;
 pub use schema::{PageDirection, PaginatedResult, PaginationRequest};
 
+pub mod merkle;
 pub mod schema;
 pub mod types;
 
@@ -73,21 +90,90 @@ impl FuelClient {
 
         match (response.data, response.errors) {
             (Some(d), _) => Ok(d),
-            (_, Some(e)) => {
-                let e = e.into_iter().map(|e| e.message).fold(
-                    String::from("Response errors"),
-                    |mut s, e| {
-                        s.push_str("; ");
-                        s.push_str(e.as_str());
-                        s
-                    },
-                );
-                Err(io::Error::new(io::ErrorKind::Other, e))
-            }
+            (_, Some(e)) => Err(response_errors(e)),
             _ => Err(io::Error::new(io::ErrorKind::Other, "Invalid response")),
         }
     }
 
+    /// Start `subscription` over a new WebSocket connection using the `graphql-transport-ws`
+    /// protocol. The returned stream ends once the node completes the subscription.
+    async fn subscribe<R: 'static>(
+        &self,
+        subscription: StreamingOperation<'static, R>,
+    ) -> io::Result<BoxStream<'static, io::Result<R>>> {
+        let mut url = self.url.clone();
+        let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+        url.set_scheme(scheme)
+            .map_err(|_| io::Error::new(ErrorKind::InvalidInput, "Invalid fuel-core URL"))?;
+        url.set_path("/graphql-ws");
+
+        let mut request = url.as_str().into_client_request().map_err(other_error)?;
+        request.headers_mut().insert(
+            "Sec-WebSocket-Protocol",
+            HeaderValue::from_static("graphql-transport-ws"),
+        );
+        let (mut socket, _) = connect_async(request).await.map_err(other_error)?;
+
+        send_message(&mut socket, json!({ "type": "connection_init" })).await?;
+        match receive_message(&mut socket).await? {
+            Some(message) if message.r#type == "connection_ack" => {}
+            _ => {
+                return Err(io::Error::new(
+                    ErrorKind::ConnectionRefused,
+                    "Subscription connection wasn't acknowledged",
+                ))
+            }
+        }
+        send_message(
+            &mut socket,
+            json!({ "id": "1", "type": "subscribe", "payload": &subscription }),
+        )
+        .await?;
+
+        Ok(
+            stream::unfold(Some((socket, subscription)), |state| async move {
+                let (mut socket, subscription) = state?;
+                loop {
+                    let message = match receive_message(&mut socket).await {
+                        Ok(Some(message)) => message,
+                        Ok(None) => return None,
+                        Err(e) => return Some((Err(e), None)),
+                    };
+                    match message.r#type.as_str() {
+                        "next" => {
+                            let response = serde_json::from_value(message.payload)
+                                .map_err(other_error)
+                                .and_then(|response| {
+                                    subscription.decode_response(response).map_err(other_error)
+                                })
+                                .and_then(|response| match (response.data, response.errors) {
+                                    (Some(d), _) => Ok(d),
+                                    (_, Some(e)) => Err(response_errors(e)),
+                                    _ => Err(io::Error::new(ErrorKind::Other, "Invalid response")),
+                                });
+                            return Some((response, Some((socket, subscription))));
+                        }
+                        "error" => {
+                            let error = serde_json::from_value(message.payload)
+                                .map_or_else(other_error, response_errors);
+                            return Some((Err(error), None));
+                        }
+                        "complete" => return None,
+                        "ping" => {
+                            if let Err(e) =
+                                send_message(&mut socket, json!({ "type": "pong" })).await
+                            {
+                                return Some((Err(e), None));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            })
+            .boxed(),
+        )
+    }
+
     pub async fn health(&self) -> io::Result<bool> {
         let query = schema::Health::build(());
         self.query(query).await.map(|r| r.health)
@@ -103,6 +189,21 @@ impl FuelClient {
         self.query(query).await.map(|r| r.chain)
     }
 
+    /// The consensus parameters of the block at `height`, or of the next block if not set
+    pub async fn consensus_parameters(
+        &self,
+        height: Option<u64>,
+    ) -> io::Result<schema::chain::ConsensusParameters> {
+        let query = schema::chain::ConsensusParametersQuery::build(
+            &schema::chain::ConsensusParametersArgs {
+                height: height.map(Into::into),
+            },
+        );
+        self.query(query)
+            .await
+            .map(|r| r.chain.consensus_parameters)
+    }
+
     /// Default dry run, matching the exact configuration as the node
     pub async fn dry_run(&self, tx: &Transaction) -> io::Result<Vec<Receipt>> {
         self.dry_run_opt(tx, None).await
@@ -127,6 +228,78 @@ impl FuelClient {
             .collect()
     }
 
+    /// Dry run `tx`, also returning the coins it spends and creates and the changes it makes to
+    /// contract balances and storage
+    pub async fn dry_run_state_diff(
+        &self,
+        tx: &Transaction,
+        utxo_validation: Option<bool>,
+    ) -> io::Result<schema::tx::DryRunStateDiff> {
+        let tx = tx.clone().to_bytes();
+        let query = schema::tx::DryRunStateDiffMutation::build(&DryRunArg {
+            tx: HexString(Bytes(tx)),
+            utxo_validation,
+        });
+        self.query(query).await.map(|r| r.dry_run_state_diff)
+    }
+
+    /// Dry run the transaction, returning the tree of the calls it made. If `instructions` is
+    /// set, every executed instruction is traced as well.
+    pub async fn dry_run_trace(
+        &self,
+        tx: &Transaction,
+        utxo_validation: Option<bool>,
+        instructions: Option<bool>,
+    ) -> io::Result<schema::tx::ExecutionTrace> {
+        let tx = tx.clone().to_bytes();
+        let query = schema::tx::DryRunTrace::build(&DryRunTraceArg {
+            tx: HexString(Bytes(tx)),
+            utxo_validation,
+            instructions,
+        });
+        self.query(query).await.map(|r| r.dry_run_trace)
+    }
+
+    /// Dry run an ordered bundle of transactions in a single block, returning the receipts of
+    /// each of them. The overrides are applied to the state the bundle is executed against.
+    pub async fn dry_run_bundle(
+        &self,
+        txs: &[Transaction],
+        utxo_validation: Option<bool>,
+        overrides: Option<DryRunOverridesInput>,
+    ) -> io::Result<Vec<Vec<Receipt>>> {
+        let txs = txs
+            .iter()
+            .map(|tx| HexString(Bytes(tx.clone().to_bytes())))
+            .collect();
+        let query = schema::tx::DryRunBundle::build(&DryRunBundleArg {
+            txs,
+            utxo_validation,
+            overrides,
+        });
+        let results = self.query(query).await.map(|r| r.dry_run_bundle)?;
+        results
+            .into_iter()
+            .map(|result| {
+                result
+                    .receipts
+                    .into_iter()
+                    .map(|receipt| receipt.try_into().map_err(Into::into))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The smallest gas limit `tx` can be executed with without reverting, along with the gas
+    /// it uses and the fee it pays with that limit
+    pub async fn estimate_gas(&self, tx: &Transaction) -> io::Result<schema::tx::GasEstimate> {
+        let tx = tx.clone().to_bytes();
+        let query = schema::tx::EstimateGasQuery::build(&TxArg {
+            tx: HexString(Bytes(tx)),
+        });
+        self.query(query).await.map(|r| r.estimate_gas)
+    }
+
     pub async fn submit(&self, tx: &Transaction) -> io::Result<TransactionId> {
         let tx = tx.clone().to_bytes();
         let query = schema::tx::Submit::build(&TxArg {
@@ -241,6 +414,27 @@ impl FuelClient {
         Ok(transaction.map(|tx| tx.try_into()).transpose()?)
     }
 
+    /// Get the proof that a transaction is included in the transactions root of its block.
+    /// Returns `None` if the transaction isn't part of a block.
+    pub async fn transaction_proof(
+        &self,
+        id: &str,
+    ) -> io::Result<Option<schema::tx::TransactionProof>> {
+        let query = schema::tx::TransactionProofQuery::build(&TxIdArgs { id: id.parse()? });
+        let proof = self.query(query).await?.transaction_proof;
+        Ok(proof)
+    }
+
+    /// Get the call tree of an executed transaction, if the node stores traces
+    pub async fn transaction_trace(
+        &self,
+        id: &str,
+    ) -> io::Result<Option<schema::tx::ExecutionTrace>> {
+        let query = schema::tx::TransactionTraceQuery::build(&TxIdArgs { id: id.parse()? });
+        let trace = self.query(query).await?.transaction_trace;
+        Ok(trace)
+    }
+
     /// Get the status of a transaction
     pub async fn transaction_status(&self, id: &str) -> io::Result<TransactionStatus> {
         let query = schema::tx::TransactionQuery::build(&TxIdArgs { id: id.parse()? });
@@ -261,6 +455,18 @@ impl FuelClient {
         Ok(status)
     }
 
+    /// Stream the status changes of a transaction, starting with its current status if the
+    /// node knows about it. The stream ends once the transaction is executed or squeezed out.
+    pub async fn subscribe_transaction_status(
+        &self,
+        id: &str,
+    ) -> io::Result<impl Stream<Item = io::Result<TransactionStatus>>> {
+        let subscription =
+            schema::tx::StatusChangeSubscription::build(TxIdArgs { id: id.parse()? });
+        let stream = self.subscribe(subscription).await?;
+        Ok(stream.map(|r| Ok(r?.status_change.try_into()?)))
+    }
+
     /// returns a paginated set of transactions sorted by block height
     pub async fn transactions(
         &self,
@@ -271,6 +477,31 @@ impl FuelClient {
         Ok(transactions)
     }
 
+    /// Aggregate statistics of transactions waiting inside the txpool
+    pub async fn txpool_stats(&self) -> io::Result<schema::txpool::TxPoolStats> {
+        let query = schema::txpool::TxPoolStatsQuery::build(());
+        self.query(query).await.map(|r| r.txpool.stats)
+    }
+
+    /// Get a transaction from the txpool, returns `None` if it isn't pending
+    pub async fn pending_transaction(
+        &self,
+        id: &str,
+    ) -> io::Result<Option<schema::txpool::PendingTransaction>> {
+        let query = schema::txpool::PendingTransactionQuery::build(&TxIdArgs { id: id.parse()? });
+        self.query(query).await.map(|r| r.txpool.transaction)
+    }
+
+    /// Returns a paginated set of txpool transactions sorted by gas price, highest first
+    pub async fn pending_transactions(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<schema::txpool::PendingTransaction, String>> {
+        let query = schema::txpool::PendingTransactionsQuery::build(&request.into());
+        let transactions = self.query(query).await?.txpool.transactions.into();
+        Ok(transactions)
+    }
+
     /// Returns a paginated set of transactions associated with a txo owner address.
     pub async fn transactions_by_owner(
         &self,
@@ -284,6 +515,35 @@ impl FuelClient {
         Ok(transactions)
     }
 
+    /// Returns a paginated set of transactions that used or created a contract.
+    pub async fn transactions_by_contract(
+        &self,
+        contract: &str,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<TransactionResponse, String>> {
+        let contract: schema::ContractId = contract.parse()?;
+        let query = schema::tx::TransactionsByContractQuery::build(&(contract, request).into());
+
+        let transactions = self
+            .query(query)
+            .await?
+            .transactions_by_contract
+            .try_into()?;
+        Ok(transactions)
+    }
+
+    /// Returns a paginated set of logs emitted by successful transactions, in chain order.
+    pub async fn logs(
+        &self,
+        filter: schema::log::LogFilterInput,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<schema::log::Log, String>> {
+        let query = schema::log::LogsQuery::build(&(filter, request).into());
+
+        let logs = self.query(query).await?.logs.into();
+        Ok(logs)
+    }
+
     pub async fn receipts(&self, id: &str) -> io::Result<Vec<fuel_tx::Receipt>> {
         let query = schema::tx::TransactionQuery::build(&TxIdArgs { id: id.parse()? });
 
@@ -309,6 +569,20 @@ impl FuelClient {
         Ok(block)
     }
 
+    /// Get the proof that a block is included in the `prev_root` of the later block `root_id`
+    pub async fn block_header_proof(
+        &self,
+        id: &str,
+        root_id: &str,
+    ) -> io::Result<schema::block::BlockHeaderProof> {
+        let query = schema::block::BlockHeaderProofQuery::build(&BlockHeaderProofArgs {
+            id: id.parse()?,
+            root_id: root_id.parse()?,
+        });
+        let proof = self.query(query).await?.block_header_proof;
+        Ok(proof)
+    }
+
     /// Retrieve multiple blocks
     pub async fn blocks(
         &self,
@@ -321,27 +595,68 @@ impl FuelClient {
         Ok(blocks)
     }
 
+    /// Stream blocks as they are imported by the node
+    pub async fn subscribe_blocks(
+        &self,
+    ) -> io::Result<impl Stream<Item = io::Result<schema::block::Block>>> {
+        let subscription = schema::block::BlocksSubscription::build(());
+        let stream = self.subscribe(subscription).await?;
+        Ok(stream.map(|r| r.map(|r| r.blocks)))
+    }
+
     pub async fn coin(&self, id: &str) -> io::Result<Option<Coin>> {
+        self.coin_at_height(id, None).await
+    }
+
+    /// Retrieve a coin as it was at the end of the block at `height`, or the latest if `None`
+    pub async fn coin_at_height(&self, id: &str, height: Option<u64>) -> io::Result<Option<Coin>> {
         let query = schema::coin::CoinByIdQuery::build(CoinByIdArgs {
             utxo_id: id.parse()?,
+            at_height: height.map(Into::into),
         });
         let coin = self.query(query).await?.coin;
         Ok(coin)
     }
 
+    /// Stream coins created for `owner` by newly imported blocks
+    pub async fn subscribe_coins(
+        &self,
+        owner: &str,
+    ) -> io::Result<impl Stream<Item = io::Result<Coin>>> {
+        let subscription = schema::coin::CoinsReceivedSubscription::build(CoinsReceivedArgs {
+            owner: owner.parse()?,
+        });
+        let stream = self.subscribe(subscription).await?;
+        Ok(stream.map(|r| r.map(|r| r.coins_received)))
+    }
+
     /// Retrieve a page of coins by their owner
     pub async fn coins(
         &self,
         owner: &str,
         asset_id: Option<&str>,
         request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<schema::coin::Coin, String>> {
+        self.coins_at_height(owner, asset_id, request, None).await
+    }
+
+    /// Retrieve a page of the unspent coins of an owner at the end of the block at `height`,
+    /// or the latest if `None`
+    pub async fn coins_at_height(
+        &self,
+        owner: &str,
+        asset_id: Option<&str>,
+        request: PaginationRequest<String>,
+        height: Option<u64>,
     ) -> io::Result<PaginatedResult<schema::coin::Coin, String>> {
         let owner: schema::Address = owner.parse()?;
         let asset_id: schema::AssetId = match asset_id {
             Some(asset_id) => asset_id.parse()?,
             None => schema::AssetId::default(),
         };
-        let query = schema::coin::CoinsQuery::build(&(owner, asset_id, request).into());
+        let mut args: CoinsConnectionArgs = (owner, asset_id, request).into();
+        args.at_height = height.map(Into::into);
+        let query = schema::coin::CoinsQuery::build(&args);
 
         let coins = self.query(query).await?.coins.into();
         Ok(coins)
@@ -384,6 +699,17 @@ impl FuelClient {
     }
 
     pub async fn contract_balance(&self, id: &str, asset: Option<&str>) -> io::Result<u64> {
+        self.contract_balance_at_height(id, asset, None).await
+    }
+
+    /// Retrieve the balance of a contract at the end of the block at `height`, or the latest
+    /// if `None`
+    pub async fn contract_balance_at_height(
+        &self,
+        id: &str,
+        asset: Option<&str>,
+        height: Option<u64>,
+    ) -> io::Result<u64> {
         let asset_id: schema::AssetId = match asset {
             Some(asset) => asset.parse()?,
             None => schema::AssetId::default(),
@@ -392,6 +718,7 @@ impl FuelClient {
         let query = schema::contract::ContractBalanceQuery::build(ContractBalanceQueryArgs {
             id: id.parse()?,
             asset: asset_id,
+            at_height: height.map(Into::into),
         });
 
         let balance = self.query(query).await.unwrap().contract_balance.amount;
@@ -399,12 +726,27 @@ impl FuelClient {
     }
 
     pub async fn balance(&self, owner: &str, asset_id: Option<&str>) -> io::Result<u64> {
+        self.balance_at_height(owner, asset_id, None).await
+    }
+
+    /// Retrieve the balance of an owner at the end of the block at `height`, or the latest
+    /// if `None`
+    pub async fn balance_at_height(
+        &self,
+        owner: &str,
+        asset_id: Option<&str>,
+        height: Option<u64>,
+    ) -> io::Result<u64> {
         let owner: schema::Address = owner.parse()?;
         let asset_id: schema::AssetId = match asset_id {
             Some(asset_id) => asset_id.parse()?,
             None => schema::AssetId::default(),
         };
-        let query = schema::balance::BalanceQuery::build(BalanceArgs { owner, asset_id });
+        let query = schema::balance::BalanceQuery::build(BalanceArgs {
+            owner,
+            asset_id,
+            at_height: height.map(Into::into),
+        });
         let balance = self.query(query).await?.balance;
         Ok(balance.amount.into())
     }
@@ -414,9 +756,22 @@ impl FuelClient {
         &self,
         owner: &str,
         request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<schema::balance::Balance, String>> {
+        self.balances_at_height(owner, request, None).await
+    }
+
+    /// Retrieve a page of the balances of an owner at the end of the block at `height`, or the
+    /// latest if `None`
+    pub async fn balances_at_height(
+        &self,
+        owner: &str,
+        request: PaginationRequest<String>,
+        height: Option<u64>,
     ) -> io::Result<PaginatedResult<schema::balance::Balance, String>> {
         let owner: schema::Address = owner.parse()?;
-        let query = schema::balance::BalancesQuery::build(&(owner, request).into());
+        let mut args: BalancesConnectionArgs = (owner, request).into();
+        args.at_height = height.map(Into::into);
+        let query = schema::balance::BalancesQuery::build(&args);
 
         let balances = self.query(query).await?.balances.into();
         Ok(balances)
@@ -434,6 +789,127 @@ impl FuelClient {
 
         Ok(balances)
     }
+
+    /// Returns the value of a contract storage slot, if the slot was ever written
+    pub async fn contract_storage_slot(
+        &self,
+        contract: &str,
+        key: &str,
+    ) -> io::Result<Option<Bytes32>> {
+        self.contract_storage_slot_at_height(contract, key, None)
+            .await
+    }
+
+    /// Returns the value of a contract storage slot at the end of the block at `height`, or the
+    /// latest if `None`
+    pub async fn contract_storage_slot_at_height(
+        &self,
+        contract: &str,
+        key: &str,
+        height: Option<u64>,
+    ) -> io::Result<Option<Bytes32>> {
+        let query = schema::contract::ContractStorageSlotQuery::build(ContractStorageSlotArgs {
+            contract: contract.parse()?,
+            key: key.parse()?,
+            at_height: height.map(Into::into),
+        });
+
+        let slot = self.query(query).await?.contract_storage_slot;
+        Ok(slot.map(|slot| slot.value.into()))
+    }
+
+    /// Returns a proof of the value of a contract storage slot, or of its absence, against the
+    /// state root of the latest block
+    pub async fn contract_storage_slot_proof(
+        &self,
+        contract: &str,
+        key: &str,
+    ) -> io::Result<schema::contract::ContractStorageSlotProof> {
+        let query =
+            schema::contract::ContractStorageSlotProofQuery::build(ContractStorageSlotProofArgs {
+                contract: contract.parse()?,
+                key: key.parse()?,
+            });
+        let proof = self.query(query).await?.contract_storage_slot_proof;
+        Ok(proof)
+    }
+
+    /// Returns a paginated set of contract storage slots sorted by their key
+    pub async fn contract_storage(
+        &self,
+        contract: &str,
+        request: PaginationRequest<String>,
+    ) -> io::Result<PaginatedResult<schema::contract::ContractStorageSlot, String>> {
+        self.contract_storage_at_height(contract, request, None)
+            .await
+    }
+
+    /// Returns a page of the storage slots of a contract at the end of the block at `height`, or
+    /// the latest if `None`
+    pub async fn contract_storage_at_height(
+        &self,
+        contract: &str,
+        request: PaginationRequest<String>,
+        height: Option<u64>,
+    ) -> io::Result<PaginatedResult<schema::contract::ContractStorageSlot, String>> {
+        let contract_id: schema::ContractId = contract.parse()?;
+        let mut args: ContractStorageConnectionArgs = (contract_id, request).into();
+        args.at_height = height.map(Into::into);
+        let query = schema::contract::ContractStorageQuery::build(&args);
+
+        let slots = self.query(query).await?.contract_storage.into();
+
+        Ok(slots)
+    }
+}
+
+/// A message of the `graphql-transport-ws` protocol sent by the node
+#[derive(Deserialize)]
+struct SubscriptionMessage {
+    r#type: String,
+    #[serde(default)]
+    payload: serde_json::Value,
+}
+
+type Socket = WebSocketStream<ConnectStream>;
+
+async fn send_message(socket: &mut Socket, message: serde_json::Value) -> io::Result<()> {
+    socket
+        .send(Message::Text(message.to_string()))
+        .await
+        .map_err(other_error)
+}
+
+/// Receive the next protocol message, returns `None` once the connection is closed.
+async fn receive_message(socket: &mut Socket) -> io::Result<Option<SubscriptionMessage>> {
+    while let Some(message) = socket.next().await {
+        match message.map_err(other_error)? {
+            Message::Text(text) => return serde_json::from_str(&text).map_err(other_error),
+            Message::Close(_) => return Ok(None),
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+fn response_errors(errors: Vec<GraphQlError>) -> io::Error {
+    let e =
+        errors
+            .into_iter()
+            .map(|e| e.message)
+            .fold(String::from("Response errors"), |mut s, e| {
+                s.push_str("; ");
+                s.push_str(e.as_str());
+                s
+            });
+    io::Error::new(io::ErrorKind::Other, e)
+}
+
+fn other_error<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::Other, e)
 }
 
 #[cfg(any(test, feature = "test-helpers"))]
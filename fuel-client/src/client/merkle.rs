@@ -0,0 +1,219 @@
+//! Verification of proofs against the Merkle trees committed to in block headers.
+
+use fuel_core_interfaces::merkle::{leaf_sum, node_sum, sparse_leaf_sum};
+use fuel_types::Bytes32;
+use fuel_vm::fuel_crypto::Hasher;
+
+/// Verify that `data` is the leaf at `index` of a binary Merkle tree with `leaves_count`
+/// leaves and the given `root`. `proof_set` holds the sibling hashes from the leaf up to the
+/// root.
+pub fn verify_binary_merkle_proof(
+    root: &Bytes32,
+    data: &[u8],
+    proof_set: &[Bytes32],
+    index: u64,
+    leaves_count: u64,
+) -> bool {
+    if index >= leaves_count {
+        return false;
+    }
+
+    let mut sum = leaf_sum(data);
+    let mut proof_set = proof_set.iter();
+    let mut height = 1u32;
+    // the last leaf of the perfect subtree that contains the leaf
+    let mut stable_end = index;
+
+    // walk up the perfect subtrees the leaf is part of
+    loop {
+        let subtree_start = index / (1 << height) * (1 << height);
+        let subtree_end = subtree_start + (1 << height) - 1;
+        if subtree_end >= leaves_count {
+            break;
+        }
+        stable_end = subtree_end;
+        let side = match proof_set.next() {
+            Some(side) => side,
+            None => return false,
+        };
+        if index - subtree_start < 1 << (height - 1) {
+            sum = node_sum(&sum, side);
+        } else {
+            sum = node_sum(side, &sum);
+        }
+        height += 1;
+    }
+
+    // the subtree is joined with the root of the leaves to its right
+    if stable_end != leaves_count - 1 {
+        let side = match proof_set.next() {
+            Some(side) => side,
+            None => return false,
+        };
+        sum = node_sum(&sum, side);
+    }
+
+    // and then with the perfect subtrees to its left
+    for side in proof_set {
+        sum = node_sum(side, &sum);
+    }
+
+    &sum == root
+}
+
+/// Verify a proof of a key of a sparse Merkle tree with the given `root`. If `data` is `Some`,
+/// the proof must show the key holds that data, otherwise that the key isn't in the tree.
+/// `leaf` is the key and data hash of the leaf the path of the key ends in, if any, and
+/// `proof_set` holds the sibling hashes from that leaf up to the root.
+pub fn verify_sparse_merkle_proof(
+    root: &Bytes32,
+    key: &Bytes32,
+    data: Option<&[u8]>,
+    leaf: Option<(&Bytes32, &Bytes32)>,
+    proof_set: &[Bytes32],
+) -> bool {
+    if proof_set.len() > Bytes32::LEN * 8 {
+        return false;
+    }
+    let goes_right = |key: &Bytes32, depth: usize| key[depth / 8] & (0x80 >> (depth % 8)) != 0;
+
+    let mut sum = match (data, leaf) {
+        (Some(data), Some((leaf_key, data_hash))) => {
+            if leaf_key != key || data_hash != &Hasher::hash(data) {
+                return false;
+            }
+            sparse_leaf_sum(leaf_key, data_hash)
+        }
+        (Some(_), None) => return false,
+        (None, Some((leaf_key, data_hash))) => {
+            // another leaf takes the place of the key, so it must be on the path of the key
+            if leaf_key == key
+                || (0..proof_set.len())
+                    .any(|depth| goes_right(key, depth) != goes_right(leaf_key, depth))
+            {
+                return false;
+            }
+            sparse_leaf_sum(leaf_key, data_hash)
+        }
+        (None, None) => Bytes32::zeroed(),
+    };
+
+    for (height, side) in proof_set.iter().enumerate() {
+        let depth = proof_set.len() - 1 - height;
+        if goes_right(key, depth) {
+            sum = node_sum(side, &sum);
+        } else {
+            sum = node_sum(&sum, side);
+        }
+    }
+
+    &sum == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_vm::fuel_merkle::{binary::MerkleTree, common::StorageMap};
+
+    #[test]
+    fn proofs_of_every_leaf_are_valid() {
+        for leaves_count in 1..20u64 {
+            let leaves: Vec<Vec<u8>> = (0..leaves_count).map(|i| vec![i as u8; 8]).collect();
+            let mut storage = StorageMap::new();
+            let mut tree = MerkleTree::new(&mut storage);
+            for leaf in &leaves {
+                tree.push(leaf).unwrap();
+            }
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                let (root, proof_set) = tree.prove(index as u64).unwrap();
+                let root = root.into();
+                // the first element of the set is the leaf itself
+                let proof_set: Vec<Bytes32> =
+                    proof_set[1..].iter().copied().map(Into::into).collect();
+
+                assert!(verify_binary_merkle_proof(
+                    &root,
+                    leaf,
+                    &proof_set,
+                    index as u64,
+                    leaves_count
+                ));
+                assert!(!verify_binary_merkle_proof(
+                    &root,
+                    &[0xff; 8],
+                    &proof_set,
+                    index as u64,
+                    leaves_count
+                ));
+                assert!(!verify_binary_merkle_proof(
+                    &root,
+                    leaf,
+                    &proof_set,
+                    leaves_count,
+                    leaves_count
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn sparse_proofs_of_inclusion_and_absence_are_valid() {
+        // `a` and `c` only diverge at the second bit, `b` goes right from the root
+        let a = Bytes32::from([0x00; 32]);
+        let b = Bytes32::from([0x80; 32]);
+        let c = Bytes32::from([0x40; 32]);
+        let a_leaf = (&a, &Hasher::hash(a));
+        let b_leaf_sum = sparse_leaf_sum(&b, &Hasher::hash(b));
+        let root = node_sum(&sparse_leaf_sum(a_leaf.0, a_leaf.1), &b_leaf_sum);
+        let proof_set = [b_leaf_sum];
+        assert!(verify_sparse_merkle_proof(
+            &root,
+            &a,
+            Some(a.as_ref()),
+            Some(a_leaf),
+            &proof_set
+        ));
+        assert!(!verify_sparse_merkle_proof(
+            &root,
+            &a,
+            Some(b.as_ref()),
+            Some(a_leaf),
+            &proof_set
+        ));
+        assert!(!verify_sparse_merkle_proof(
+            &root,
+            &a,
+            None,
+            Some(a_leaf),
+            &proof_set
+        ));
+
+        // the path of `c` ends in the leaf of `a`
+        assert!(verify_sparse_merkle_proof(
+            &root,
+            &c,
+            None,
+            Some(a_leaf),
+            &proof_set
+        ));
+        // but the path of `b` doesn't
+        assert!(!verify_sparse_merkle_proof(
+            &root,
+            &b,
+            None,
+            Some(a_leaf),
+            &proof_set
+        ));
+
+        // the empty tree proves the absence of every key
+        assert!(verify_sparse_merkle_proof(
+            &Bytes32::zeroed(),
+            &a,
+            None,
+            None,
+            &[]
+        ));
+        assert!(!verify_sparse_merkle_proof(&root, &a, None, None, &[]));
+    }
+}
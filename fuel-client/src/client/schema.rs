@@ -18,9 +18,11 @@ pub mod block;
 pub mod chain;
 pub mod coin;
 pub mod contract;
+pub mod log;
 pub mod node_info;
 pub mod primitives;
 pub mod tx;
+pub mod txpool;
 
 #[derive(cynic::QueryFragment, Debug)]
 #[cynic(schema_path = "./assets/schema.sdl", graphql_type = "Query")]
@@ -5,6 +5,8 @@ use crate::client::{PageDirection, PaginatedResult, PaginationRequest};
 pub struct BalanceArgs {
     pub owner: Address,
     pub asset_id: AssetId,
+    /// Read the balance at the end of this block instead of the latest
+    pub at_height: Option<U64>,
 }
 
 #[derive(cynic::QueryFragment, Debug)]
@@ -14,7 +16,7 @@ pub struct BalanceArgs {
     argument_struct = "BalanceArgs"
 )]
 pub struct BalanceQuery {
-    #[arguments(owner = &args.owner, asset_id = &args.asset_id)]
+    #[arguments(owner = &args.owner, asset_id = &args.asset_id, at_height = &args.at_height)]
     pub balance: Balance,
 }
 
@@ -29,6 +31,8 @@ pub struct BalanceFilterInput {
 pub struct BalancesConnectionArgs {
     /// Filter coins based on a filter
     filter: BalanceFilterInput,
+    /// Read the balances at the end of this block instead of the latest
+    pub at_height: Option<U64>,
     /// Skip until coin id (forward pagination)
     pub after: Option<String>,
     /// Skip until coin id (backward pagination)
@@ -49,6 +53,7 @@ impl From<(Address, PaginationRequest<String>)> for BalancesConnectionArgs {
                 before: None,
                 first: Some(r.1.results as i32),
                 last: None,
+                at_height: None,
             },
             PageDirection::Backward => BalancesConnectionArgs {
                 filter: BalanceFilterInput { owner: r.0 },
@@ -56,6 +61,7 @@ impl From<(Address, PaginationRequest<String>)> for BalancesConnectionArgs {
                 before: r.1.cursor,
                 first: None,
                 last: Some(r.1.results as i32),
+                at_height: None,
             },
         }
     }
@@ -68,7 +74,7 @@ impl From<(Address, PaginationRequest<String>)> for BalancesConnectionArgs {
     argument_struct = "BalancesConnectionArgs"
 )]
 pub struct BalancesQuery {
-    #[arguments(filter = &args.filter, after = &args.after, before = &args.before, first = &args.first, last = &args.last)]
+    #[arguments(filter = &args.filter, after = &args.after, before = &args.before, at_height = &args.at_height, first = &args.first, last = &args.last)]
     pub balances: BalanceConnection,
 }
 
@@ -115,6 +121,7 @@ mod tests {
         let operation = BalanceQuery::build(BalanceArgs {
             owner: Address::default(),
             asset_id: AssetId::default(),
+            at_height: None,
         });
         insta::assert_snapshot!(operation.query)
     }
@@ -130,6 +137,7 @@ mod tests {
             before: None,
             first: None,
             last: None,
+            at_height: None,
         });
         insta::assert_snapshot!(operation.query)
     }
@@ -1,8 +1,11 @@
+use crate::client::merkle::verify_binary_merkle_proof;
 use crate::client::schema::{
-    primitives::Address, primitives::DateTime, schema, BlockId, ConnectionArgs, PageInfo, U64,
+    primitives::Address, primitives::DateTime, schema, BlockId, Bytes32, ConnectionArgs, PageInfo,
+    U64,
 };
 use crate::client::PaginatedResult;
 
+use super::coin::Coin;
 use super::tx::TransactionIdFragment;
 
 #[derive(cynic::FragmentArguments, Debug)]
@@ -64,6 +67,10 @@ pub struct Block {
     pub id: BlockId,
     pub time: DateTime,
     pub producer: Address,
+    pub prev_root: Bytes32,
+    pub state_root: Bytes32,
+    pub fees: U64,
+    pub coinbase: Option<Coin>,
     pub transactions: Vec<TransactionIdFragment>,
 }
 
@@ -73,6 +80,55 @@ pub struct BlockIdFragment {
     pub id: BlockId,
 }
 
+#[derive(cynic::FragmentArguments, Debug)]
+pub struct BlockHeaderProofArgs {
+    pub id: BlockId,
+    pub root_id: BlockId,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct BlockHeaderProof {
+    pub block_id: BlockId,
+    pub root_block_id: BlockId,
+    pub prev_root: Bytes32,
+    pub index: U64,
+    pub leaves_count: U64,
+    pub proof_set: Vec<Bytes32>,
+}
+
+impl BlockHeaderProof {
+    /// Verify that the proof includes the block into the `prev_root` of the root block.
+    pub fn verify(&self) -> bool {
+        let proof_set: Vec<fuel_types::Bytes32> =
+            self.proof_set.iter().map(|side| side.0 .0).collect();
+        verify_binary_merkle_proof(
+            &self.prev_root.0 .0,
+            self.block_id.0 .0.as_ref(),
+            &proof_set,
+            self.index.0,
+            self.leaves_count.0,
+        )
+    }
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "BlockHeaderProofArgs"
+)]
+pub struct BlockHeaderProofQuery {
+    #[arguments(id = &args.id, root_id = &args.root_id)]
+    pub block_header_proof: BlockHeaderProof,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "Subscription")]
+pub struct BlocksSubscription {
+    pub blocks: Block,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,4 +153,23 @@ mod tests {
         });
         insta::assert_snapshot!(operation.query)
     }
+
+    #[test]
+    fn block_header_proof_query_gql_output() {
+        use cynic::QueryBuilder;
+        let operation = BlockHeaderProofQuery::build(BlockHeaderProofArgs {
+            id: BlockId::default(),
+            root_id: BlockId::default(),
+        });
+        insta::assert_snapshot!(operation.query)
+    }
+
+    #[test]
+    fn blocks_subscription_gql_output() {
+        use cynic::SubscriptionBuilder;
+        let operation = BlocksSubscription::build(());
+        insta::assert_snapshot!(serde_json::to_value(&operation).unwrap()["query"]
+            .as_str()
+            .unwrap())
+    }
 }
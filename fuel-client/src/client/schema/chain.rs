@@ -53,6 +53,32 @@ pub struct ChainInfo {
     pub consensus_parameters: ConsensusParameters,
 }
 
+#[derive(cynic::FragmentArguments, Debug)]
+pub struct ConsensusParametersArgs {
+    pub height: Option<U64>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "ChainInfo",
+    argument_struct = "ConsensusParametersArgs"
+)]
+pub struct ChainConsensusParameters {
+    #[arguments(height = &args.height)]
+    pub consensus_parameters: ConsensusParameters,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "ConsensusParametersArgs"
+)]
+pub struct ConsensusParametersQuery {
+    pub chain: ChainConsensusParameters,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,4 +89,13 @@ mod tests {
         let operation = ChainQuery::build(());
         insta::assert_snapshot!(operation.query)
     }
+
+    #[test]
+    fn consensus_parameters_gql_query_output() {
+        use cynic::QueryBuilder;
+        let operation = ConsensusParametersQuery::build(&ConsensusParametersArgs {
+            height: Some(10u64.into()),
+        });
+        insta::assert_snapshot!(operation.query)
+    }
 }
@@ -4,6 +4,8 @@ use crate::client::{PageDirection, PaginatedResult, PaginationRequest};
 #[derive(cynic::FragmentArguments, Debug)]
 pub struct CoinByIdArgs {
     pub utxo_id: UtxoId,
+    /// Read the coin at the end of this block instead of the latest
+    pub at_height: Option<U64>,
 }
 
 #[derive(cynic::QueryFragment, Debug)]
@@ -13,7 +15,7 @@ pub struct CoinByIdArgs {
     argument_struct = "CoinByIdArgs"
 )]
 pub struct CoinByIdQuery {
-    #[arguments(utxo_id = &args.utxo_id)]
+    #[arguments(utxo_id = &args.utxo_id, at_height = &args.at_height)]
     pub coin: Option<Coin>,
 }
 
@@ -30,6 +32,8 @@ pub struct CoinFilterInput {
 pub struct CoinsConnectionArgs {
     /// Filter coins based on a filter
     filter: CoinFilterInput,
+    /// Read the coins at the end of this block instead of the latest
+    pub at_height: Option<U64>,
     /// Skip until coin id (forward pagination)
     pub after: Option<String>,
     /// Skip until coin id (backward pagination)
@@ -53,6 +57,7 @@ impl From<(Address, AssetId, PaginationRequest<String>)> for CoinsConnectionArgs
                 before: None,
                 first: Some(r.2.results as i32),
                 last: None,
+                at_height: None,
             },
             PageDirection::Backward => CoinsConnectionArgs {
                 filter: CoinFilterInput {
@@ -63,6 +68,7 @@ impl From<(Address, AssetId, PaginationRequest<String>)> for CoinsConnectionArgs
                 before: r.2.cursor,
                 first: None,
                 last: Some(r.2.results as i32),
+                at_height: None,
             },
         }
     }
@@ -75,7 +81,7 @@ impl From<(Address, AssetId, PaginationRequest<String>)> for CoinsConnectionArgs
     argument_struct = "CoinsConnectionArgs"
 )]
 pub struct CoinsQuery {
-    #[arguments(filter = &args.filter, after = &args.after, before = &args.before, first = &args.first, last = &args.last)]
+    #[arguments(filter = &args.filter, after = &args.after, before = &args.before, at_height = &args.at_height, first = &args.first, last = &args.last)]
     pub coins: CoinConnection,
 }
 
@@ -179,6 +185,23 @@ pub struct CoinIdFragment {
     pub utxo_id: UtxoId,
 }
 
+#[derive(cynic::FragmentArguments, Debug)]
+pub struct CoinsReceivedArgs {
+    /// Address of the owner
+    pub owner: Address,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Subscription",
+    argument_struct = "CoinsReceivedArgs"
+)]
+pub struct CoinsReceivedSubscription {
+    #[arguments(owner = &args.owner)]
+    pub coins_received: Coin,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -188,6 +211,7 @@ mod tests {
         use cynic::QueryBuilder;
         let operation = CoinByIdQuery::build(CoinByIdArgs {
             utxo_id: UtxoId::default(),
+            at_height: None,
         });
         insta::assert_snapshot!(operation.query)
     }
@@ -204,7 +228,19 @@ mod tests {
             before: None,
             first: None,
             last: None,
+            at_height: None,
         });
         insta::assert_snapshot!(operation.query)
     }
+
+    #[test]
+    fn coins_received_subscription_gql_output() {
+        use cynic::SubscriptionBuilder;
+        let operation = CoinsReceivedSubscription::build(CoinsReceivedArgs {
+            owner: Address::default(),
+        });
+        insta::assert_snapshot!(serde_json::to_value(&operation).unwrap()["query"]
+            .as_str()
+            .unwrap())
+    }
 }
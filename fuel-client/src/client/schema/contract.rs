@@ -1,4 +1,5 @@
-use crate::client::schema::{schema, AssetId, ContractId, HexString, PageInfo, Salt, U64};
+use crate::client::merkle::verify_sparse_merkle_proof;
+use crate::client::schema::{schema, AssetId, Bytes32, ContractId, HexString, PageInfo, Salt, U64};
 use crate::client::{PageDirection, PaginatedResult, PaginationRequest};
 
 #[derive(cynic::FragmentArguments, Debug)]
@@ -29,6 +30,8 @@ pub struct ContractBalance {
 pub struct ContractBalanceQueryArgs {
     pub id: ContractId,
     pub asset: AssetId,
+    /// Read the balance at the end of this block instead of the latest
+    pub at_height: Option<U64>,
 }
 
 #[derive(cynic::QueryFragment, Debug)]
@@ -38,7 +41,7 @@ pub struct ContractBalanceQueryArgs {
     argument_struct = "ContractBalanceQueryArgs"
 )]
 pub struct ContractBalanceQuery {
-    #[arguments(contract = &args.id, asset = &args.asset)]
+    #[arguments(contract = &args.id, asset = &args.asset, at_height = &args.at_height)]
     pub contract_balance: ContractBalance,
 }
 
@@ -135,6 +138,194 @@ impl From<(ContractId, PaginationRequest<String>)> for ContractBalancesConnectio
     }
 }
 
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct ContractStorageSlot {
+    pub contract: ContractId,
+    pub key: Bytes32,
+    pub value: Bytes32,
+}
+
+#[derive(cynic::FragmentArguments, Debug)]
+pub struct ContractStorageSlotArgs {
+    pub contract: ContractId,
+    pub key: Bytes32,
+    /// Read the slot at the end of this block instead of the latest
+    pub at_height: Option<U64>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "ContractStorageSlotArgs"
+)]
+pub struct ContractStorageSlotQuery {
+    #[arguments(contract = &args.contract, key = &args.key, at_height = &args.at_height)]
+    pub contract_storage_slot: Option<ContractStorageSlot>,
+}
+
+#[derive(cynic::FragmentArguments, Debug)]
+pub struct ContractStorageSlotProofArgs {
+    pub contract: ContractId,
+    pub key: Bytes32,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct SparseMerkleProof {
+    pub leaf_key: Option<Bytes32>,
+    pub leaf_data_hash: Option<Bytes32>,
+    pub proof_set: Vec<Bytes32>,
+}
+
+impl SparseMerkleProof {
+    fn verify(&self, root: &Bytes32, key: &fuel_types::Bytes32, data: Option<&[u8]>) -> bool {
+        let leaf = match (&self.leaf_key, &self.leaf_data_hash) {
+            (Some(key), Some(data_hash)) => Some((&key.0 .0, &data_hash.0 .0)),
+            (None, None) => None,
+            _ => return false,
+        };
+        let proof_set: Vec<fuel_types::Bytes32> =
+            self.proof_set.iter().map(|side| side.0 .0).collect();
+        verify_sparse_merkle_proof(&root.0 .0, key, data, leaf, &proof_set)
+    }
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct ContractStorageSlotProof {
+    pub contract: ContractId,
+    pub key: Bytes32,
+    pub value: Option<Bytes32>,
+    pub storage_root: Bytes32,
+    pub balances_root: Bytes32,
+    pub state_root: Bytes32,
+    pub block_height: U64,
+    pub slot_proof: SparseMerkleProof,
+    pub contract_proof: SparseMerkleProof,
+}
+
+impl ContractStorageSlotProof {
+    /// Verify that the slot holds `value` in the storage tree of the contract, and that the
+    /// roots of the contract are in the `state_root`.
+    pub fn verify(&self) -> bool {
+        let value = self.value.as_ref().map(|value| value.0 .0);
+        if !self.slot_proof.verify(
+            &self.storage_root,
+            &self.key.0 .0,
+            value.as_ref().map(AsRef::as_ref),
+        ) {
+            return false;
+        }
+
+        let roots = [
+            self.storage_root.0 .0.as_ref(),
+            self.balances_root.0 .0.as_ref(),
+        ]
+        .concat();
+        // contracts without any state or balances aren't in the tree
+        let empty = fuel_types::Bytes32::zeroed();
+        let roots = if self.storage_root.0 .0 != empty || self.balances_root.0 .0 != empty {
+            Some(roots.as_slice())
+        } else {
+            None
+        };
+        self.contract_proof.verify(
+            &self.state_root,
+            &fuel_types::Bytes32::new(*self.contract.0 .0),
+            roots,
+        )
+    }
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "ContractStorageSlotProofArgs"
+)]
+pub struct ContractStorageSlotProofQuery {
+    #[arguments(contract = &args.contract, key = &args.key)]
+    pub contract_storage_slot_proof: ContractStorageSlotProof,
+}
+
+#[derive(cynic::FragmentArguments, Debug)]
+pub struct ContractStorageConnectionArgs {
+    /// The contract to list the storage slots of
+    pub contract: ContractId,
+    /// Read the slots at the end of this block instead of the latest
+    pub at_height: Option<U64>,
+    /// Skip until storage key (forward pagination)
+    pub after: Option<String>,
+    /// Skip until storage key (backward pagination)
+    pub before: Option<String>,
+    /// Retrieve the first n storage slots in order (forward pagination)
+    pub first: Option<i32>,
+    /// Retrieve the last n storage slots in order (backward pagination).
+    /// Can't be used at the same time as `first`.
+    pub last: Option<i32>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct ContractStorageSlotEdge {
+    pub cursor: String,
+    pub node: ContractStorageSlot,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct ContractStorageSlotConnection {
+    pub edges: Vec<ContractStorageSlotEdge>,
+    pub page_info: PageInfo,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "ContractStorageConnectionArgs"
+)]
+pub struct ContractStorageQuery {
+    #[arguments(contract = &args.contract, after = &args.after, before = &args.before, at_height = &args.at_height, first = &args.first, last = &args.last)]
+    pub contract_storage: ContractStorageSlotConnection,
+}
+
+impl From<ContractStorageSlotConnection> for PaginatedResult<ContractStorageSlot, String> {
+    fn from(conn: ContractStorageSlotConnection) -> Self {
+        PaginatedResult {
+            has_next_page: conn.page_info.has_next_page,
+            has_previous_page: conn.page_info.has_previous_page,
+            cursor: conn.page_info.end_cursor,
+            results: conn.edges.into_iter().map(|e| e.node).collect(),
+        }
+    }
+}
+
+impl From<(ContractId, PaginationRequest<String>)> for ContractStorageConnectionArgs {
+    fn from(r: (ContractId, PaginationRequest<String>)) -> Self {
+        match r.1.direction {
+            PageDirection::Forward => ContractStorageConnectionArgs {
+                contract: r.0,
+                after: r.1.cursor,
+                before: None,
+                first: Some(r.1.results as i32),
+                last: None,
+                at_height: None,
+            },
+            PageDirection::Backward => ContractStorageConnectionArgs {
+                contract: r.0,
+                after: None,
+                before: r.1.cursor,
+                first: None,
+                last: Some(r.1.results as i32),
+                at_height: None,
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,4 +338,39 @@ mod tests {
         });
         insta::assert_snapshot!(operation.query)
     }
+
+    #[test]
+    fn contract_storage_slot_query_gql_output() {
+        use cynic::QueryBuilder;
+        let operation = ContractStorageSlotQuery::build(ContractStorageSlotArgs {
+            contract: ContractId::default(),
+            key: Bytes32::default(),
+            at_height: None,
+        });
+        insta::assert_snapshot!(operation.query)
+    }
+
+    #[test]
+    fn contract_storage_slot_proof_query_gql_output() {
+        use cynic::QueryBuilder;
+        let operation = ContractStorageSlotProofQuery::build(ContractStorageSlotProofArgs {
+            contract: ContractId::default(),
+            key: Bytes32::default(),
+        });
+        insta::assert_snapshot!(operation.query)
+    }
+
+    #[test]
+    fn contract_storage_connection_query_gql_output() {
+        use cynic::QueryBuilder;
+        let operation = ContractStorageQuery::build(ContractStorageConnectionArgs {
+            contract: ContractId::default(),
+            after: None,
+            before: None,
+            first: None,
+            last: None,
+            at_height: None,
+        });
+        insta::assert_snapshot!(operation.query)
+    }
 }
@@ -0,0 +1,126 @@
+use crate::client::schema::{
+    schema,
+    tx::{OpaqueReceipt, ReceiptType},
+    ContractId, PageInfo, TransactionId, U64,
+};
+use crate::client::{PageDirection, PaginatedResult, PaginationRequest};
+
+#[derive(cynic::InputObject, Clone, Debug, Default)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct LogFilterInput {
+    /// Contract that emitted the logs
+    pub contract: Option<ContractId>,
+    /// Either `LOG` or `LOG_DATA`
+    pub receipt_type: Option<ReceiptType>,
+    /// Value of the `ra` register of the log
+    pub ra: Option<U64>,
+    /// Value of the `rb` register of the log
+    pub rb: Option<U64>,
+    /// Lowest block height of the logs, inclusive
+    pub from_height: Option<U64>,
+    /// Highest block height of the logs, inclusive
+    pub to_height: Option<U64>,
+}
+
+#[derive(cynic::FragmentArguments, Debug)]
+pub struct LogsConnectionArgs {
+    /// Filter logs based on a filter
+    pub filter: LogFilterInput,
+    /// Skip until cursor (forward pagination)
+    pub after: Option<String>,
+    /// Skip until cursor (backward pagination)
+    pub before: Option<String>,
+    /// Retrieve the first n logs in order (forward pagination)
+    pub first: Option<i32>,
+    /// Retrieve the last n logs in order (backward pagination).
+    /// Can't be used at the same time as `first`.
+    pub last: Option<i32>,
+}
+
+impl From<(LogFilterInput, PaginationRequest<String>)> for LogsConnectionArgs {
+    fn from(r: (LogFilterInput, PaginationRequest<String>)) -> Self {
+        match r.1.direction {
+            PageDirection::Forward => LogsConnectionArgs {
+                filter: r.0,
+                after: r.1.cursor,
+                before: None,
+                first: Some(r.1.results as i32),
+                last: None,
+            },
+            PageDirection::Backward => LogsConnectionArgs {
+                filter: r.0,
+                after: None,
+                before: r.1.cursor,
+                first: None,
+                last: Some(r.1.results as i32),
+            },
+        }
+    }
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "LogsConnectionArgs"
+)]
+pub struct LogsQuery {
+    #[arguments(filter = &args.filter, after = &args.after, before = &args.before, first = &args.first, last = &args.last)]
+    pub logs: LogConnection,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct Log {
+    pub transaction_id: TransactionId,
+    pub block_height: U64,
+    pub receipt_index: U64,
+    pub receipt: OpaqueReceipt,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct LogConnection {
+    pub edges: Vec<LogEdge>,
+    pub page_info: PageInfo,
+}
+
+impl From<LogConnection> for PaginatedResult<Log, String> {
+    fn from(conn: LogConnection) -> Self {
+        PaginatedResult {
+            cursor: conn.page_info.end_cursor,
+            has_next_page: conn.page_info.has_next_page,
+            has_previous_page: conn.page_info.has_previous_page,
+            results: conn.edges.into_iter().map(|e| e.node).collect(),
+        }
+    }
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct LogEdge {
+    pub cursor: String,
+    pub node: Log,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cynic::QueryBuilder;
+
+    #[test]
+    fn logs_connection_query_gql_output() {
+        let operation = LogsQuery::build(LogsConnectionArgs {
+            filter: LogFilterInput {
+                contract: Some(ContractId::default()),
+                receipt_type: Some(ReceiptType::LogData),
+                ..Default::default()
+            },
+            after: None,
+            before: None,
+            first: None,
+            last: None,
+        });
+        insta::assert_snapshot!(operation.query)
+    }
+}
@@ -9,6 +9,7 @@ pub struct NodeInfo {
     pub min_gas_price: U64,
     pub min_byte_price: U64,
     pub max_tx: U64,
+    pub max_tx_per_owner: U64,
     pub max_depth: U64,
     pub node_version: String,
 }
@@ -1,10 +1,13 @@
 use super::block::BlockIdFragment;
+use super::coin::Coin;
+use crate::client::merkle::verify_binary_merkle_proof;
 use crate::client::schema::{
-    schema, Address, ConnectionArgs, ConversionError, HexString, PageInfo, TransactionId,
+    schema, Address, AssetId, BlockId, ConnectionArgs, ContractId, ConversionError, HexString,
+    PageInfo, TransactionId, UtxoId, U64,
 };
 use crate::client::types::TransactionResponse;
 use crate::client::{PageDirection, PaginatedResult, PaginationRequest};
-use fuel_types::bytes::Deserializable;
+use fuel_types::bytes::{Deserializable, SerializableVec};
 use fuel_types::Bytes32;
 use std::convert::{TryFrom, TryInto};
 
@@ -106,6 +109,21 @@ impl TryFrom<OpaqueReceipt> for fuel_tx::Receipt {
     }
 }
 
+#[derive(cynic::Enum, Clone, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub enum ReceiptType {
+    Call,
+    Return,
+    ReturnData,
+    Panic,
+    Revert,
+    Log,
+    LogData,
+    Transfer,
+    TransferOut,
+    ScriptResult,
+}
+
 #[derive(cynic::Enum, Copy, Clone, Debug)]
 #[cynic(schema_path = "./assets/schema.sdl")]
 pub enum ReturnType {
@@ -150,6 +168,7 @@ pub enum TransactionStatus {
     SubmittedStatus(SubmittedStatus),
     SuccessStatus(SuccessStatus),
     FailureStatus(FailureStatus),
+    SqueezedOutStatus(SqueezedOutStatus),
 }
 
 #[derive(cynic::QueryFragment, Debug)]
@@ -175,6 +194,23 @@ pub struct FailureStatus {
     pub program_state: Option<ProgramState>,
 }
 
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct SqueezedOutStatus {
+    pub reason: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Subscription",
+    argument_struct = "TxIdArgs"
+)]
+pub struct StatusChangeSubscription {
+    #[arguments(id = &args.id)]
+    pub status_change: TransactionStatus,
+}
+
 #[derive(cynic::FragmentArguments, Debug)]
 pub struct TransactionsByOwnerConnectionArgs {
     /// Select transactions based on related `owner`s
@@ -222,6 +258,151 @@ pub struct TransactionsByOwnerQuery {
     pub transactions_by_owner: TransactionConnection,
 }
 
+#[derive(cynic::FragmentArguments, Debug)]
+pub struct TransactionsByContractConnectionArgs {
+    /// Select transactions that used or created the `contract`
+    pub contract: ContractId,
+    /// Skip until cursor (forward pagination)
+    pub after: Option<String>,
+    /// Skip until cursor (backward pagination)
+    pub before: Option<String>,
+    /// Retrieve the first n transactions in order (forward pagination)
+    pub first: Option<i32>,
+    /// Retrieve the last n transactions in order (backward pagination).
+    /// Can't be used at the same time as `first`.
+    pub last: Option<i32>,
+}
+
+impl From<(ContractId, PaginationRequest<String>)> for TransactionsByContractConnectionArgs {
+    fn from(r: (ContractId, PaginationRequest<String>)) -> Self {
+        match r.1.direction {
+            PageDirection::Forward => TransactionsByContractConnectionArgs {
+                contract: r.0,
+                after: r.1.cursor,
+                before: None,
+                first: Some(r.1.results as i32),
+                last: None,
+            },
+            PageDirection::Backward => TransactionsByContractConnectionArgs {
+                contract: r.0,
+                after: None,
+                before: r.1.cursor,
+                first: None,
+                last: Some(r.1.results as i32),
+            },
+        }
+    }
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "TransactionsByContractConnectionArgs"
+)]
+pub struct TransactionsByContractQuery {
+    #[arguments(contract = &args.contract, after = &args.after, before = &args.before, first = &args.first, last = &args.last)]
+    pub transactions_by_contract: TransactionConnection,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct TransactionProof {
+    pub block_id: BlockId,
+    pub transactions_root: super::Bytes32,
+    pub index: U64,
+    pub transactions_count: U64,
+    pub proof_set: Vec<super::Bytes32>,
+}
+
+impl TransactionProof {
+    /// Verify that the proof includes `tx` into the `transactions_root`.
+    pub fn verify(&self, tx: &fuel_tx::Transaction) -> bool {
+        let proof_set: Vec<Bytes32> = self.proof_set.iter().map(|side| side.0 .0).collect();
+        verify_binary_merkle_proof(
+            &self.transactions_root.0 .0,
+            &tx.clone().to_bytes(),
+            &proof_set,
+            self.index.0,
+            self.transactions_count.0,
+        )
+    }
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "TxIdArgs"
+)]
+pub struct TransactionProofQuery {
+    #[arguments(id = &args.id)]
+    pub transaction_proof: Option<TransactionProof>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct GasEstimate {
+    pub gas_limit: U64,
+    pub gas_used: U64,
+    pub fee: U64,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "TxArg"
+)]
+pub struct EstimateGasQuery {
+    #[arguments(tx = &args.tx)]
+    pub estimate_gas: GasEstimate,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct TraceCall {
+    pub depth: i32,
+    pub call: OpaqueReceipt,
+    pub outcome: Option<OpaqueReceipt>,
+    pub gas_used: Option<U64>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct TraceRegister {
+    pub index: i32,
+    pub value: U64,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct TraceStep {
+    pub contract: ContractId,
+    pub pc: U64,
+    pub instruction: String,
+    pub registers: Vec<TraceRegister>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct ExecutionTrace {
+    pub gas_used: U64,
+    pub calls: Vec<TraceCall>,
+    pub steps: Vec<TraceStep>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "TxIdArgs"
+)]
+pub struct TransactionTraceQuery {
+    #[arguments(id = &args.id)]
+    pub transaction_trace: Option<ExecutionTrace>,
+}
+
 // mutations
 
 #[derive(cynic::FragmentArguments)]
@@ -246,6 +427,123 @@ pub struct DryRun {
     pub dry_run: Vec<OpaqueReceipt>,
 }
 
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct ContractBalanceChange {
+    pub contract: ContractId,
+    pub asset_id: AssetId,
+    pub before: U64,
+    pub after: U64,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct StorageSlotChange {
+    pub contract: ContractId,
+    pub key: super::Bytes32,
+    pub before: Option<super::Bytes32>,
+    pub after: Option<super::Bytes32>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct DryRunStateDiff {
+    pub receipts: Vec<OpaqueReceipt>,
+    pub spent_coins: Vec<Coin>,
+    pub created_coins: Vec<Coin>,
+    pub contract_balance_changes: Vec<ContractBalanceChange>,
+    pub storage_slot_changes: Vec<StorageSlotChange>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Mutation",
+    argument_struct = "DryRunArg"
+)]
+pub struct DryRunStateDiffMutation {
+    #[arguments(tx = &args.tx, utxo_validation = &args.utxo_validation)]
+    pub dry_run_state_diff: DryRunStateDiff,
+}
+
+#[derive(cynic::FragmentArguments)]
+pub struct DryRunTraceArg {
+    pub tx: HexString,
+    pub utxo_validation: Option<bool>,
+    pub instructions: Option<bool>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Mutation",
+    argument_struct = "DryRunTraceArg"
+)]
+pub struct DryRunTrace {
+    #[arguments(tx = &args.tx, utxo_validation = &args.utxo_validation, instructions = &args.instructions)]
+    pub dry_run_trace: ExecutionTrace,
+}
+
+#[derive(cynic::InputObject, Clone, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct CoinOverrideInput {
+    pub utxo_id: UtxoId,
+    pub owner: Address,
+    pub amount: U64,
+    pub asset_id: AssetId,
+    pub maturity: Option<U64>,
+}
+
+#[derive(cynic::InputObject, Clone, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct ContractBalanceOverrideInput {
+    pub contract: ContractId,
+    pub asset_id: AssetId,
+    pub amount: U64,
+}
+
+#[derive(cynic::InputObject, Clone, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct StorageSlotOverrideInput {
+    pub contract: ContractId,
+    pub key: super::Bytes32,
+    pub value: super::Bytes32,
+}
+
+/// Temporary changes to the state a dry run is executed against
+#[derive(cynic::InputObject, Clone, Debug, Default)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct DryRunOverridesInput {
+    pub coins: Option<Vec<CoinOverrideInput>>,
+    pub contract_balances: Option<Vec<ContractBalanceOverrideInput>>,
+    pub storage_slots: Option<Vec<StorageSlotOverrideInput>>,
+}
+
+#[derive(cynic::FragmentArguments)]
+pub struct DryRunBundleArg {
+    pub txs: Vec<HexString>,
+    pub utxo_validation: Option<bool>,
+    pub overrides: Option<DryRunOverridesInput>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct DryRunResult {
+    pub id: TransactionId,
+    pub receipts: Vec<OpaqueReceipt>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Mutation",
+    argument_struct = "DryRunBundleArg"
+)]
+pub struct DryRunBundle {
+    #[arguments(txs = &args.txs, utxo_validation = &args.utxo_validation, overrides = &args.overrides)]
+    pub dry_run_bundle: Vec<DryRunResult>,
+}
+
 #[derive(cynic::QueryFragment, Debug)]
 #[cynic(
     schema_path = "./assets/schema.sdl",
@@ -261,7 +559,6 @@ pub struct Submit {
 pub mod tests {
     use super::*;
     use crate::client::schema::Bytes;
-    use fuel_types::bytes::SerializableVec;
 
     pub mod transparent_receipt;
     pub mod transparent_tx;
@@ -275,6 +572,15 @@ pub mod tests {
         insta::assert_snapshot!(operation.query)
     }
 
+    #[test]
+    fn transaction_proof_query_gql_output() {
+        use cynic::QueryBuilder;
+        let operation = TransactionProofQuery::build(TxIdArgs {
+            id: TransactionId::default(),
+        });
+        insta::assert_snapshot!(operation.query)
+    }
+
     #[test]
     fn opaque_transaction_by_id_query_gql_output() {
         use cynic::QueryBuilder;
@@ -309,6 +615,49 @@ pub mod tests {
         insta::assert_snapshot!(operation.query)
     }
 
+    #[test]
+    fn transactions_by_contract_gql_output() {
+        use cynic::QueryBuilder;
+        let operation = TransactionsByContractQuery::build(TransactionsByContractConnectionArgs {
+            contract: Default::default(),
+            after: None,
+            before: None,
+            first: None,
+            last: None,
+        });
+        insta::assert_snapshot!(operation.query)
+    }
+
+    #[test]
+    fn status_change_subscription_gql_output() {
+        use cynic::SubscriptionBuilder;
+        let operation = StatusChangeSubscription::build(TxIdArgs {
+            id: TransactionId::default(),
+        });
+        insta::assert_snapshot!(serde_json::to_value(&operation).unwrap()["query"]
+            .as_str()
+            .unwrap())
+    }
+
+    #[test]
+    fn transaction_trace_query_gql_output() {
+        use cynic::QueryBuilder;
+        let operation = TransactionTraceQuery::build(TxIdArgs {
+            id: TransactionId::default(),
+        });
+        insta::assert_snapshot!(operation.query)
+    }
+
+    #[test]
+    fn estimate_gas_query_gql_output() {
+        use cynic::QueryBuilder;
+        let mut tx = fuel_tx::Transaction::default();
+        let query = EstimateGasQuery::build(TxArg {
+            tx: HexString(Bytes(tx.to_bytes())),
+        });
+        insta::assert_snapshot!(query.query)
+    }
+
     #[test]
     fn dry_run_tx_gql_output() {
         use cynic::MutationBuilder;
@@ -320,6 +669,41 @@ pub mod tests {
         insta::assert_snapshot!(query.query)
     }
 
+    #[test]
+    fn dry_run_state_diff_gql_output() {
+        use cynic::MutationBuilder;
+        let mut tx = fuel_tx::Transaction::default();
+        let query = DryRunStateDiffMutation::build(DryRunArg {
+            tx: HexString(Bytes(tx.to_bytes())),
+            utxo_validation: None,
+        });
+        insta::assert_snapshot!(query.query)
+    }
+
+    #[test]
+    fn dry_run_trace_gql_output() {
+        use cynic::MutationBuilder;
+        let mut tx = fuel_tx::Transaction::default();
+        let query = DryRunTrace::build(DryRunTraceArg {
+            tx: HexString(Bytes(tx.to_bytes())),
+            utxo_validation: None,
+            instructions: Some(true),
+        });
+        insta::assert_snapshot!(query.query)
+    }
+
+    #[test]
+    fn dry_run_bundle_gql_output() {
+        use cynic::MutationBuilder;
+        let mut tx = fuel_tx::Transaction::default();
+        let query = DryRunBundle::build(DryRunBundleArg {
+            txs: vec![HexString(Bytes(tx.to_bytes()))],
+            utxo_validation: None,
+            overrides: Some(Default::default()),
+        });
+        insta::assert_snapshot!(query.query)
+    }
+
     #[test]
     fn submit_tx_gql_output() {
         use cynic::MutationBuilder;
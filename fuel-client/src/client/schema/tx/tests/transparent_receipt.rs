@@ -1,3 +1,4 @@
+use crate::client::schema::tx::ReceiptType;
 use crate::client::schema::{
     contract::ContractIdFragment, schema, Address, AssetId, Bytes32, ConversionError,
     ConversionError::MissingField, HexString, U64,
@@ -32,21 +33,6 @@ pub struct Receipt {
     pub data: Option<HexString>,
 }
 
-#[derive(cynic::Enum, Clone, Copy, Debug)]
-#[cynic(schema_path = "./assets/schema.sdl")]
-pub enum ReceiptType {
-    Call,
-    Return,
-    ReturnData,
-    Panic,
-    Revert,
-    Log,
-    LogData,
-    Transfer,
-    TransferOut,
-    ScriptResult,
-}
-
 impl TryFrom<Receipt> for fuel_vm::prelude::Receipt {
     type Error = ConversionError;
 
@@ -0,0 +1,136 @@
+use crate::client::schema::{
+    schema, tx::OpaqueTransaction, tx::TxIdArgs, ConnectionArgs, DateTime, PageInfo, TransactionId,
+    U64,
+};
+use crate::client::PaginatedResult;
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct TxPoolStats {
+    pub count: U64,
+    pub total_bytes: U64,
+    pub min_gas_price: Option<U64>,
+    pub max_gas_price: Option<U64>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "TxPool")]
+pub struct TxPoolStatsFragment {
+    pub stats: TxPoolStats,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "Query")]
+pub struct TxPoolStatsQuery {
+    pub txpool: TxPoolStatsFragment,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct PendingTransaction {
+    pub id: TransactionId,
+    pub transaction: OpaqueTransaction,
+    pub gas_price: U64,
+    pub byte_price: U64,
+    pub submitted_time: DateTime,
+    pub dependencies: Vec<TransactionId>,
+    pub dependents: Vec<TransactionId>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "TxPool",
+    argument_struct = "TxIdArgs"
+)]
+pub struct TxPoolTransactionFragment {
+    #[arguments(id = &args.id)]
+    pub transaction: Option<PendingTransaction>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "TxIdArgs"
+)]
+pub struct PendingTransactionQuery {
+    pub txpool: TxPoolTransactionFragment,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "TxPool",
+    argument_struct = "ConnectionArgs"
+)]
+pub struct TxPoolTransactionsFragment {
+    #[arguments(after = &args.after, before = &args.before, first = &args.first, last = &args.last)]
+    pub transactions: PendingTransactionConnection,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    argument_struct = "ConnectionArgs"
+)]
+pub struct PendingTransactionsQuery {
+    pub txpool: TxPoolTransactionsFragment,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct PendingTransactionConnection {
+    pub edges: Vec<PendingTransactionEdge>,
+    pub page_info: PageInfo,
+}
+
+impl From<PendingTransactionConnection> for PaginatedResult<PendingTransaction, String> {
+    fn from(conn: PendingTransactionConnection) -> Self {
+        PaginatedResult {
+            cursor: conn.page_info.end_cursor,
+            has_next_page: conn.page_info.has_next_page,
+            has_previous_page: conn.page_info.has_previous_page,
+            results: conn.edges.into_iter().map(|e| e.node).collect(),
+        }
+    }
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl")]
+pub struct PendingTransactionEdge {
+    pub cursor: String,
+    pub node: PendingTransaction,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cynic::QueryBuilder;
+
+    #[test]
+    fn txpool_stats_query_gql_output() {
+        let operation = TxPoolStatsQuery::build(());
+        insta::assert_snapshot!(operation.query)
+    }
+
+    #[test]
+    fn pending_transaction_by_id_query_gql_output() {
+        let operation = PendingTransactionQuery::build(TxIdArgs {
+            id: TransactionId::default(),
+        });
+        insta::assert_snapshot!(operation.query)
+    }
+
+    #[test]
+    fn pending_transactions_connection_query_gql_output() {
+        let operation = PendingTransactionsQuery::build(ConnectionArgs {
+            after: None,
+            before: None,
+            first: None,
+            last: None,
+        });
+        insta::assert_snapshot!(operation.query)
+    }
+}
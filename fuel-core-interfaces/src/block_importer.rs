@@ -38,6 +38,15 @@ pub enum ImportBlockBroadcast {
     },
 }
 
+impl ImportBlockBroadcast {
+    pub fn block(&self) -> &FuelBlock {
+        match self {
+            Self::PendingBlockImported { block } => block,
+            Self::SealedFuelBlockImported { block, .. } => &block.block,
+        }
+    }
+}
+
 pub enum ImportBlockMpsc {
     ImportSealedFuelBlock {
         block: Arc<SealedFuelBlock>,
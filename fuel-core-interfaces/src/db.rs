@@ -1,3 +1,4 @@
+use crate::model::BlockHeight;
 use std::io::ErrorKind;
 
 use fuel_vm::prelude::InterpreterError;
@@ -14,6 +15,13 @@ pub enum Error {
     ChainUninitialized,
     #[error("Invalid database version")]
     InvalidDatabaseVersion,
+    #[error("Can't roll back to height {height}, the chain is at height {current}")]
+    RollbackAboveChainHeight {
+        height: BlockHeight,
+        current: BlockHeight,
+    },
+    #[error("No undo log is stored for block {0}")]
+    MissingUndoLog(BlockHeight),
     #[error("error occurred in the underlying datastore `{0}`")]
     DatabaseError(Box<dyn std::error::Error + Send + Sync>),
 }
@@ -728,6 +736,10 @@ pub mod helpers {
         fn contract_exist(&self, contract_id: ContractId) -> Result<bool, Error> {
             Ok(self.data.lock().contract.get(&contract_id).is_some())
         }
+
+        fn current_block_height(&self) -> Result<BlockHeight, Error> {
+            Ok(self.data.lock().chain_height)
+        }
     }
 
     // token deposit. Used by relayer.
@@ -2,12 +2,14 @@ pub mod bft;
 pub mod block_importer;
 pub mod block_producer;
 pub mod db;
+pub mod merkle;
 pub mod model;
 pub mod p2p;
 pub mod relayer;
 pub mod signer;
 pub mod sync;
 pub mod txpool;
+pub mod validation;
 
 pub mod common {
     #[doc(no_inline)]
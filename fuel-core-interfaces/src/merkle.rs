@@ -0,0 +1,31 @@
+//! Node hashes of the Merkle trees committed to in block headers, shared by the node building
+//! the trees and the clients verifying proofs against them.
+
+use fuel_crypto::Hasher;
+use fuel_types::Bytes32;
+
+const LEAF: u8 = 0x00;
+const NODE: u8 = 0x01;
+
+/// Hash of a leaf of a binary Merkle tree holding `data`.
+pub fn leaf_sum(data: &[u8]) -> Bytes32 {
+    Hasher::default().chain([LEAF]).chain(data).finalize()
+}
+
+/// Hash of an internal node of a binary or sparse Merkle tree.
+pub fn node_sum(lhs: &Bytes32, rhs: &Bytes32) -> Bytes32 {
+    Hasher::default()
+        .chain([NODE])
+        .chain(lhs)
+        .chain(rhs)
+        .finalize()
+}
+
+/// Hash of a leaf of a sparse Merkle tree mapping `key` to data hashing to `data_hash`.
+pub fn sparse_leaf_sum(key: &Bytes32, data_hash: &Bytes32) -> Bytes32 {
+    Hasher::default()
+        .chain([LEAF])
+        .chain(key)
+        .chain(data_hash)
+        .finalize()
+}
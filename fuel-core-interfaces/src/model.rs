@@ -2,6 +2,7 @@ mod block;
 mod block_height;
 mod coin;
 mod deposit_coin;
+mod parameter_upgrade;
 mod txpool;
 mod vote;
 
@@ -10,6 +11,7 @@ pub use block_height::BlockHeight;
 pub use coin::{Coin, CoinStatus};
 pub use deposit_coin::DepositCoin;
 use fuel_types::{Address, Bytes32};
+pub use parameter_upgrade::ParameterUpgrade;
 pub use txpool::{ArcTx, TxInfo};
 pub use vote::Vote;
 
@@ -25,6 +25,10 @@ pub struct FuelBlockHeader {
     pub prev_root: Bytes32,
     /// Merkle root of transactions.
     pub transactions_root: Bytes32,
+    /// Sparse merkle root of the state and balances of every contract after the block.
+    pub state_root: Bytes32,
+    /// Fees paid by the transactions of the block, credited to the producer.
+    pub fees: Word,
     /// The block producer time
     pub time: DateTime<Utc>,
     /// The block producer public key
@@ -50,6 +54,8 @@ impl FuelBlockHeader {
         hasher.input(self.parent_hash.as_ref());
         hasher.input(self.prev_root.as_ref());
         hasher.input(self.transactions_root.as_ref());
+        hasher.input(self.state_root.as_ref());
+        hasher.input(self.fees.to_be_bytes());
         hasher.input(self.time.timestamp_millis().to_be_bytes());
         hasher.input(self.producer.as_ref());
         hasher.digest()
@@ -73,6 +79,8 @@ impl Default for FuelBlockHeader {
             parent_hash: Bytes32::default(),
             prev_root: Bytes32::default(),
             transactions_root: Bytes32::default(),
+            state_root: Bytes32::default(),
+            fees: 0,
             producer: Address::default(),
             metadata: None,
         }
@@ -113,13 +121,24 @@ impl FuelBlock {
         }
     }
 
-    /* TODO for functions bellow, they are mostly going to be removed
-    https://github.com/FuelLabs/fuel-core/issues/364 */
+    /// The sum of the gas limits of the transactions.
+    pub fn transactions_gas_limit(&self) -> Word {
+        self.transactions
+            .iter()
+            .fold(0, |gas, tx| gas.saturating_add(tx.gas_limit()))
+    }
 
-    pub fn transaction_data_lenght(&self) -> usize {
-        self.transactions.len() * 100
+    /// The sum of the serialized sizes of the transactions, excluding witness data, the same as
+    /// they are metered for byte fees.
+    pub fn transactions_size(&self) -> u64 {
+        self.transactions.iter().fold(0, |size, tx| {
+            size.saturating_add(tx.metered_bytes_size() as u64)
+        })
     }
 
+    /* TODO for functions bellow, they are mostly going to be removed
+    https://github.com/FuelLabs/fuel-core/issues/364 */
+
     pub fn transaction_data_hash(&self) -> Bytes32 {
         Bytes32::zeroed()
     }
@@ -0,0 +1,93 @@
+use crate::model::BlockHeight;
+use fuel_tx::ConsensusParameters;
+
+/// New consensus parameters and feature switches that take effect from a block height on.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterUpgrade {
+    /// The height of the first block the upgrade applies to.
+    pub height: BlockHeight,
+    /// Replaces the consensus parameters, which are kept as they are if not set.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub transaction_parameters: Option<ConsensusParameters>,
+    /// Enables or disables predicate inputs, which are kept as they are if not set.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub predicates: Option<bool>,
+}
+
+impl ParameterUpgrade {
+    /// The consensus parameters active at `height` under the `upgrades` schedule, or `initial`
+    /// if no upgrade up to `height` replaces them.
+    pub fn transaction_parameters_at(
+        upgrades: &[Self],
+        height: BlockHeight,
+        initial: ConsensusParameters,
+    ) -> ConsensusParameters {
+        Self::active_at(upgrades, height, |upgrade| upgrade.transaction_parameters)
+            .unwrap_or(initial)
+    }
+
+    /// Whether predicates are enabled at `height` under the `upgrades` schedule, or `initial` if
+    /// no upgrade up to `height` switches them.
+    pub fn predicates_at(upgrades: &[Self], height: BlockHeight, initial: bool) -> bool {
+        Self::active_at(upgrades, height, |upgrade| upgrade.predicates).unwrap_or(initial)
+    }
+
+    /// The value set by the last upgrade up to `height` that sets it. Upgrades don't need to be
+    /// sorted, later entries win over earlier ones at the same height.
+    fn active_at<T>(
+        upgrades: &[Self],
+        height: BlockHeight,
+        value: impl Fn(&Self) -> Option<T>,
+    ) -> Option<T> {
+        upgrades
+            .iter()
+            .filter(|upgrade| upgrade.height <= height)
+            .filter_map(|upgrade| value(upgrade).map(|value| (upgrade.height, value)))
+            .max_by_key(|(height, _)| **height)
+            .map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upgrades_apply_from_their_height() {
+        let params = |max_inputs| ConsensusParameters {
+            max_inputs,
+            ..ConsensusParameters::DEFAULT
+        };
+        let upgrades = vec![
+            ParameterUpgrade {
+                height: 20u32.into(),
+                transaction_parameters: Some(params(3)),
+                predicates: None,
+            },
+            ParameterUpgrade {
+                height: 10u32.into(),
+                transaction_parameters: Some(params(2)),
+                predicates: Some(true),
+            },
+            ParameterUpgrade {
+                height: 30u32.into(),
+                transaction_parameters: None,
+                predicates: Some(false),
+            },
+        ];
+        let initial = params(1);
+
+        let active = |height: u32| {
+            (
+                ParameterUpgrade::transaction_parameters_at(&upgrades, height.into(), initial)
+                    .max_inputs,
+                ParameterUpgrade::predicates_at(&upgrades, height.into(), false),
+            )
+        };
+        assert_eq!(active(9), (1, false));
+        assert_eq!(active(10), (2, true));
+        assert_eq!(active(25), (3, true));
+        assert_eq!(active(30), (3, false));
+    }
+}
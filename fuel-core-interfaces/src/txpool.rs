@@ -1,12 +1,13 @@
 use crate::{
     db::{Error as DbStateError, KvStoreError},
-    model::Coin,
     model::TxInfo,
+    model::{BlockHeight, Coin},
 };
 use derive_more::{Deref, DerefMut};
 use fuel_storage::Storage;
-use fuel_tx::{ContractId, UtxoId};
+use fuel_tx::{Address, ContractId, UtxoId};
 use fuel_tx::{Transaction, TxId};
+use fuel_types::Word;
 use fuel_vm::prelude::Contract;
 use std::sync::Arc;
 use thiserror::Error;
@@ -25,6 +26,9 @@ pub trait TxPoolDb:
     fn contract_exist(&self, contract_id: ContractId) -> Result<bool, DbStateError> {
         Storage::<ContractId, Contract>::contains_key(self, &contract_id)
     }
+
+    /// Height of the latest block, used to check maturity of submitted transactions.
+    fn current_block_height(&self) -> Result<BlockHeight, DbStateError>;
 }
 
 #[derive(Clone, Deref, DerefMut)]
@@ -44,6 +48,12 @@ impl Sender {
         receiver.await.map_err(Into::into)
     }
 
+    pub async fn pending(&self) -> Result<Vec<TxInfo>, anyhow::Error> {
+        let (response, receiver) = oneshot::channel();
+        let _ = self.send(TxPoolMpsc::Pending { response }).await;
+        receiver.await.map_err(Into::into)
+    }
+
     pub async fn find(&self, ids: Vec<TxId>) -> Result<Vec<Option<TxInfo>>, anyhow::Error> {
         let (response, receiver) = oneshot::channel();
         let _ = self.send(TxPoolMpsc::Find { ids, response }).await;
@@ -79,10 +89,41 @@ impl Sender {
         receiver.await.map_err(Into::into)
     }
 
-    pub async fn remove(&self, ids: Vec<TxId>) -> Result<(), anyhow::Error> {
-        self.send(TxPoolMpsc::Remove { ids })
-            .await
-            .map_err(Into::into)
+    pub async fn includable_within(
+        &self,
+        max_gas: Word,
+        max_bytes: usize,
+    ) -> Result<Vec<Arc<Transaction>>, anyhow::Error> {
+        let (response, receiver) = oneshot::channel();
+        let _ = self
+            .send(TxPoolMpsc::IncludableWithin {
+                max_gas,
+                max_bytes,
+                response,
+            })
+            .await;
+        receiver.await.map_err(Into::into)
+    }
+
+    /// Remove transactions and their dependents from the pool, returning everything removed
+    /// once the pool has been updated.
+    pub async fn remove(&self, ids: Vec<TxId>) -> Result<Vec<Arc<Transaction>>, anyhow::Error> {
+        let (response, receiver) = oneshot::channel();
+        let _ = self.send(TxPoolMpsc::Remove { ids, response }).await;
+        receiver.await.map_err(Into::into)
+    }
+
+    /// Remove transactions that were executed in a block, returning the removed ones. The
+    /// transactions depending on them stay in the pool.
+    pub async fn remove_committed(
+        &self,
+        ids: Vec<TxId>,
+    ) -> Result<Vec<Arc<Transaction>>, anyhow::Error> {
+        let (response, receiver) = oneshot::channel();
+        let _ = self
+            .send(TxPoolMpsc::RemoveCommitted { ids, response })
+            .await;
+        receiver.await.map_err(Into::into)
     }
 }
 
@@ -93,6 +134,13 @@ pub enum TxPoolMpsc {
     Includable {
         response: oneshot::Sender<Vec<Arc<Transaction>>>,
     },
+    /// Return transactions for the next block that fit inside `max_gas` and `max_bytes`,
+    /// with parents always placed before their children.
+    IncludableWithin {
+        max_gas: Word,
+        max_bytes: usize,
+        response: oneshot::Sender<Vec<Arc<Transaction>>>,
+    },
     /// import list of transaction into txpool. All needed parents need to be known
     /// and parent->child order should be enforced in Vec, we will not do that check inside
     /// txpool and will just drop child and include only parent. Additional restrain is that
@@ -103,6 +151,10 @@ pub enum TxPoolMpsc {
         txs: Vec<Arc<Transaction>>,
         response: oneshot::Sender<Vec<anyhow::Result<Vec<Arc<Transaction>>>>>,
     },
+    /// Return all transactions inside the pool sorted by gas price, highest first.
+    Pending {
+        response: oneshot::Sender<Vec<TxInfo>>,
+    },
     /// find all tx by their hash
     Find {
         ids: Vec<TxId>,
@@ -119,7 +171,15 @@ pub enum TxPoolMpsc {
         response: oneshot::Sender<Vec<Arc<Transaction>>>,
     },
     /// remove transaction from pool needed on user demand. Low priority
-    Remove { ids: Vec<TxId> },
+    Remove {
+        ids: Vec<TxId>,
+        response: oneshot::Sender<Vec<Arc<Transaction>>>,
+    },
+    /// Remove transactions that were executed in a block, keeping their dependents.
+    RemoveCommitted {
+        ids: Vec<TxId>,
+        response: oneshot::Sender<Vec<Arc<Transaction>>>,
+    },
     /// Iterete over `hashes` and return all hashes that we dont have.
     /// Needed when we receive list of new hashed from peer with
     /// **BroadcastTransactionHashes**, so txpool needs to return
@@ -155,6 +215,8 @@ pub enum Error {
     NotInsertedTxKnown,
     #[error("Transaction is not inserted. Pool limit is hit, try to increase gas_price")]
     NotInsertedLimitHit,
+    #[error("Transaction is not inserted. Pool limit of transactions for owner {0:#x} is hit")]
+    NotInsertedOwnerLimitHit(Address),
     #[error("TxPool required that transaction contains metadata")]
     NoMetadata,
     #[error("Transaction is not inserted. The gas price is too low.")]
@@ -200,4 +262,8 @@ pub enum Error {
     // small todo for now it can pass but in future we should include better messages
     #[error("Transaction removed.")]
     Removed,
+    #[error("Transaction expired because it exceeded the configured time to live.")]
+    Expired,
+    #[error("Transaction removed. It isn't valid under the upgraded consensus parameters.")]
+    InvalidatedByUpgrade,
 }
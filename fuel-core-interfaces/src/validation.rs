@@ -0,0 +1,256 @@
+//! Transaction validity checks shared by the executor and the txpool, so transactions
+//! are rejected on submission for the same reasons they would be rejected in a block.
+
+use crate::{
+    db::KvStoreError,
+    model::{BlockHeight, Coin, CoinStatus},
+};
+use fuel_storage::Storage;
+use fuel_tx::{
+    AssetId, ConsensusParameters, Input, Output, Transaction, TxId, UtxoId, ValidationError,
+};
+use fuel_types::Word;
+use fuel_vm::prelude::{Interpreter, PredicateStorage};
+use std::error::Error as StdError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum TransactionValidityError {
+    #[error("Coin input was already spent")]
+    CoinAlreadySpent(UtxoId),
+    #[error("Coin has not yet reached maturity")]
+    CoinHasNotMatured(UtxoId),
+    #[error("The specified coin doesn't exist")]
+    CoinDoesntExist(UtxoId),
+    #[error("Contract output index isn't valid: {0:#x}")]
+    InvalidContractInputIndex(UtxoId),
+    #[error("The transaction must have at least one coin input type: {0:#x}")]
+    NoCoinInput(TxId),
+    #[error("The transaction contains predicate inputs which aren't enabled: {0:#x}")]
+    PredicateExecutionDisabled(TxId),
+    #[error(
+        "The transaction contains a predicate which failed to validate: TransactionId({0:#x})"
+    )]
+    InvalidPredicate(TxId),
+    #[error("Transaction doesn't include enough value to pay for gas: {provided} < {required}")]
+    InsufficientFeeAmount { provided: Word, required: Word },
+    #[error("The computed fee caused an integer overflow")]
+    FeeOverflow,
+    #[error("Transaction validity: {0:#?}")]
+    Validation(#[from] ValidationError),
+    #[error("Datastore error occurred")]
+    DataStoreError(Box<dyn StdError + Send + Sync>),
+}
+
+impl From<KvStoreError> for TransactionValidityError {
+    fn from(e: KvStoreError) -> Self {
+        Self::DataStoreError(Box::new(e))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TransactionValidator {
+    params: ConsensusParameters,
+    utxo_validation: bool,
+    predicates: bool,
+}
+
+impl TransactionValidator {
+    pub fn new(params: ConsensusParameters, utxo_validation: bool, predicates: bool) -> Self {
+        Self {
+            params,
+            utxo_validation,
+            predicates,
+        }
+    }
+
+    pub fn utxo_validation(&self) -> bool {
+        self.utxo_validation
+    }
+
+    /// Run all checks that the executor does before a transaction is executed
+    /// in a block at `block_height`. The consensus parameter limits aren't part of them, the
+    /// txpool checks those on submission with [`Self::verify_parameters`].
+    pub fn validate<D>(
+        &self,
+        db: &D,
+        tx: &Transaction,
+        block_height: BlockHeight,
+    ) -> Result<(), TransactionValidityError>
+    where
+        D: Storage<UtxoId, Coin, Error = KvStoreError>,
+    {
+        self.verify_predicates(tx)?;
+        if self.utxo_validation {
+            self.verify_has_at_least_one_coin(tx)?;
+            self.verify_input_state(db, tx, block_height)?;
+            self.verify_signatures(tx)?;
+        }
+        self.verify_fee(tx)
+    }
+
+    /// Verify all the predicates of a tx.
+    pub fn verify_predicates(&self, tx: &Transaction) -> Result<(), TransactionValidityError> {
+        // fail if tx contains any predicates when predicates are disabled
+        if !self.predicates {
+            let has_predicate = tx.inputs().iter().any(|input| input.is_coin_predicate());
+            if has_predicate {
+                return Err(TransactionValidityError::PredicateExecutionDisabled(
+                    tx.id(),
+                ));
+            }
+        } else {
+            // otherwise attempt to validate any predicates if the feature flag is enabled
+            if !Interpreter::<PredicateStorage>::check_predicates(tx.clone(), self.params) {
+                return Err(TransactionValidityError::InvalidPredicate(tx.id()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify the transaction has at least one coin.
+    ///
+    /// TODO: This verification really belongs in fuel-tx, and can be removed once
+    ///       https://github.com/FuelLabs/fuel-tx/issues/118 is resolved.
+    pub fn verify_has_at_least_one_coin(
+        &self,
+        tx: &Transaction,
+    ) -> Result<(), TransactionValidityError> {
+        if tx.inputs().iter().filter(|input| input.is_coin()).count() == 0 {
+            Err(TransactionValidityError::NoCoinInput(tx.id()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Verify that all coin inputs exist in `db` and can be spent at `block_height`.
+    pub fn verify_input_state<D>(
+        &self,
+        db: &D,
+        tx: &Transaction,
+        block_height: BlockHeight,
+    ) -> Result<(), TransactionValidityError>
+    where
+        D: Storage<UtxoId, Coin, Error = KvStoreError>,
+    {
+        for input in tx.inputs() {
+            match input {
+                Input::CoinSigned { utxo_id, .. } | Input::CoinPredicate { utxo_id, .. } => {
+                    if let Some(coin) = Storage::<UtxoId, Coin>::get(db, utxo_id)? {
+                        self.verify_coin(utxo_id, &coin, block_height)?;
+                    } else {
+                        return Err(TransactionValidityError::CoinDoesntExist(*utxo_id));
+                    }
+                }
+                Input::Contract { .. } => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify that `coin` is unspent and has reached maturity at `block_height`.
+    pub fn verify_coin(
+        &self,
+        utxo_id: &UtxoId,
+        coin: &Coin,
+        block_height: BlockHeight,
+    ) -> Result<(), TransactionValidityError> {
+        if coin.status == CoinStatus::Spent {
+            return Err(TransactionValidityError::CoinAlreadySpent(*utxo_id));
+        }
+        if block_height < coin.block_created + coin.maturity {
+            return Err(TransactionValidityError::CoinHasNotMatured(*utxo_id));
+        }
+        Ok(())
+    }
+
+    pub fn verify_signatures(&self, tx: &Transaction) -> Result<(), TransactionValidityError> {
+        tx.validate_input_signature().map_err(Into::into)
+    }
+
+    /// Verify the transaction against the limits of the consensus parameters.
+    pub fn verify_parameters(
+        &self,
+        tx: &Transaction,
+        block_height: BlockHeight,
+    ) -> Result<(), TransactionValidityError> {
+        tx.validate_without_signature(block_height.into(), &self.params)
+            .map_err(Into::into)
+    }
+
+    /// Verify that the transaction has enough gas to cover fees.
+    pub fn verify_fee(&self, tx: &Transaction) -> Result<(), TransactionValidityError> {
+        if tx.gas_price() != 0 || tx.byte_price() != 0 {
+            let gas = base_asset_inputs(tx);
+            let gas_fees = ((tx.gas_limit() * tx.gas_price()) as f64
+                / self.params.gas_price_factor as f64)
+                .ceil() as u64;
+            let total_gas_required = base_asset_outputs(tx)
+                .checked_add(self.byte_fees(tx))
+                .ok_or(TransactionValidityError::FeeOverflow)?
+                .checked_add(gas_fees)
+                .ok_or(TransactionValidityError::FeeOverflow)?;
+            gas.checked_sub(total_gas_required).ok_or(
+                TransactionValidityError::InsufficientFeeAmount {
+                    provided: gas,
+                    required: total_gas_required,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// The largest gas limit the base asset inputs of the transaction can pay for at its gas
+    /// price, after its coin outputs and byte fees. `None` if its gas is free.
+    pub fn max_affordable_gas(&self, tx: &Transaction) -> Option<Word> {
+        if tx.gas_price() == 0 {
+            return None;
+        }
+        let budget = base_asset_inputs(tx)
+            .saturating_sub(base_asset_outputs(tx))
+            .saturating_sub(self.byte_fees(tx));
+        let gas = budget as u128 * self.params.gas_price_factor as u128 / tx.gas_price() as u128;
+        Some(gas.try_into().unwrap_or(Word::MAX))
+    }
+
+    fn byte_fees(&self, tx: &Transaction) -> Word {
+        ((tx.metered_bytes_size() as Word * tx.byte_price()) as f64
+            / self.params.gas_price_factor as f64)
+            .ceil() as u64
+    }
+}
+
+/// The amount of the base asset the coin inputs of the transaction hold.
+fn base_asset_inputs(tx: &Transaction) -> Word {
+    tx.inputs()
+        .iter()
+        .filter_map(|input| match input {
+            Input::CoinSigned {
+                amount, asset_id, ..
+            } if asset_id == &AssetId::default() => Some(amount),
+            Input::CoinPredicate {
+                amount, asset_id, ..
+            } if asset_id == &AssetId::default() => Some(amount),
+            _ => None,
+        })
+        .sum()
+}
+
+/// The amount of the base asset the coin and withdrawal outputs of the transaction spend.
+fn base_asset_outputs(tx: &Transaction) -> Word {
+    tx.outputs()
+        .iter()
+        .filter_map(|output| match output {
+            Output::Coin {
+                amount, asset_id, ..
+            } if asset_id == &AssetId::default() => Some(amount),
+            Output::Withdrawal {
+                amount, asset_id, ..
+            } if asset_id == &AssetId::default() => Some(amount),
+            _ => None,
+        })
+        .sum()
+}
@@ -1,6 +1,8 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use fuel_core::config::{Config, DbType, VMConfig};
+use fuel_core_interfaces::common::fuel_tx::Address;
 use std::str::FromStr;
+use std::time::Duration;
 use std::{env, io, net, path::PathBuf};
 use strum::VariantNames;
 use tracing_subscriber::filter::EnvFilter;
@@ -45,6 +47,11 @@ pub struct Opt {
     #[clap(long = "vm-backtrace")]
     pub vm_backtrace: bool,
 
+    /// Record the call tree of every executed transaction, available from the
+    /// `transactionTrace` query
+    #[clap(long = "store-transaction-traces")]
+    pub store_transaction_traces: bool,
+
     /// Enable full utxo stateful validation
     /// disabled by default until downstream consumers stabilize
     #[clap(long = "utxo-validation")]
@@ -58,47 +65,44 @@ pub struct Opt {
     #[clap(long = "min-byte-price", default_value = "0")]
     pub min_byte_price: u64,
 
+    /// The max time in seconds a transaction can stay inside the txpool before it is pruned
+    #[clap(long = "tx-pool-ttl", default_value = "300")]
+    pub tx_pool_ttl: u64,
+
+    /// The max number of txpool transactions spending coins of a single owner
+    #[clap(long = "tx-pool-max-tx-per-owner", default_value = "64")]
+    pub tx_pool_max_tx_per_owner: usize,
+
+    /// Keep a journal of txpool transactions in this file,
+    /// so pending transactions are restored after the node restarts
+    #[clap(long = "tx-pool-journal", parse(from_os_str))]
+    pub tx_pool_journal: Option<PathBuf>,
+
     /// Enable predicate execution on transaction inputs.
     /// Will reject any transactions with predicates if set to false.
     #[clap(long = "predicates")]
     pub predicates: bool,
+
+    /// The address the fees of produced blocks are credited to
+    #[clap(long = "coinbase-recipient", parse(try_from_str))]
+    pub coinbase_recipient: Option<Address>,
+
+    /// Execute the transactions of a block that don't conflict with each other in parallel
+    #[clap(long = "parallel-execution")]
+    pub parallel_execution: bool,
+
+    /// The number of latest blocks the chain can be rolled back by and state can be read at.
+    /// The history of every block is kept if not set
+    #[clap(long = "history-depth")]
+    pub history_depth: Option<u32>,
+
+    #[clap(subcommand)]
+    pub command: Option<Command>,
 }
 
 impl Opt {
     pub fn exec(self) -> io::Result<Config> {
-        let filter = match env::var_os(LOG_FILTER) {
-            Some(_) => EnvFilter::try_from_default_env().expect("Invalid `RUST_LOG` provided"),
-            None => EnvFilter::new("info"),
-        };
-
-        let human_logging = env::var_os(HUMAN_LOGGING)
-            .map(|s| {
-                bool::from_str(s.to_str().unwrap())
-                    .expect("Expected `true` or `false` to be provided for `HUMAN_LOGGING`")
-            })
-            .unwrap_or(true);
-
-        let sub = tracing_subscriber::fmt::Subscriber::builder()
-            .with_writer(std::io::stderr)
-            .with_env_filter(filter);
-
-        if human_logging {
-            // use pretty logs
-            sub.with_ansi(true)
-                .with_level(true)
-                .with_line_number(true)
-                .init();
-        } else {
-            // use machine parseable structured logs
-            sub
-                // disable terminal colors
-                .with_ansi(false)
-                .with_level(true)
-                .with_line_number(true)
-                // use json
-                .json()
-                .init();
-        }
+        init_logging();
 
         let Opt {
             ip,
@@ -107,12 +111,28 @@ impl Opt {
             database_type,
             chain_config,
             vm_backtrace,
+            store_transaction_traces,
             utxo_validation,
             min_gas_price,
             min_byte_price,
+            tx_pool_ttl,
+            tx_pool_max_tx_per_owner,
+            tx_pool_journal,
             predicates,
+            coinbase_recipient,
+            parallel_execution,
+            history_depth,
+            command: _,
         } = self;
 
+        let coinbase_recipient = coinbase_recipient.unwrap_or_default();
+        if coinbase_recipient == Address::zeroed() {
+            tracing::warn!(
+                "The fees of produced blocks are credited to the zero address, \
+                set --coinbase-recipient to collect them"
+            );
+        }
+
         let addr = net::SocketAddr::new(ip, port);
         Ok(Config {
             addr,
@@ -122,18 +142,192 @@ impl Opt {
             utxo_validation,
             vm: VMConfig {
                 backtrace: vm_backtrace,
+                store_traces: store_transaction_traces,
             },
             txpool: fuel_txpool::Config {
                 min_gas_price,
                 min_byte_price,
+                transaction_ttl: Duration::from_secs(tx_pool_ttl),
+                max_tx_per_owner: tx_pool_max_tx_per_owner,
+                journal_path: tx_pool_journal,
                 ..Default::default()
             },
             predicates,
             block_importer: Default::default(),
-            block_producer: Default::default(),
-            block_executor: Default::default(),
+            block_producer: fuel_block_producer::Config { coinbase_recipient },
+            block_executor: fuel_block_executor::Config {
+                parallel_execution,
+                history_depth,
+                ..Default::default()
+            },
             bft: Default::default(),
             sync: Default::default(),
         })
     }
 }
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Roll the database back to a previous block height, reverting all blocks above it.
+    /// The node must not be running.
+    Rollback(RollbackCmd),
+    /// Execute the stored blocks of a height range again and report the blocks whose
+    /// transactions root, transaction statuses or receipts differ from the stored ones.
+    /// Nothing is written to the database.
+    Replay(ReplayCmd),
+}
+
+#[derive(Parser, Debug)]
+pub struct RollbackCmd {
+    #[clap(
+        name = "DB_PATH",
+        long = "db-path",
+        parse(from_os_str),
+        default_value = (*DEFAULT_DB_PATH).to_str().unwrap()
+    )]
+    pub database_path: PathBuf,
+
+    /// The height of the block that becomes the head of the chain
+    #[clap(long = "target-height")]
+    pub target_height: u32,
+}
+
+#[derive(Parser, Debug)]
+pub struct ReplayCmd {
+    #[clap(
+        name = "DB_PATH",
+        long = "db-path",
+        parse(from_os_str),
+        default_value = (*DEFAULT_DB_PATH).to_str().unwrap()
+    )]
+    pub database_path: PathBuf,
+
+    /// Specify either an alias to a built-in configuration or filepath to a JSON file.
+    /// Must be the configuration the blocks were produced with.
+    #[clap(name = "CHAIN_CONFIG", long = "chain", default_value = "local_testnet")]
+    pub chain_config: String,
+
+    /// Enable full utxo stateful validation
+    #[clap(long = "utxo-validation")]
+    pub utxo_validation: bool,
+
+    /// Enable predicate execution on transaction inputs.
+    #[clap(long = "predicates")]
+    pub predicates: bool,
+
+    /// The height of the first block to replay
+    #[clap(long = "from")]
+    pub from: u32,
+
+    /// The height of the last block to replay
+    #[clap(long = "to")]
+    pub to: u32,
+}
+
+impl Command {
+    pub async fn exec(self) -> anyhow::Result<()> {
+        init_logging();
+        match self {
+            Command::Rollback(cmd) => cmd.exec(),
+            Command::Replay(cmd) => cmd.exec().await,
+        }
+    }
+}
+
+impl RollbackCmd {
+    #[cfg(feature = "rocksdb")]
+    pub fn exec(self) -> anyhow::Result<()> {
+        let database = fuel_core::database::Database::open(&self.database_path)?;
+        let height = database.get_block_height()?.unwrap_or_default();
+        database.rollback_to(self.target_height.into())?;
+        tracing::info!(
+            "Rolled back the database from height {} to {}",
+            height,
+            self.target_height
+        );
+        Ok(())
+    }
+
+    #[cfg(not(feature = "rocksdb"))]
+    pub fn exec(self) -> anyhow::Result<()> {
+        anyhow::bail!("Rolling back requires the `rocksdb` feature")
+    }
+}
+
+impl ReplayCmd {
+    #[cfg(feature = "rocksdb")]
+    pub async fn exec(self) -> anyhow::Result<()> {
+        let executor = fuel_core::executor::Executor {
+            database: fuel_core::database::Database::open(&self.database_path)?,
+            config: Config {
+                chain_conf: self.chain_config.as_str().parse()?,
+                utxo_validation: self.utxo_validation,
+                predicates: self.predicates,
+                ..Config::local_node()
+            },
+        };
+        let mismatches = executor.replay(self.from.into(), self.to.into()).await?;
+        for mismatch in &mismatches {
+            match &mismatch.error {
+                Some(error) => tracing::error!(
+                    "Block {} ({:#x}) was rejected: {}",
+                    mismatch.height,
+                    mismatch.block_id,
+                    error
+                ),
+                None => tracing::error!(
+                    "Block {} ({:#x}) has transactions with different outcomes: {:?}",
+                    mismatch.height,
+                    mismatch.block_id,
+                    mismatch.transactions
+                ),
+            }
+        }
+        if !mismatches.is_empty() {
+            anyhow::bail!("{} replayed blocks don't match the chain", mismatches.len());
+        }
+        tracing::info!("Replayed blocks {} to {}", self.from, self.to);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "rocksdb"))]
+    pub async fn exec(self) -> anyhow::Result<()> {
+        anyhow::bail!("Replaying requires the `rocksdb` feature")
+    }
+}
+
+fn init_logging() {
+    let filter = match env::var_os(LOG_FILTER) {
+        Some(_) => EnvFilter::try_from_default_env().expect("Invalid `RUST_LOG` provided"),
+        None => EnvFilter::new("info"),
+    };
+
+    let human_logging = env::var_os(HUMAN_LOGGING)
+        .map(|s| {
+            bool::from_str(s.to_str().unwrap())
+                .expect("Expected `true` or `false` to be provided for `HUMAN_LOGGING`")
+        })
+        .unwrap_or(true);
+
+    let sub = tracing_subscriber::fmt::Subscriber::builder()
+        .with_writer(std::io::stderr)
+        .with_env_filter(filter);
+
+    if human_logging {
+        // use pretty logs
+        sub.with_ansi(true)
+            .with_level(true)
+            .with_line_number(true)
+            .init();
+    } else {
+        // use machine parseable structured logs
+        sub
+            // disable terminal colors
+            .with_ansi(false)
+            .with_level(true)
+            .with_line_number(true)
+            // use json
+            .json()
+            .init();
+    }
+}
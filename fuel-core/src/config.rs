@@ -1,6 +1,7 @@
 pub mod chain_config;
 pub mod serialization;
 
+use crate::model::{BlockHeight, ParameterUpgrade};
 use chain_config::ChainConfig;
 use std::{
     net::{Ipv4Addr, SocketAddr},
@@ -44,11 +45,19 @@ impl Config {
             sync: Default::default(),
         }
     }
+
+    /// Whether predicates are enabled for the block at `height`: the `predicates` flag, unless an
+    /// upgrade of the chain config switched them up to that height.
+    pub fn predicates_at(&self, height: BlockHeight) -> bool {
+        ParameterUpgrade::predicates_at(&self.chain_conf.upgrades, height, self.predicates)
+    }
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct VMConfig {
     pub backtrace: bool,
+    /// Record the call tree of every executed transaction.
+    pub store_traces: bool,
 }
 
 #[derive(Clone, Debug, Display, PartialEq, EnumString, EnumVariantNames)]
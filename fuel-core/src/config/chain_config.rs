@@ -1,8 +1,8 @@
 use super::serialization::{HexNumber, HexType};
-use crate::model::BlockHeight;
+use crate::model::{BlockHeight, ParameterUpgrade};
 use fuel_core_interfaces::common::{
     fuel_tx::ConsensusParameters,
-    fuel_types::{Address, AssetId, Bytes32, Salt},
+    fuel_types::{Address, AssetId, Bytes32, Salt, Word},
 };
 use itertools::Itertools;
 use rand::rngs::StdRng;
@@ -13,6 +13,10 @@ use std::{io::ErrorKind, path::PathBuf, str::FromStr};
 
 pub const LOCAL_TESTNET: &str = "local_testnet";
 pub const TESTNET_INITIAL_BALANCE: u64 = 10_000_000;
+/// Enough for ten transactions using the maximum gas each.
+pub const DEFAULT_MAX_GAS_PER_BLOCK: Word = 10 * ConsensusParameters::DEFAULT.max_gas_per_tx;
+/// Enough for a transaction deploying a contract of the maximum size.
+pub const DEFAULT_MAX_BLOCK_SIZE: u64 = 2 * ConsensusParameters::DEFAULT.contract_max_size;
 
 #[skip_serializing_none]
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -22,6 +26,27 @@ pub struct ChainConfig {
     #[serde(default)]
     pub initial_state: Option<StateConfig>,
     pub transaction_parameters: ConsensusParameters,
+    /// The sum of the gas limits of the transactions of a block can't exceed this. Not covered by
+    /// `upgrades`.
+    #[serde(default = "default_max_gas_per_block")]
+    pub max_gas_per_block: Word,
+    /// The sum of the metered sizes of the transactions of a block, which exclude witness data,
+    /// can't exceed this. Not covered by `upgrades`.
+    #[serde(default = "default_max_block_size")]
+    pub max_block_size: u64,
+    /// Changes to the transaction parameters and feature switches scheduled by block height, so
+    /// limits can change without a regenesis. The block gas and size limits and the parameters
+    /// of debug sessions stay at their genesis values.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub upgrades: Vec<ParameterUpgrade>,
+}
+
+fn default_max_gas_per_block() -> Word {
+    DEFAULT_MAX_GAS_PER_BLOCK
+}
+
+fn default_max_block_size() -> u64 {
+    DEFAULT_MAX_BLOCK_SIZE
 }
 
 impl Default for ChainConfig {
@@ -31,11 +56,23 @@ impl Default for ChainConfig {
             block_production: ProductionStrategy::Instant,
             transaction_parameters: ConsensusParameters::DEFAULT,
             initial_state: None,
+            max_gas_per_block: DEFAULT_MAX_GAS_PER_BLOCK,
+            max_block_size: DEFAULT_MAX_BLOCK_SIZE,
+            upgrades: vec![],
         }
     }
 }
 
 impl ChainConfig {
+    /// The transaction parameters of the block at `height`, after the upgrades scheduled up to it.
+    pub fn transaction_parameters_at(&self, height: BlockHeight) -> ConsensusParameters {
+        ParameterUpgrade::transaction_parameters_at(
+            &self.upgrades,
+            height,
+            self.transaction_parameters,
+        )
+    }
+
     pub fn local_testnet() -> Self {
         // endow some preset accounts with an initial balance
         tracing::info!("Initial Accounts");
@@ -70,6 +107,9 @@ impl ChainConfig {
                 ..StateConfig::default()
             }),
             transaction_parameters: ConsensusParameters::DEFAULT,
+            max_gas_per_block: DEFAULT_MAX_GAS_PER_BLOCK,
+            max_block_size: DEFAULT_MAX_BLOCK_SIZE,
+            upgrades: vec![],
         }
     }
 }
@@ -200,6 +240,48 @@ mod tests {
         assert_eq!(config, deserialized_config);
     }
 
+    #[test]
+    fn block_limits_default_when_missing() {
+        let mut json = serde_json::to_value(ChainConfig::local_testnet()).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("max_gas_per_block");
+        fields.remove("max_block_size");
+
+        let config: ChainConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.max_gas_per_block, DEFAULT_MAX_GAS_PER_BLOCK);
+        assert_eq!(config.max_block_size, DEFAULT_MAX_BLOCK_SIZE);
+    }
+
+    #[test]
+    fn can_roundtrip_serialize_parameter_upgrades() {
+        let config = ChainConfig {
+            upgrades: vec![ParameterUpgrade {
+                height: 10u32.into(),
+                transaction_parameters: Some(ConsensusParameters {
+                    max_inputs: 2,
+                    ..ConsensusParameters::DEFAULT
+                }),
+                predicates: Some(true),
+            }],
+            ..ChainConfig::local_testnet()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let deserialized_config: ChainConfig = serde_json::from_str(json.as_str()).unwrap();
+        assert_eq!(config, deserialized_config);
+        assert_eq!(
+            deserialized_config
+                .transaction_parameters_at(9u32.into())
+                .max_inputs,
+            ConsensusParameters::DEFAULT.max_inputs
+        );
+        assert_eq!(
+            deserialized_config
+                .transaction_parameters_at(10u32.into())
+                .max_inputs,
+            2
+        );
+    }
+
     #[test]
     fn snapshot_configurable_block_height() {
         let mut rng = StdRng::seed_from_u64(2);
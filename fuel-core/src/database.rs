@@ -37,22 +37,28 @@ use self::columns::METADATA;
 
 pub mod balances;
 pub mod block;
+pub mod block_merkle;
 pub mod code_root;
 pub mod coin;
 pub mod contracts;
 pub mod delegates_index;
 pub mod deposit_coin;
+pub mod history;
 pub mod metadata;
-mod receipts;
+pub mod receipts;
+pub mod rollback;
+pub mod sparse_merkle;
 pub mod staking_diffs;
 pub mod state;
+pub mod state_diff;
+pub mod trace;
 pub mod transaction;
 pub mod transactional;
 pub mod validator_set;
 
 // Crude way to invalidate incompatible databases,
 // can be used to perform migrations in the future.
-pub const VERSION: u32 = 0;
+pub const VERSION: u32 = 5;
 
 pub mod columns {
     pub const METADATA: u32 = 0;
@@ -80,10 +86,34 @@ pub mod columns {
     pub const STAKING_DIFFS: u32 = 16;
     /// Maps delegate address with validator_set_diff index where last delegate change happened
     pub const DELEGATES_INDEX: u32 = 17;
+    // (block height, tx idx, receipt idx) -> (tx id, log receipt)
+    pub const LOGS: u32 = 18;
+    // (contract id, block height, tx idx, receipt idx) -> (tx id, log receipt)
+    pub const LOGS_BY_CONTRACT: u32 = 19;
+    // (contract id, block height, tx idx) -> tx id
+    pub const TRANSACTIONS_BY_CONTRACT: u32 = 20;
+    // (owner, asset id) => sum of unspent coins
+    pub const OWNED_BALANCES: u32 = 21;
+    // (owner, asset id, coin id) => true
+    pub const OWNED_COINS_BY_ASSET: u32 = 22;
+    // (column, key, block height) -> value of the key before the block
+    pub const STATE_HISTORY: u32 = 23;
+    // (block height, column, key) -> value of the key before the block
+    pub const BLOCK_UNDO_LOG: u32 = 24;
+    // (level, index) -> root of a perfect subtree of the merkle tree over block ids
+    pub const BLOCK_HEADER_MERKLE: u32 = 25;
+    // block height -> index of the block's leaf in the merkle tree over block ids
+    pub const BLOCK_HEADER_MERKLE_LEAVES: u32 = 26;
+    // (tree namespace, node hash) -> node of a sparse merkle tree over contract state
+    pub const SPARSE_MERKLE_NODES: u32 = 27;
+    // tree namespace -> root of a sparse merkle tree over contract state
+    pub const SPARSE_MERKLE_ROOTS: u32 = 28;
+    // tx id -> call tree of the tx, if traces are stored
+    pub const TRANSACTION_TRACES: u32 = 29;
 
     // Number of columns
     #[cfg(feature = "rocksdb")]
-    pub const COLUMN_NUM: u32 = 18;
+    pub const COLUMN_NUM: u32 = 30;
 }
 
 #[derive(Clone, Debug)]
@@ -131,7 +161,11 @@ impl Drop for DropResources {
 unsafe impl Send for Database {}
 unsafe impl Sync for Database {}
 
-impl TxPoolDb for Database {}
+impl TxPoolDb for Database {
+    fn current_block_height(&self) -> Result<BlockHeight, Error> {
+        self.get_block_height().map(Option::unwrap_or_default)
+    }
+}
 
 impl Database {
     #[cfg(feature = "rocksdb")]
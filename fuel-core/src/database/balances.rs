@@ -1,16 +1,13 @@
 use crate::state::Error;
 use crate::{
-    database::{columns::BALANCES, Database},
-    state::{IterDirection, MultiKey},
+    database::{columns::BALANCES, sparse_merkle::SparseMerkleTree, Database},
+    model::BlockHeight,
+    state::MultiKey,
 };
 use fuel_core_interfaces::common::{
     fuel_storage::MerkleRoot,
-    fuel_vm::{
-        crypto,
-        prelude::{AssetId, ContractId, MerkleStorage, Word},
-    },
+    fuel_vm::prelude::{AssetId, Bytes32, ContractId, MerkleStorage, Word},
 };
-use itertools::Itertools;
 use std::borrow::Cow;
 
 impl MerkleStorage<ContractId, AssetId, Word> for Database {
@@ -22,13 +19,25 @@ impl MerkleStorage<ContractId, AssetId, Word> for Database {
         key: &AssetId,
         value: &Word,
     ) -> Result<Option<Word>, Error> {
-        let key = MultiKey::new((parent, key));
-        Database::insert(self, key.as_ref().to_vec(), BALANCES, *value)
+        let balance = MultiKey::new((parent, key));
+        let previous = Database::insert(self, balance.as_ref().to_vec(), BALANCES, *value)?;
+        self.update_contract_sparse_merkle_tree(
+            &SparseMerkleTree::ContractBalances(*parent),
+            &Bytes32::new(**key),
+            Some(&value.to_be_bytes()),
+        )?;
+        Ok(previous)
     }
 
     fn remove(&mut self, parent: &ContractId, key: &AssetId) -> Result<Option<Word>, Error> {
-        let key = MultiKey::new((parent, key));
-        Database::remove(self, key.as_ref(), BALANCES)
+        let balance = MultiKey::new((parent, key));
+        let previous = Database::remove(self, balance.as_ref(), BALANCES)?;
+        self.update_contract_sparse_merkle_tree(
+            &SparseMerkleTree::ContractBalances(*parent),
+            &Bytes32::new(**key),
+            None,
+        )?;
+        Ok(previous)
     }
 
     fn get(&self, parent: &ContractId, key: &AssetId) -> Result<Option<Cow<Word>>, Error> {
@@ -42,24 +51,21 @@ impl MerkleStorage<ContractId, AssetId, Word> for Database {
     }
 
     fn root(&mut self, parent: &ContractId) -> Result<MerkleRoot, Error> {
-        let items: Vec<_> = Database::iter_all::<Vec<u8>, Word>(
-            self,
-            BALANCES,
-            Some(parent.as_ref().to_vec()),
-            None,
-            Some(IterDirection::Forward),
-        )
-        .try_collect()?;
-
-        let root = items
-            .iter()
-            .filter_map(|(key, value)| {
-                (&key[..parent.len()] == parent.as_ref()).then(|| (key, value))
-            })
-            .sorted_by_key(|t| t.0)
-            .map(|(_, value)| value.to_be_bytes());
+        let root = self.sparse_merkle_root(&SparseMerkleTree::ContractBalances(*parent))?;
+        Ok(root.into())
+    }
+}
 
-        Ok(crypto::ephemeral_merkle_root(root).into())
+impl Database {
+    /// The balance of the contract at the end of the block at `height`.
+    pub fn contract_balance_at_height(
+        &self,
+        contract: &ContractId,
+        asset_id: &AssetId,
+        height: BlockHeight,
+    ) -> Result<Option<Word>, Error> {
+        let key = MultiKey::new((contract, asset_id));
+        self.get_at_height(key.as_ref(), BALANCES, height)
     }
 }
 
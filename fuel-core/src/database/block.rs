@@ -3,7 +3,12 @@ use crate::{
     model::{BlockHeight, FuelBlockDb},
     state::{Error, IterDirection},
 };
-use fuel_core_interfaces::common::{fuel_storage::Storage, fuel_tx::Bytes32};
+use fuel_core_interfaces::common::{
+    fuel_merkle::{binary::MerkleTree, common::StorageMap},
+    fuel_storage::Storage,
+    fuel_tx::{Bytes32, Transaction},
+    fuel_types::bytes::SerializableVec,
+};
 use std::borrow::Cow;
 use std::convert::{TryFrom, TryInto};
 
@@ -60,6 +65,31 @@ impl Database {
         Database::get(self, &height.to_bytes()[..], BLOCK_IDS)
     }
 
+    /// The side nodes of the Merkle tree over the transactions of the block, from the leaf of
+    /// the transaction at `index` up to the `transactions_root`.
+    pub fn transaction_proof(
+        &self,
+        block: &FuelBlockDb,
+        index: u64,
+    ) -> Result<Vec<Bytes32>, KvStoreError> {
+        let mut storage = StorageMap::new();
+        let mut txs_merkle = MerkleTree::new(&mut storage);
+        for tx_id in &block.transactions {
+            let mut tx = Storage::<Bytes32, Transaction>::get(self, tx_id)?
+                .ok_or(KvStoreError::NotFound)?
+                .into_owned();
+            // the same bytes the executor committed to
+            txs_merkle
+                .push(&tx.to_bytes())
+                .expect("In-memory impl should be infallible");
+        }
+        let (_, proof_set) = txs_merkle
+            .prove(index)
+            .map_err(|e| KvStoreError::Error(Box::new(e)))?;
+        // the first element of the set is the leaf itself
+        Ok(proof_set.into_iter().skip(1).map(Into::into).collect())
+    }
+
     pub fn all_block_ids(
         &self,
         start: Option<BlockHeight>,
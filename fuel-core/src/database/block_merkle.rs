@@ -0,0 +1,177 @@
+//! Append-only binary Merkle tree over the ids of all blocks, in height order. The root of the
+//! tree before a block is appended is the `prev_root` of that block.
+//!
+//! Only the roots of perfect subtrees are stored, keyed by their level and position at that
+//! level. They never change once written, so any past root and proof can be rebuilt from them.
+
+use crate::{
+    database::{
+        columns::{BLOCK_HEADER_MERKLE, BLOCK_HEADER_MERKLE_LEAVES},
+        Database, KvStoreError,
+    },
+    model::BlockHeight,
+    state::{Error, IterDirection},
+};
+use fuel_core_interfaces::{
+    common::{fuel_crypto::Hasher, fuel_tx::Bytes32},
+    merkle::{leaf_sum, node_sum},
+};
+
+fn node_key(level: u32, index: u64) -> Vec<u8> {
+    [&level.to_be_bytes()[..], &index.to_be_bytes()[..]].concat()
+}
+
+/// Levels and positions of the perfect subtrees a tree with `leaves_count` leaves is made of,
+/// from the leftmost (largest) to the rightmost.
+fn peaks(leaves_count: u64) -> Vec<(u32, u64)> {
+    let mut peaks = vec![];
+    let mut start = 0;
+    for level in (0..u64::BITS).rev() {
+        if leaves_count & (1 << level) != 0 {
+            peaks.push((level, start >> level));
+            start += 1 << level;
+        }
+    }
+    peaks
+}
+
+impl Database {
+    /// Number of blocks appended to the tree.
+    pub fn block_header_leaves_count(&self) -> Result<u64, Error> {
+        let last_leaf = self
+            .iter_all::<Vec<u8>, u64>(
+                BLOCK_HEADER_MERKLE_LEAVES,
+                None,
+                None,
+                Some(IterDirection::Reverse),
+            )
+            .next()
+            .transpose()?;
+        Ok(last_leaf.map(|(_, index)| index + 1).unwrap_or_default())
+    }
+
+    /// Index of the leaf of the block at `height`, which is also the number of leaves in the
+    /// tree whose root is the `prev_root` of that block.
+    pub fn block_header_leaf_index(&self, height: BlockHeight) -> Result<Option<u64>, Error> {
+        self.get(&height.to_bytes(), BLOCK_HEADER_MERKLE_LEAVES)
+    }
+
+    /// The root of the tree over the first `leaves_count` blocks.
+    pub fn block_header_merkle_root(&self, leaves_count: u64) -> Result<Bytes32, KvStoreError> {
+        let mut peaks = peaks(leaves_count)
+            .into_iter()
+            .map(|(level, index)| self.block_header_merkle_node(level, index))
+            .rev();
+        let mut root = match peaks.next() {
+            Some(peak) => peak?,
+            None => return Ok(Hasher::default().finalize()),
+        };
+        for peak in peaks {
+            root = node_sum(&peak?, &root);
+        }
+        Ok(root)
+    }
+
+    /// Append the id of the block at `height` as the leaf at `index`.
+    pub fn append_block_header_merkle_leaf(
+        &self,
+        index: u64,
+        height: BlockHeight,
+        block_id: &Bytes32,
+    ) -> Result<(), KvStoreError> {
+        self.insert(height, BLOCK_HEADER_MERKLE_LEAVES, index)?;
+        let mut hash = leaf_sum(block_id.as_ref());
+        let mut level = 0;
+        let mut position = index;
+        self.insert(node_key(level, position), BLOCK_HEADER_MERKLE, hash)?;
+        // complete every perfect subtree the leaf is the last leaf of
+        while position % 2 == 1 {
+            let left = self.block_header_merkle_node(level, position - 1)?;
+            hash = node_sum(&left, &hash);
+            level += 1;
+            position /= 2;
+            self.insert(node_key(level, position), BLOCK_HEADER_MERKLE, hash)?;
+        }
+        Ok(())
+    }
+
+    /// The sibling hashes from the leaf at `index` up to the root of the tree over the first
+    /// `leaves_count` blocks.
+    pub fn block_header_merkle_proof(
+        &self,
+        index: u64,
+        leaves_count: u64,
+    ) -> Result<Vec<Bytes32>, KvStoreError> {
+        let peaks = peaks(leaves_count);
+        let peak = peaks
+            .iter()
+            .position(|(level, position)| index >> level == *position)
+            .ok_or(KvStoreError::NotFound)?;
+
+        // siblings inside the perfect subtree of the leaf
+        let mut proof_set = (0..peaks[peak].0)
+            .map(|level| self.block_header_merkle_node(level, (index >> level) ^ 1))
+            .collect::<Result<Vec<_>, KvStoreError>>()?;
+
+        // the root of the subtrees to the right
+        let mut right_peaks = peaks[peak + 1..]
+            .iter()
+            .map(|(level, position)| self.block_header_merkle_node(*level, *position))
+            .rev();
+        if let Some(right_root) = right_peaks.next() {
+            let mut right_root = right_root?;
+            for right_peak in right_peaks {
+                right_root = node_sum(&right_peak?, &right_root);
+            }
+            proof_set.push(right_root);
+        }
+
+        // each of the subtrees to the left
+        for (level, position) in peaks[..peak].iter().rev() {
+            proof_set.push(self.block_header_merkle_node(*level, *position)?);
+        }
+        Ok(proof_set)
+    }
+
+    fn block_header_merkle_node(&self, level: u32, index: u64) -> Result<Bytes32, KvStoreError> {
+        self.get(&node_key(level, index), BLOCK_HEADER_MERKLE)?
+            .ok_or(KvStoreError::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_core_interfaces::common::fuel_merkle::{binary::MerkleTree, common::StorageMap};
+
+    #[test]
+    fn roots_and_proofs_match_binary_merkle_tree() {
+        let db = Database::default();
+        let block_ids: Vec<Bytes32> = (0..20u8).map(|i| Bytes32::from([i; 32])).collect();
+
+        let mut storage = StorageMap::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        for (index, block_id) in block_ids.iter().enumerate() {
+            let leaves_count = index as u64;
+            assert_eq!(
+                db.block_header_merkle_root(leaves_count).unwrap(),
+                Bytes32::from(tree.root().unwrap())
+            );
+
+            for proven in 0..leaves_count {
+                let (_, expected) = tree.prove(proven).unwrap();
+                let expected: Vec<Bytes32> =
+                    expected[1..].iter().copied().map(Into::into).collect();
+                assert_eq!(
+                    db.block_header_merkle_proof(proven, leaves_count).unwrap(),
+                    expected
+                );
+            }
+
+            assert_eq!(db.block_header_leaves_count().unwrap(), leaves_count);
+            db.append_block_header_merkle_leaf(leaves_count, (index * 2).into(), block_id)
+                .unwrap();
+            tree.push(block_id.as_ref()).unwrap();
+        }
+    }
+}
@@ -1,16 +1,16 @@
 use crate::{
     database::{
-        columns::{self, OWNED_COINS},
+        columns::{self, OWNED_BALANCES, OWNED_COINS, OWNED_COINS_BY_ASSET},
         Database, KvStoreError,
     },
-    model::Coin,
+    model::{BlockHeight, Coin, CoinStatus},
     state::{Error, IterDirection},
 };
 use fuel_core_interfaces::common::{
     fuel_storage::Storage,
     fuel_tx::{Address, AssetId, Bytes32, UtxoId},
+    fuel_types::Word,
 };
-use itertools::Itertools;
 use std::borrow::Cow;
 
 fn owner_coin_id_key(owner: &Address, coin_id: &UtxoId) -> Vec<u8> {
@@ -22,6 +22,21 @@ fn owner_coin_id_key(owner: &Address, coin_id: &UtxoId) -> Vec<u8> {
         .collect()
 }
 
+fn owner_asset_key(owner: &Address, asset_id: &AssetId) -> Vec<u8> {
+    owner
+        .as_ref()
+        .iter()
+        .chain(asset_id.as_ref().iter())
+        .copied()
+        .collect()
+}
+
+fn owner_asset_coin_id_key(owner: &Address, asset_id: &AssetId, coin_id: &UtxoId) -> Vec<u8> {
+    let mut key = owner_asset_key(owner, asset_id);
+    key.extend(utxo_id_to_bytes(coin_id));
+    key
+}
+
 // 32 Bytes for Tx_id + 1 byte for output_index
 const SIZE_OF_UTXO_ID: usize = 264;
 
@@ -41,16 +56,39 @@ impl Storage<UtxoId, Coin> for Database {
         let insert = Database::insert(self, utxo_id_to_bytes(key), columns::COIN, value.clone())?;
         // insert secondary index by owner
         Database::insert(self, coin_by_owner, columns::OWNED_COINS, true)?;
+        // insert secondary index by owner and asset
+        Database::insert(
+            self,
+            owner_asset_coin_id_key(&value.owner, &value.asset_id, key),
+            OWNED_COINS_BY_ASSET,
+            true,
+        )?;
+        // keep the owner balance in sync with the unspent coins
+        if let Some(previous) = &insert {
+            if previous.status == CoinStatus::Unspent {
+                self.decrease_owned_balance(&previous.owner, &previous.asset_id, previous.amount)?;
+            }
+        }
+        if value.status == CoinStatus::Unspent {
+            self.increase_owned_balance(&value.owner, &value.asset_id, value.amount)?;
+        }
         Ok(insert)
     }
 
     fn remove(&mut self, key: &UtxoId) -> Result<Option<Coin>, KvStoreError> {
         let coin: Option<Coin> = Database::remove(self, &utxo_id_to_bytes(key), columns::COIN)?;
 
-        // cleanup secondary index
+        // cleanup secondary indexes
         if let Some(coin) = &coin {
-            let key = owner_coin_id_key(&coin.owner, key);
-            let _: Option<bool> = Database::remove(self, key.as_slice(), columns::OWNED_COINS)?;
+            let owner_key = owner_coin_id_key(&coin.owner, key);
+            let _: Option<bool> =
+                Database::remove(self, owner_key.as_slice(), columns::OWNED_COINS)?;
+            let asset_key = owner_asset_coin_id_key(&coin.owner, &coin.asset_id, key);
+            let _: Option<bool> =
+                Database::remove(self, asset_key.as_slice(), OWNED_COINS_BY_ASSET)?;
+            if coin.status == CoinStatus::Unspent {
+                self.decrease_owned_balance(&coin.owner, &coin.asset_id, coin.amount)?;
+            }
         }
 
         Ok(coin)
@@ -89,7 +127,6 @@ impl Database {
         })
     }
 
-    // TODO: Optimize this by creating an index
     pub fn owned_coins_by_asset_id(
         &self,
         owner: Address,
@@ -98,26 +135,171 @@ impl Database {
         direction: Option<IterDirection>,
     ) -> impl Iterator<Item = Result<UtxoId, Error>> + '_ {
         self.iter_all::<Vec<u8>, bool>(
-            OWNED_COINS,
-            Some(owner.as_ref().to_vec()),
-            start_coin.map(|b| owner_coin_id_key(&owner, &b)),
+            OWNED_COINS_BY_ASSET,
+            Some(owner_asset_key(&owner, &asset_id)),
+            start_coin.map(|b| owner_asset_coin_id_key(&owner, &asset_id, &b)),
             direction,
         )
-        // Safety: key is always 64 bytes
+        // Safety: key is always 97 bytes
         .map(|res| {
             res.map(|(key, _)| {
                 UtxoId::new(
-                    unsafe { Bytes32::from_slice_unchecked(&key[32..64]) },
-                    key[64],
+                    unsafe { Bytes32::from_slice_unchecked(&key[64..96]) },
+                    key[96],
                 )
             })
         })
-        .filter_ok(move |id| {
-            Storage::<UtxoId, Coin>::get(self, id)
-                .unwrap()
-                .unwrap()
-                .asset_id
-                == asset_id
-        })
+    }
+
+    /// The coin as it was at the end of the block at `height`.
+    pub fn coin_at_height(
+        &self,
+        utxo_id: &UtxoId,
+        height: BlockHeight,
+    ) -> Result<Option<Coin>, Error> {
+        self.get_at_height(&utxo_id_to_bytes(utxo_id), columns::COIN, height)
+    }
+
+    /// The sum of the unspent coins of the owner with the asset id at the end of the block at
+    /// `height`.
+    pub fn owned_balance_at_height(
+        &self,
+        owner: &Address,
+        asset_id: &AssetId,
+        height: BlockHeight,
+    ) -> Result<Word, Error> {
+        let balance: Option<Word> =
+            self.get_at_height(&owner_asset_key(owner, asset_id), OWNED_BALANCES, height)?;
+        Ok(balance.unwrap_or_default())
+    }
+
+    /// The sum of the unspent coins of the owner with the asset id.
+    pub fn owned_balance(&self, owner: &Address, asset_id: &AssetId) -> Result<Word, Error> {
+        let balance: Option<Word> = self.get(&owner_asset_key(owner, asset_id), OWNED_BALANCES)?;
+        Ok(balance.unwrap_or_default())
+    }
+
+    /// Iterates over the non-zero balances of the owner, sorted by asset id.
+    pub fn owned_balances(
+        &self,
+        owner: Address,
+        start_asset: Option<AssetId>,
+        direction: Option<IterDirection>,
+    ) -> impl Iterator<Item = Result<(AssetId, Word), Error>> + '_ {
+        self.iter_all::<Vec<u8>, Word>(
+            OWNED_BALANCES,
+            Some(owner.as_ref().to_vec()),
+            start_asset.map(|asset_id| owner_asset_key(&owner, &asset_id)),
+            direction,
+        )
+        .map(|res| res.map(|(key, balance)| (AssetId::new(key[32..].try_into().unwrap()), balance)))
+    }
+
+    /// Iterates over the non-zero balances the owner had at the end of the block at `height`,
+    /// sorted by asset id.
+    pub fn owned_balances_at_height(
+        &self,
+        owner: Address,
+        start_asset: Option<AssetId>,
+        direction: Option<IterDirection>,
+        height: BlockHeight,
+    ) -> impl Iterator<Item = Result<(AssetId, Word), Error>> + '_ {
+        self.iter_at_height::<Word>(
+            OWNED_BALANCES,
+            owner.as_ref().to_vec(),
+            start_asset.map(|asset_id| owner_asset_key(&owner, &asset_id)),
+            direction.unwrap_or_default(),
+            height,
+        )
+        .map(|res| res.map(|(key, balance)| (AssetId::new(key[32..].try_into().unwrap()), balance)))
+    }
+
+    fn increase_owned_balance(
+        &self,
+        owner: &Address,
+        asset_id: &AssetId,
+        amount: Word,
+    ) -> Result<(), Error> {
+        let balance = self.owned_balance(owner, asset_id)?;
+        self.set_owned_balance(owner, asset_id, balance.saturating_add(amount))
+    }
+
+    fn decrease_owned_balance(
+        &self,
+        owner: &Address,
+        asset_id: &AssetId,
+        amount: Word,
+    ) -> Result<(), Error> {
+        let balance = self.owned_balance(owner, asset_id)?;
+        self.set_owned_balance(owner, asset_id, balance.saturating_sub(amount))
+    }
+
+    fn set_owned_balance(
+        &self,
+        owner: &Address,
+        asset_id: &AssetId,
+        balance: Word,
+    ) -> Result<(), Error> {
+        let key = owner_asset_key(owner, asset_id);
+        if balance == 0 {
+            let _: Option<Word> = Database::remove(self, &key, OWNED_BALANCES)?;
+        } else {
+            Database::insert(self, key, OWNED_BALANCES, balance)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    fn coin(owner: Address, asset_id: AssetId, amount: Word) -> Coin {
+        Coin {
+            owner,
+            amount,
+            asset_id,
+            maturity: Default::default(),
+            status: CoinStatus::Unspent,
+            block_created: Default::default(),
+        }
+    }
+
+    #[test]
+    fn owned_balance_tracks_unspent_coins() {
+        let owner = Address::from([1u8; 32]);
+        let asset_a = AssetId::from([2u8; 32]);
+        let asset_b = AssetId::from([3u8; 32]);
+        let utxo_1 = UtxoId::new(Bytes32::from([4u8; 32]), 0);
+        let utxo_2 = UtxoId::new(Bytes32::from([4u8; 32]), 1);
+        let utxo_3 = UtxoId::new(Bytes32::from([5u8; 32]), 0);
+        let mut db = Database::default();
+
+        Storage::<UtxoId, Coin>::insert(&mut db, &utxo_1, &coin(owner, asset_a, 10)).unwrap();
+        Storage::<UtxoId, Coin>::insert(&mut db, &utxo_2, &coin(owner, asset_a, 20)).unwrap();
+        Storage::<UtxoId, Coin>::insert(&mut db, &utxo_3, &coin(owner, asset_b, 5)).unwrap();
+        assert_eq!(db.owned_balance(&owner, &asset_a).unwrap(), 30);
+
+        // spending a coin keeps it in the asset index but removes it from the balance
+        let mut spent = coin(owner, asset_a, 10);
+        spent.status = CoinStatus::Spent;
+        Storage::<UtxoId, Coin>::insert(&mut db, &utxo_1, &spent).unwrap();
+        assert_eq!(db.owned_balance(&owner, &asset_a).unwrap(), 20);
+        let coins: Vec<UtxoId> = db
+            .owned_coins_by_asset_id(owner, asset_a, None, None)
+            .try_collect()
+            .unwrap();
+        assert_eq!(coins, vec![utxo_1, utxo_2]);
+
+        Storage::<UtxoId, Coin>::remove(&mut db, &utxo_3).unwrap();
+        let balances: Vec<(AssetId, Word)> =
+            db.owned_balances(owner, None, None).try_collect().unwrap();
+        assert_eq!(balances, vec![(asset_a, 20)]);
+        assert_eq!(
+            db.owned_coins_by_asset_id(owner, asset_b, None, None)
+                .count(),
+            0
+        );
     }
 }
@@ -0,0 +1,261 @@
+//! Previous values of state keys, recorded for every block so that state can be read as it was
+//! at any past block height.
+
+use crate::{
+    database::{
+        columns::{BALANCES, COIN, CONTRACTS_STATE, OWNED_BALANCES, STATE_HISTORY},
+        Database,
+    },
+    model::BlockHeight,
+    state::{ColumnId, Error, IterDirection},
+};
+use itertools::Itertools;
+use serde::de::DeserializeOwned;
+
+/// Columns whose values can be queried at a past block height.
+const HISTORICAL_COLUMNS: [ColumnId; 4] = [COIN, OWNED_BALANCES, BALANCES, CONTRACTS_STATE];
+
+impl Database {
+    /// Record the value every changed key of a historical column had in `previous`, before the
+    /// block at `height` changed it.
+    pub fn record_state_history(
+        &self,
+        height: BlockHeight,
+        previous: &Database,
+        changed_keys: &[(ColumnId, Vec<u8>)],
+    ) -> Result<(), Error> {
+        for (column, key) in changed_keys {
+            if !HISTORICAL_COLUMNS.contains(column) {
+                continue;
+            }
+            let value = previous.data.get(key, *column)?;
+            self.insert(history_key(*column, key, height), STATE_HISTORY, value)?;
+        }
+        Ok(())
+    }
+
+    /// The value of the key at the end of the block at `height`.
+    pub(super) fn get_at_height<V: DeserializeOwned>(
+        &self,
+        key: &[u8],
+        column: ColumnId,
+        height: BlockHeight,
+    ) -> Result<Option<V>, Error> {
+        let next_height = match height.checked_add(1) {
+            Some(next_height) => BlockHeight::from(next_height),
+            None => return self.get(key, column),
+        };
+        // the first change after `height` holds the value the key had at `height`
+        let change = self
+            .iter_all::<Vec<u8>, Option<Vec<u8>>>(
+                STATE_HISTORY,
+                Some(history_key_prefix(column, key)),
+                Some(history_key(column, key, next_height)),
+                Some(IterDirection::Forward),
+            )
+            .next()
+            .transpose()?;
+        match change {
+            Some((_, value)) => value
+                .map(|value| bincode::deserialize(&value).map_err(|_| Error::Codec))
+                .transpose(),
+            None => self.get(key, column),
+        }
+    }
+
+    /// Iterates over the keys under `prefix` of a historical column with their values at the end
+    /// of the block at `height`, sorted by key. `start` is the full key to start from. Keys that
+    /// didn't exist at `height` are skipped, and keys that were removed since are included.
+    pub(super) fn iter_at_height<V: DeserializeOwned>(
+        &self,
+        column: ColumnId,
+        prefix: Vec<u8>,
+        start: Option<Vec<u8>>,
+        direction: IterDirection,
+        height: BlockHeight,
+    ) -> impl Iterator<Item = Result<(Vec<u8>, V), Error>> + '_ {
+        let current = self
+            .data
+            .iter_all(column, Some(prefix.clone()), start.clone(), direction)
+            .map(|(key, _)| key);
+        // the history of a key is sorted by height after it, so reverse iteration starts from
+        // the latest change of the start key
+        let history_start = start.map(|start| match direction {
+            IterDirection::Forward => history_key_prefix(column, &start),
+            IterDirection::Reverse => history_key(column, &start, u32::MAX.into()),
+        });
+        let changed = self
+            .data
+            .iter_all(
+                STATE_HISTORY,
+                Some(history_key_prefix(column, &prefix)),
+                history_start,
+                direction,
+            )
+            .map(|(history_key, _)| history_key[4..history_key.len() - 4].to_vec());
+
+        current
+            .merge_by(changed, move |a, b| match direction {
+                IterDirection::Forward => a <= b,
+                IterDirection::Reverse => a >= b,
+            })
+            .dedup()
+            .filter_map(move |key| match self.get_at_height(&key, column, height) {
+                Ok(Some(value)) => Some(Ok((key, value))),
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            })
+    }
+}
+
+fn history_key_prefix(column: ColumnId, key: &[u8]) -> Vec<u8> {
+    let mut prefix = Vec::with_capacity(4 + key.len());
+    prefix.extend(column.to_be_bytes());
+    prefix.extend(key);
+    prefix
+}
+
+fn history_key(column: ColumnId, key: &[u8], height: BlockHeight) -> Vec<u8> {
+    // column + key + block height
+    let mut history_key = history_key_prefix(column, key);
+    history_key.extend(height.to_bytes());
+    history_key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Coin, CoinStatus};
+    use fuel_core_interfaces::common::{
+        fuel_storage::Storage,
+        fuel_tx::{Address, AssetId, Bytes32, UtxoId},
+    };
+
+    fn apply_block(db: &Database, height: BlockHeight, coins: &[(UtxoId, Coin)]) {
+        let mut block_db_transaction = db.transaction();
+        for (utxo_id, coin) in coins {
+            Storage::<UtxoId, Coin>::insert(block_db_transaction.as_mut(), utxo_id, coin).unwrap();
+        }
+        let changed_keys = block_db_transaction.changed_keys();
+        block_db_transaction
+            .record_state_history(height, db, &changed_keys)
+            .unwrap();
+        block_db_transaction.commit().unwrap();
+    }
+
+    #[test]
+    fn state_can_be_read_at_past_heights() {
+        let owner = Address::from([1u8; 32]);
+        let asset_id = AssetId::from([2u8; 32]);
+        let utxo_1 = UtxoId::new(Bytes32::from([3u8; 32]), 0);
+        let utxo_2 = UtxoId::new(Bytes32::from([4u8; 32]), 0);
+        let coin = |amount, status| Coin {
+            owner,
+            amount,
+            asset_id,
+            maturity: Default::default(),
+            status,
+            block_created: Default::default(),
+        };
+        let db = Database::default();
+
+        apply_block(&db, 1u32.into(), &[(utxo_1, coin(10, CoinStatus::Unspent))]);
+        apply_block(
+            &db,
+            2u32.into(),
+            &[
+                (utxo_1, coin(10, CoinStatus::Spent)),
+                (utxo_2, coin(5, CoinStatus::Unspent)),
+            ],
+        );
+
+        let balance_at = |height: u32| {
+            db.owned_balance_at_height(&owner, &asset_id, height.into())
+                .unwrap()
+        };
+        assert_eq!(balance_at(0), 0);
+        assert_eq!(balance_at(1), 10);
+        assert_eq!(balance_at(2), 5);
+        assert_eq!(balance_at(3), 5);
+
+        assert!(db.coin_at_height(&utxo_1, 0u32.into()).unwrap().is_none());
+        assert_eq!(
+            db.coin_at_height(&utxo_1, 1u32.into())
+                .unwrap()
+                .unwrap()
+                .status,
+            CoinStatus::Unspent
+        );
+        assert_eq!(
+            db.coin_at_height(&utxo_1, 2u32.into())
+                .unwrap()
+                .unwrap()
+                .status,
+            CoinStatus::Spent
+        );
+        assert!(db.coin_at_height(&utxo_2, 1u32.into()).unwrap().is_none());
+    }
+
+    #[test]
+    fn listings_can_be_read_at_past_heights() {
+        let owner = Address::from([1u8; 32]);
+        let asset_1 = AssetId::from([2u8; 32]);
+        let asset_2 = AssetId::from([3u8; 32]);
+        let coin = |asset_id, status| Coin {
+            owner,
+            amount: 10,
+            asset_id,
+            maturity: Default::default(),
+            status,
+            block_created: Default::default(),
+        };
+        let utxo_1 = UtxoId::new(Bytes32::from([4u8; 32]), 0);
+        let utxo_2 = UtxoId::new(Bytes32::from([5u8; 32]), 0);
+        let db = Database::default();
+
+        apply_block(
+            &db,
+            1u32.into(),
+            &[(utxo_1, coin(asset_1, CoinStatus::Unspent))],
+        );
+        // the balance of the first asset drops to zero and is removed
+        apply_block(
+            &db,
+            2u32.into(),
+            &[
+                (utxo_1, coin(asset_1, CoinStatus::Spent)),
+                (utxo_2, coin(asset_2, CoinStatus::Unspent)),
+            ],
+        );
+
+        let balances_at = |height: u32, direction| {
+            db.owned_balances_at_height(owner, None, Some(direction), height.into())
+                .map(|res| res.unwrap().0)
+                .collect::<Vec<_>>()
+        };
+        assert!(balances_at(0, IterDirection::Forward).is_empty());
+        assert_eq!(balances_at(1, IterDirection::Forward), vec![asset_1]);
+        assert_eq!(balances_at(2, IterDirection::Forward), vec![asset_2]);
+
+        // the first asset has a balance again
+        apply_block(
+            &db,
+            3u32.into(),
+            &[(utxo_1, coin(asset_1, CoinStatus::Unspent))],
+        );
+        assert_eq!(
+            balances_at(3, IterDirection::Reverse),
+            vec![asset_2, asset_1]
+        );
+        let from_asset_2 = db
+            .owned_balances_at_height(
+                owner,
+                Some(asset_2),
+                Some(IterDirection::Reverse),
+                1u32.into(),
+            )
+            .map(|res| res.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(from_asset_2, vec![asset_1]);
+    }
+}
@@ -1,10 +1,20 @@
-use crate::database::{columns::RECEIPTS, Database, KvStoreError};
+use crate::{
+    database::{
+        columns::{LOGS, LOGS_BY_CONTRACT, RECEIPTS},
+        transaction::TransactionIndex,
+        Database, KvStoreError,
+    },
+    model::BlockHeight,
+    state::{Error, IterDirection},
+};
 use fuel_core_interfaces::common::{
     fuel_storage::Storage,
-    fuel_tx::{Bytes32, Receipt},
+    fuel_tx::{Bytes32, ContractId, Receipt},
 };
 use std::borrow::Cow;
 
+pub type ReceiptIndex = u32;
+
 impl Storage<Bytes32, Vec<Receipt>> for Database {
     type Error = KvStoreError;
 
@@ -28,3 +38,101 @@ impl Storage<Bytes32, Vec<Receipt>> for Database {
         Database::exists(self, key.as_ref(), RECEIPTS).map_err(Into::into)
     }
 }
+
+impl Database {
+    /// Index a `Log` or `LogData` receipt by its position on chain, and by the contract that
+    /// emitted it.
+    pub fn record_log(
+        &self,
+        position: &LogIndexCursor,
+        tx_id: &Bytes32,
+        receipt: &Receipt,
+    ) -> Result<(), Error> {
+        let log = (*tx_id, receipt.clone());
+        if let Some(contract) = receipt.id() {
+            self.insert(
+                log_index_key(Some(contract), position),
+                LOGS_BY_CONTRACT,
+                log.clone(),
+            )?;
+        }
+        self.insert(log_index_key(None, position), LOGS, log)?;
+        Ok(())
+    }
+
+    /// Iterates over a KV mapping of `[contract id + block height + tx idx + receipt idx] =>
+    /// (tx id, receipt)`, or of `[block height + tx idx + receipt idx] => (tx id, receipt)` if no
+    /// contract is given. Logs are sorted by their position on chain, which is also used as the
+    /// cursor for pagination.
+    pub fn logs(
+        &self,
+        contract: Option<&ContractId>,
+        start: Option<&LogIndexCursor>,
+        direction: Option<IterDirection>,
+    ) -> impl Iterator<Item = Result<(LogIndexCursor, Bytes32, Receipt), Error>> + '_ {
+        let column = if contract.is_some() {
+            LOGS_BY_CONTRACT
+        } else {
+            LOGS
+        };
+        let prefix = contract.map(|contract| contract.to_vec());
+        let start = start.map(|cursor| log_index_key(contract, cursor));
+        self.iter_all::<LogIndexKey, (Bytes32, Receipt)>(column, prefix, start, direction)
+            .map(|res| res.map(|(key, (tx_id, receipt))| (key.0, tx_id, receipt)))
+    }
+}
+
+/// Position of a log receipt on chain.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct LogIndexCursor {
+    pub block_height: BlockHeight,
+    pub tx_idx: TransactionIndex,
+    pub receipt_idx: ReceiptIndex,
+}
+
+struct LogIndexKey(LogIndexCursor);
+
+impl From<Vec<u8>> for LogIndexKey {
+    fn from(bytes: Vec<u8>) -> Self {
+        // the position is always the suffix of the key, the contract id prefix is already known
+        // when querying
+        Self(bytes[bytes.len() - 12..].to_vec().into())
+    }
+}
+
+impl From<Vec<u8>> for LogIndexCursor {
+    fn from(bytes: Vec<u8>) -> Self {
+        let mut block_height_bytes: [u8; 4] = Default::default();
+        block_height_bytes.copy_from_slice(&bytes[..4]);
+        let mut tx_idx_bytes: [u8; 4] = Default::default();
+        tx_idx_bytes.copy_from_slice(&bytes[4..8]);
+        let mut receipt_idx_bytes: [u8; 4] = Default::default();
+        receipt_idx_bytes.copy_from_slice(&bytes[8..12]);
+
+        Self {
+            block_height: u32::from_be_bytes(block_height_bytes).into(),
+            tx_idx: u32::from_be_bytes(tx_idx_bytes),
+            receipt_idx: u32::from_be_bytes(receipt_idx_bytes),
+        }
+    }
+}
+
+impl From<LogIndexCursor> for Vec<u8> {
+    fn from(cursor: LogIndexCursor) -> Self {
+        let mut bytes = Vec::with_capacity(12);
+        bytes.extend(cursor.block_height.to_bytes());
+        bytes.extend(cursor.tx_idx.to_be_bytes());
+        bytes.extend(cursor.receipt_idx.to_be_bytes());
+        bytes
+    }
+}
+
+fn log_index_key(contract: Option<&ContractId>, position: &LogIndexCursor) -> Vec<u8> {
+    // contract id + block_height + tx_idx + receipt_idx
+    let mut key = Vec::with_capacity(44);
+    if let Some(contract) = contract {
+        key.extend(contract.as_ref());
+    }
+    key.extend(Vec::<u8>::from(*position));
+    key
+}
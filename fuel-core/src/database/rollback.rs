@@ -0,0 +1,125 @@
+//! Undo logs of the writes made by every block, so the chain can be rolled back to a previous
+//! height.
+
+use crate::{
+    database::{
+        columns::{BLOCK_UNDO_LOG, METADATA, STATE_HISTORY},
+        metadata::HISTORY_START_KEY,
+        transactional::DatabaseTransaction,
+        Database,
+    },
+    model::BlockHeight,
+    state::{ColumnId, Error, IterDirection},
+};
+
+impl Database {
+    /// Record the value every changed key had in `previous`, before the block at `height`
+    /// changed it.
+    pub fn record_block_undo_log(
+        &self,
+        height: BlockHeight,
+        previous: &Database,
+        changed_keys: &[(ColumnId, Vec<u8>)],
+    ) -> Result<(), Error> {
+        for (column, key) in changed_keys {
+            let value = previous.data.get(key, *column)?;
+            self.insert(undo_log_key(height, *column, key), BLOCK_UNDO_LOG, value)?;
+        }
+        Ok(())
+    }
+
+    /// Revert the writes of every block above `height`, newest first, so the database is in the
+    /// state it was after the block at `height` was committed.
+    pub fn rollback_to(&self, height: BlockHeight) -> Result<(), Error> {
+        self.fork_at(height)?.commit()
+    }
+
+    /// A transaction of the database with the writes of every block above `height` reverted,
+    /// which holds the state after the block at `height` until it is committed.
+    pub fn fork_at(&self, height: BlockHeight) -> Result<DatabaseTransaction, Error> {
+        let current = self.get_block_height()?.unwrap_or_default();
+        if height > current {
+            return Err(Error::RollbackAboveChainHeight { height, current });
+        }
+
+        let db_transaction = self.transaction();
+        let mut block_height = current;
+        while block_height > height {
+            db_transaction.revert_block(block_height)?;
+            block_height = (*block_height - 1).into();
+        }
+        Ok(db_transaction)
+    }
+
+    /// Drop the undo logs of the blocks up to `height`, together with the state history they
+    /// recorded. The chain can't be rolled back below `height` and state can't be read below it
+    /// afterwards.
+    pub fn prune_history(&self, height: BlockHeight) -> Result<(), Error> {
+        let start = self.history_start()?;
+        if height <= start {
+            return Ok(());
+        }
+        for block_height in (*start + 1)..=*height {
+            let undo_log = self
+                .iter_all::<Vec<u8>, Option<Vec<u8>>>(
+                    BLOCK_UNDO_LOG,
+                    Some(BlockHeight::from(block_height).to_bytes().to_vec()),
+                    None,
+                    Some(IterDirection::Forward),
+                )
+                .collect::<Result<Vec<_>, Error>>()?;
+            for (undo_log_key, _) in undo_log {
+                let column = ColumnId::from_be_bytes(undo_log_key[4..8].try_into().unwrap());
+                if column == STATE_HISTORY {
+                    self.data.delete(&undo_log_key[8..], STATE_HISTORY)?;
+                }
+                self.data.delete(&undo_log_key, BLOCK_UNDO_LOG)?;
+            }
+        }
+        let _: Option<BlockHeight> = self.insert(HISTORY_START_KEY, METADATA, height)?;
+        Ok(())
+    }
+
+    /// The lowest height the chain can be rolled back to and state can be read at.
+    pub fn history_start(&self) -> Result<BlockHeight, Error> {
+        Ok(self.get(HISTORY_START_KEY, METADATA)?.unwrap_or_default())
+    }
+
+    fn revert_block(&self, height: BlockHeight) -> Result<(), Error> {
+        let undo_log = self
+            .iter_all::<Vec<u8>, Option<Vec<u8>>>(
+                BLOCK_UNDO_LOG,
+                Some(height.to_bytes().to_vec()),
+                None,
+                Some(IterDirection::Forward),
+            )
+            .collect::<Result<Vec<_>, Error>>()?;
+        if undo_log.is_empty() {
+            return Err(Error::MissingUndoLog(height));
+        }
+
+        for (undo_log_key, previous) in undo_log {
+            let column = ColumnId::from_be_bytes(undo_log_key[4..8].try_into().unwrap());
+            let key = undo_log_key[8..].to_vec();
+            match previous {
+                Some(previous) => {
+                    self.data.put(key, column, previous)?;
+                }
+                None => {
+                    self.data.delete(&key, column)?;
+                }
+            }
+            self.data.delete(&undo_log_key, BLOCK_UNDO_LOG)?;
+        }
+        Ok(())
+    }
+}
+
+fn undo_log_key(height: BlockHeight, column: ColumnId, key: &[u8]) -> Vec<u8> {
+    // block height + column + key
+    let mut undo_log_key = Vec::with_capacity(8 + key.len());
+    undo_log_key.extend(height.to_bytes());
+    undo_log_key.extend(column.to_be_bytes());
+    undo_log_key.extend(key);
+    undo_log_key
+}
@@ -0,0 +1,434 @@
+//! Sparse Merkle trees over 32 byte keys, updated in place as contract state and balances are
+//! written. Every contract has a tree for its storage slots and one for its balances, and a
+//! global tree maps each contract id to the roots of both, committing to the state of the whole
+//! chain.
+//!
+//! Subtrees with a single leaf are collapsed into that leaf and empty subtrees hash to zero, so
+//! the roots are the same as the ones of the fuel-merkle sparse trees.
+
+use crate::{
+    database::{
+        columns::{SPARSE_MERKLE_NODES, SPARSE_MERKLE_ROOTS},
+        Database, KvStoreError,
+    },
+    state::Error,
+};
+use fuel_core_interfaces::{
+    common::{
+        fuel_crypto::Hasher,
+        fuel_tx::{Bytes32, ContractId},
+    },
+    merkle::{node_sum, sparse_leaf_sum},
+};
+use serde::{Deserialize, Serialize};
+
+/// The hash of an empty subtree.
+const PLACEHOLDER: Bytes32 = Bytes32::zeroed();
+
+/// The trees state commitments are kept in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SparseMerkleTree {
+    /// Storage slots of a contract, keyed by the slot key.
+    ContractState(ContractId),
+    /// Balances of a contract, keyed by asset id, with big endian amounts as the leaf data.
+    ContractBalances(ContractId),
+    /// Every contract with state or balances, keyed by contract id, with the state root
+    /// followed by the balances root as the leaf data.
+    Contracts,
+}
+
+impl SparseMerkleTree {
+    fn namespace(&self) -> Vec<u8> {
+        match self {
+            SparseMerkleTree::ContractState(contract) => [&[0u8][..], contract.as_ref()].concat(),
+            SparseMerkleTree::ContractBalances(contract) => {
+                [&[1u8][..], contract.as_ref()].concat()
+            }
+            SparseMerkleTree::Contracts => vec![2u8],
+        }
+    }
+}
+
+/// Proof of the value of a key, or of its absence, in a sparse Merkle tree.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SparseMerkleProof {
+    /// The key and hash of the data of the leaf on the path of the proven key. It is the leaf
+    /// of the key itself if the key is in the tree, and `None` if the path ends in an empty
+    /// subtree.
+    pub leaf: Option<(Bytes32, Bytes32)>,
+    /// The sibling hashes from the leaf up to the root.
+    pub proof_set: Vec<Bytes32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+enum Node {
+    Leaf { key: Bytes32, data_hash: Bytes32 },
+    Internal { left: Bytes32, right: Bytes32 },
+}
+
+impl Node {
+    fn hash(&self) -> Bytes32 {
+        match self {
+            Node::Leaf { key, data_hash } => sparse_leaf_sum(key, data_hash),
+            Node::Internal { left, right } => node_sum(left, right),
+        }
+    }
+}
+
+/// Whether the path to `key` goes right at `depth`.
+fn goes_right(key: &Bytes32, depth: usize) -> bool {
+    key[depth / 8] & (0x80 >> (depth % 8)) != 0
+}
+
+fn common_prefix_len(lhs: &Bytes32, rhs: &Bytes32) -> usize {
+    (0..Bytes32::LEN * 8)
+        .find(|depth| goes_right(lhs, *depth) != goes_right(rhs, *depth))
+        .unwrap_or(Bytes32::LEN * 8)
+}
+
+/// The nodes on the path from the root to a key.
+struct Path {
+    /// The hashes of the internal nodes on the path, from the root down.
+    internal_nodes: Vec<Bytes32>,
+    /// The siblings of the path at each depth, from the root down.
+    siblings: Vec<Bytes32>,
+    /// The leaf the path ends in, if it doesn't end in an empty subtree.
+    leaf: Option<(Bytes32, Bytes32, Bytes32)>,
+}
+
+impl Database {
+    /// The root of the tree, which is zero for an empty tree.
+    pub fn sparse_merkle_root(&self, tree: &SparseMerkleTree) -> Result<Bytes32, Error> {
+        Ok(self
+            .get(&tree.namespace(), SPARSE_MERKLE_ROOTS)?
+            .unwrap_or(PLACEHOLDER))
+    }
+
+    /// Set `key` to `data`, or remove it from the tree if `data` is `None`.
+    pub fn update_sparse_merkle_tree(
+        &self,
+        tree: &SparseMerkleTree,
+        key: &Bytes32,
+        data: Option<&[u8]>,
+    ) -> Result<(), KvStoreError> {
+        match data {
+            Some(data) => self.sparse_merkle_insert(tree, key, &Hasher::hash(data)),
+            None => self.sparse_merkle_delete(tree, key),
+        }
+    }
+
+    /// Update the tree of a contract and the leaf of the contract in the global tree.
+    pub(crate) fn update_contract_sparse_merkle_tree(
+        &self,
+        tree: &SparseMerkleTree,
+        key: &Bytes32,
+        data: Option<&[u8]>,
+    ) -> Result<(), KvStoreError> {
+        self.update_sparse_merkle_tree(tree, key, data)?;
+
+        let contract = match tree {
+            SparseMerkleTree::ContractState(contract)
+            | SparseMerkleTree::ContractBalances(contract) => contract,
+            SparseMerkleTree::Contracts => return Ok(()),
+        };
+        let state_root = self.sparse_merkle_root(&SparseMerkleTree::ContractState(*contract))?;
+        let balances_root =
+            self.sparse_merkle_root(&SparseMerkleTree::ContractBalances(*contract))?;
+        let contract_data = [state_root.as_ref(), balances_root.as_ref()].concat();
+        let contract_data = if state_root != PLACEHOLDER || balances_root != PLACEHOLDER {
+            Some(contract_data.as_slice())
+        } else {
+            None
+        };
+        self.update_sparse_merkle_tree(
+            &SparseMerkleTree::Contracts,
+            &Bytes32::new(**contract),
+            contract_data,
+        )
+    }
+
+    /// Proof of the current value of `key`, or of its absence.
+    pub fn sparse_merkle_proof(
+        &self,
+        tree: &SparseMerkleTree,
+        key: &Bytes32,
+    ) -> Result<SparseMerkleProof, KvStoreError> {
+        let path = self.sparse_merkle_path(tree, key)?;
+        Ok(SparseMerkleProof {
+            leaf: path.leaf.map(|(_, key, data_hash)| (key, data_hash)),
+            proof_set: path.siblings.into_iter().rev().collect(),
+        })
+    }
+
+    fn sparse_merkle_insert(
+        &self,
+        tree: &SparseMerkleTree,
+        key: &Bytes32,
+        data_hash: &Bytes32,
+    ) -> Result<(), KvStoreError> {
+        let path = self.sparse_merkle_path(tree, key)?;
+        let leaf_hash = self.put_sparse_merkle_node(
+            tree,
+            &Node::Leaf {
+                key: *key,
+                data_hash: *data_hash,
+            },
+        )?;
+
+        let mut current = leaf_hash;
+        match path.leaf {
+            Some((hash, _, _)) if hash == leaf_hash => return Ok(()),
+            Some((hash, other_key, _)) if other_key == *key => {
+                self.delete_sparse_merkle_node(tree, &hash)?;
+            }
+            Some((hash, other_key, _)) => {
+                // the leaves share the path down to where their keys diverge
+                let depth = common_prefix_len(key, &other_key);
+                current = self.put_sparse_merkle_parent(tree, key, depth, &current, &hash)?;
+                for depth in (path.siblings.len()..depth).rev() {
+                    current =
+                        self.put_sparse_merkle_parent(tree, key, depth, &current, &PLACEHOLDER)?;
+                }
+            }
+            None => {}
+        }
+
+        for node in &path.internal_nodes {
+            self.delete_sparse_merkle_node(tree, node)?;
+        }
+        for (depth, sibling) in path.siblings.iter().enumerate().rev() {
+            current = self.put_sparse_merkle_parent(tree, key, depth, &current, sibling)?;
+        }
+        self.set_sparse_merkle_root(tree, &current)
+    }
+
+    fn sparse_merkle_delete(
+        &self,
+        tree: &SparseMerkleTree,
+        key: &Bytes32,
+    ) -> Result<(), KvStoreError> {
+        let path = self.sparse_merkle_path(tree, key)?;
+        match path.leaf {
+            Some((hash, leaf_key, _)) if leaf_key == *key => {
+                self.delete_sparse_merkle_node(tree, &hash)?;
+            }
+            _ => return Ok(()),
+        }
+        for node in &path.internal_nodes {
+            self.delete_sparse_merkle_node(tree, node)?;
+        }
+
+        // a leaf left alone in its subtree moves up until it has a sibling
+        let mut current = PLACEHOLDER;
+        let mut bubbling_up = true;
+        for (depth, sibling) in path.siblings.iter().enumerate().rev() {
+            if bubbling_up {
+                if current == PLACEHOLDER {
+                    if sibling == &PLACEHOLDER {
+                        continue;
+                    }
+                    if let Some(Node::Leaf { .. }) = self.get_sparse_merkle_node(tree, sibling)? {
+                        current = *sibling;
+                        continue;
+                    }
+                } else if sibling == &PLACEHOLDER {
+                    continue;
+                }
+                bubbling_up = false;
+            }
+            current = self.put_sparse_merkle_parent(tree, key, depth, &current, sibling)?;
+        }
+        self.set_sparse_merkle_root(tree, &current)
+    }
+
+    fn sparse_merkle_path(
+        &self,
+        tree: &SparseMerkleTree,
+        key: &Bytes32,
+    ) -> Result<Path, KvStoreError> {
+        let mut path = Path {
+            internal_nodes: vec![],
+            siblings: vec![],
+            leaf: None,
+        };
+        let mut current = self.sparse_merkle_root(tree)?;
+        while current != PLACEHOLDER {
+            match self
+                .get_sparse_merkle_node(tree, &current)?
+                .ok_or(KvStoreError::NotFound)?
+            {
+                Node::Leaf {
+                    key: leaf_key,
+                    data_hash,
+                } => {
+                    path.leaf = Some((current, leaf_key, data_hash));
+                    break;
+                }
+                Node::Internal { left, right } => {
+                    path.internal_nodes.push(current);
+                    if goes_right(key, path.siblings.len()) {
+                        path.siblings.push(left);
+                        current = right;
+                    } else {
+                        path.siblings.push(right);
+                        current = left;
+                    }
+                }
+            }
+        }
+        Ok(path)
+    }
+
+    /// Store the parent of `child`, which is on the path to `key`, and of its `sibling`.
+    fn put_sparse_merkle_parent(
+        &self,
+        tree: &SparseMerkleTree,
+        key: &Bytes32,
+        depth: usize,
+        child: &Bytes32,
+        sibling: &Bytes32,
+    ) -> Result<Bytes32, KvStoreError> {
+        let (left, right) = if goes_right(key, depth) {
+            (*sibling, *child)
+        } else {
+            (*child, *sibling)
+        };
+        self.put_sparse_merkle_node(tree, &Node::Internal { left, right })
+    }
+
+    fn put_sparse_merkle_node(
+        &self,
+        tree: &SparseMerkleTree,
+        node: &Node,
+    ) -> Result<Bytes32, KvStoreError> {
+        let hash = node.hash();
+        self.insert(node_key(tree, &hash), SPARSE_MERKLE_NODES, node.clone())?;
+        Ok(hash)
+    }
+
+    fn get_sparse_merkle_node(
+        &self,
+        tree: &SparseMerkleTree,
+        hash: &Bytes32,
+    ) -> Result<Option<Node>, Error> {
+        self.get(&node_key(tree, hash), SPARSE_MERKLE_NODES)
+    }
+
+    fn delete_sparse_merkle_node(
+        &self,
+        tree: &SparseMerkleTree,
+        hash: &Bytes32,
+    ) -> Result<(), Error> {
+        let _: Option<Node> = self.remove(&node_key(tree, hash), SPARSE_MERKLE_NODES)?;
+        Ok(())
+    }
+
+    fn set_sparse_merkle_root(
+        &self,
+        tree: &SparseMerkleTree,
+        root: &Bytes32,
+    ) -> Result<(), KvStoreError> {
+        if root == &PLACEHOLDER {
+            let _: Option<Bytes32> = self.remove(&tree.namespace(), SPARSE_MERKLE_ROOTS)?;
+        } else {
+            let _: Option<Bytes32> = self.insert(tree.namespace(), SPARSE_MERKLE_ROOTS, *root)?;
+        }
+        Ok(())
+    }
+}
+
+fn node_key(tree: &SparseMerkleTree, hash: &Bytes32) -> Vec<u8> {
+    // tree namespace + node hash
+    [tree.namespace().as_slice(), hash.as_ref()].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_core_interfaces::common::fuel_merkle::{common::StorageMap, sparse::MerkleTree};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn expected_root(leaves: &[(Bytes32, Vec<u8>)]) -> Bytes32 {
+        let mut storage = StorageMap::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        for (key, data) in leaves {
+            tree.update(key, data).unwrap();
+        }
+        tree.root().into()
+    }
+
+    #[test]
+    fn roots_match_sparse_merkle_tree() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let db = Database::default();
+        let tree = SparseMerkleTree::ContractState(ContractId::from([1u8; 32]));
+        assert_eq!(db.sparse_merkle_root(&tree).unwrap(), PLACEHOLDER);
+
+        let mut leaves: Vec<(Bytes32, Vec<u8>)> = vec![];
+        for i in 0..40u8 {
+            // keys sharing long prefixes put placeholders on the path
+            let key = if i % 4 == 0 {
+                let mut key = [0xaa; 32];
+                key[31] = i;
+                Bytes32::from(key)
+            } else {
+                rng.gen()
+            };
+            let data = vec![i; 8];
+            db.update_sparse_merkle_tree(&tree, &key, Some(&data))
+                .unwrap();
+            leaves.push((key, data));
+            assert_eq!(
+                db.sparse_merkle_root(&tree).unwrap(),
+                expected_root(&leaves)
+            );
+        }
+
+        // overwriting keeps a single leaf per key
+        for (key, data) in leaves.iter_mut().step_by(3) {
+            *data = vec![0xff; 4];
+            db.update_sparse_merkle_tree(&tree, key, Some(data))
+                .unwrap();
+        }
+        assert_eq!(
+            db.sparse_merkle_root(&tree).unwrap(),
+            expected_root(&leaves)
+        );
+
+        // removing leaves gives the root of the tree built without them
+        while !leaves.is_empty() {
+            let (key, _) = leaves.remove(rng.gen_range(0..leaves.len()));
+            db.update_sparse_merkle_tree(&tree, &key, None).unwrap();
+            assert_eq!(
+                db.sparse_merkle_root(&tree).unwrap(),
+                expected_root(&leaves)
+            );
+        }
+        assert_eq!(db.sparse_merkle_root(&tree).unwrap(), PLACEHOLDER);
+        assert_eq!(
+            db.iter_all::<Vec<u8>, Vec<u8>>(SPARSE_MERKLE_NODES, None, None, None)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn trees_are_kept_apart() {
+        let db = Database::default();
+        let key = Bytes32::from([3u8; 32]);
+        let state = SparseMerkleTree::ContractState(ContractId::from([1u8; 32]));
+        let balances = SparseMerkleTree::ContractBalances(ContractId::from([1u8; 32]));
+
+        db.update_sparse_merkle_tree(&state, &key, Some(&[1]))
+            .unwrap();
+        db.update_sparse_merkle_tree(&balances, &key, Some(&[1]))
+            .unwrap();
+        db.update_sparse_merkle_tree(&state, &key, None).unwrap();
+
+        assert_eq!(db.sparse_merkle_root(&state).unwrap(), PLACEHOLDER);
+        assert_eq!(
+            db.sparse_merkle_root(&balances).unwrap(),
+            expected_root(&[(key, vec![1])])
+        );
+    }
+}
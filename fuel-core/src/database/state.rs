@@ -1,15 +1,12 @@
 use crate::{
-    database::{columns::CONTRACTS_STATE, Database},
+    database::{columns::CONTRACTS_STATE, sparse_merkle::SparseMerkleTree, Database},
+    model::BlockHeight,
     state::{Error, IterDirection, MultiKey},
 };
 use fuel_core_interfaces::{
     common::fuel_vm::prelude::MerkleRoot,
-    common::fuel_vm::{
-        crypto,
-        prelude::{Bytes32, ContractId, MerkleStorage},
-    },
+    common::fuel_vm::prelude::{Bytes32, ContractId, MerkleStorage},
 };
-use itertools::Itertools;
 use std::borrow::Cow;
 
 impl MerkleStorage<ContractId, Bytes32, Bytes32> for Database {
@@ -21,13 +18,25 @@ impl MerkleStorage<ContractId, Bytes32, Bytes32> for Database {
         key: &Bytes32,
         value: &Bytes32,
     ) -> Result<Option<Bytes32>, Error> {
-        let key = MultiKey::new((parent, key));
-        Database::insert(self, key.as_ref().to_vec(), CONTRACTS_STATE, *value).map_err(Into::into)
+        let slot = MultiKey::new((parent, key));
+        let previous = Database::insert(self, slot.as_ref().to_vec(), CONTRACTS_STATE, *value)?;
+        self.update_contract_sparse_merkle_tree(
+            &SparseMerkleTree::ContractState(*parent),
+            key,
+            Some(value.as_ref()),
+        )?;
+        Ok(previous)
     }
 
     fn remove(&mut self, parent: &ContractId, key: &Bytes32) -> Result<Option<Bytes32>, Error> {
-        let key = MultiKey::new((parent, key));
-        Database::remove(self, key.as_ref(), CONTRACTS_STATE).map_err(Into::into)
+        let slot = MultiKey::new((parent, key));
+        let previous = Database::remove(self, slot.as_ref(), CONTRACTS_STATE)?;
+        self.update_contract_sparse_merkle_tree(
+            &SparseMerkleTree::ContractState(*parent),
+            key,
+            None,
+        )?;
+        Ok(previous)
     }
 
     fn get(&self, parent: &ContractId, key: &Bytes32) -> Result<Option<Cow<Bytes32>>, Error> {
@@ -42,24 +51,56 @@ impl MerkleStorage<ContractId, Bytes32, Bytes32> for Database {
     }
 
     fn root(&mut self, parent: &ContractId) -> Result<MerkleRoot, Error> {
-        let items: Vec<_> = Database::iter_all::<Vec<u8>, Bytes32>(
-            self,
+        let root = self.sparse_merkle_root(&SparseMerkleTree::ContractState(*parent))?;
+        Ok(root.into())
+    }
+}
+
+impl Database {
+    /// The value of a storage slot of the contract at the end of the block at `height`.
+    pub fn contract_storage_at_height(
+        &self,
+        contract: &ContractId,
+        key: &Bytes32,
+        height: BlockHeight,
+    ) -> Result<Option<Bytes32>, Error> {
+        let key = MultiKey::new((contract, key));
+        self.get_at_height(key.as_ref(), CONTRACTS_STATE, height)
+    }
+
+    /// Iterates over the storage slots of a contract, sorted by their key.
+    pub fn contract_storage(
+        &self,
+        contract: ContractId,
+        start_key: Option<Bytes32>,
+        direction: Option<IterDirection>,
+    ) -> impl Iterator<Item = Result<(Bytes32, Bytes32), Error>> + '_ {
+        self.iter_all::<Vec<u8>, Bytes32>(
             CONTRACTS_STATE,
-            Some(parent.as_ref().to_vec()),
-            None,
-            Some(IterDirection::Forward),
+            Some(contract.as_ref().to_vec()),
+            start_key.map(|key| MultiKey::new((&contract, &key)).as_ref().to_vec()),
+            direction,
         )
-        .try_collect()?;
-
-        let root = items
-            .iter()
-            .filter_map(|(key, value)| {
-                (&key[..parent.len()] == parent.as_ref()).then(|| (key, value))
-            })
-            .sorted_by_key(|t| t.0)
-            .map(|(_, value)| value);
+        .map(|res| res.map(|(key, value)| (Bytes32::new(key[32..].try_into().unwrap()), value)))
+    }
 
-        Ok(crypto::ephemeral_merkle_root(root).into())
+    /// Iterates over the storage slots the contract had at the end of the block at `height`,
+    /// sorted by their key.
+    pub fn contract_storage_slots_at_height(
+        &self,
+        contract: ContractId,
+        start_key: Option<Bytes32>,
+        direction: Option<IterDirection>,
+        height: BlockHeight,
+    ) -> impl Iterator<Item = Result<(Bytes32, Bytes32), Error>> + '_ {
+        self.iter_at_height::<Bytes32>(
+            CONTRACTS_STATE,
+            contract.as_ref().to_vec(),
+            start_key.map(|key| MultiKey::new((&contract, &key)).as_ref().to_vec()),
+            direction.unwrap_or_default(),
+            height,
+        )
+        .map(|res| res.map(|(key, value)| (Bytes32::new(key[32..].try_into().unwrap()), value)))
     }
 }
 
@@ -175,4 +216,44 @@ mod tests {
             MerkleStorage::<ContractId, Bytes32, Bytes32>::root(&mut database, &storage_id.0);
         assert!(root.is_ok())
     }
+
+    #[test]
+    fn contract_storage() {
+        let contract_id = ContractId::from([1u8; 32]);
+        let other_contract_id = ContractId::from([2u8; 32]);
+        let mut database = Database::default();
+        for (contract, key) in [
+            (&contract_id, [3u8; 32]),
+            (&contract_id, [1u8; 32]),
+            (&other_contract_id, [2u8; 32]),
+            (&contract_id, [2u8; 32]),
+        ] {
+            MerkleStorage::<ContractId, Bytes32, Bytes32>::insert(
+                &mut database,
+                contract,
+                &key.into(),
+                &Bytes32::from([9u8; 32]),
+            )
+            .unwrap();
+        }
+
+        let slots: Vec<Bytes32> = database
+            .contract_storage(contract_id, None, Some(IterDirection::Forward))
+            .map(|res| res.unwrap().0)
+            .collect();
+        assert_eq!(
+            slots,
+            vec![[1u8; 32].into(), [2u8; 32].into(), [3u8; 32].into()]
+        );
+
+        let slots: Vec<Bytes32> = database
+            .contract_storage(
+                contract_id,
+                Some([2u8; 32].into()),
+                Some(IterDirection::Reverse),
+            )
+            .map(|res| res.unwrap().0)
+            .collect();
+        assert_eq!(slots, vec![[2u8; 32].into(), [1u8; 32].into()]);
+    }
 }
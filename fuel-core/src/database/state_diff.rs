@@ -0,0 +1,212 @@
+//! The changes a database transaction makes to coins, contract balances and contract storage,
+//! so dry runs can show what a transaction would do.
+
+use crate::{
+    database::{
+        columns::{BALANCES, COIN, CONTRACTS_STATE},
+        transactional::{contract_key, DatabaseTransaction},
+        Database,
+    },
+    model::{Coin, CoinStatus},
+    state::{Error, WriteOperation},
+};
+use fuel_core_interfaces::common::{
+    fuel_storage::Storage,
+    fuel_tx::UtxoId,
+    fuel_types::{AssetId, Bytes32, ContractId, Word},
+    fuel_vm::prelude::MerkleStorage,
+};
+use std::ops::Deref;
+
+/// The changes made by a database transaction.
+#[derive(Debug, Default)]
+pub struct StateDiff {
+    /// Coins that were unspent or didn't exist before, and are spent after.
+    pub spent_coins: Vec<(UtxoId, Coin)>,
+    /// Coins that didn't exist before.
+    pub created_coins: Vec<(UtxoId, Coin)>,
+    pub contract_balances: Vec<ContractBalanceChange>,
+    pub storage_slots: Vec<StorageSlotChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractBalanceChange {
+    pub contract: ContractId,
+    pub asset_id: AssetId,
+    pub before: Word,
+    pub after: Word,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageSlotChange {
+    pub contract: ContractId,
+    pub key: Bytes32,
+    pub before: Option<Bytes32>,
+    pub after: Option<Bytes32>,
+}
+
+impl DatabaseTransaction {
+    /// The changes made by this transaction over `base`, the database it was opened from. Keys
+    /// that were written back to their previous value aren't included.
+    pub fn state_diff(&self, base: &Database) -> Result<StateDiff, Error> {
+        let mut diff = StateDiff::default();
+        for write in self.writes() {
+            let (key, column) = match &write {
+                WriteOperation::Insert(key, column, _) | WriteOperation::Remove(key, column) => {
+                    (key, *column)
+                }
+            };
+            match column {
+                COIN => {
+                    let utxo_id = utxo_id_from_bytes(key)?;
+                    let before = Storage::<UtxoId, Coin>::get(base, &utxo_id)?;
+                    let after = Storage::<UtxoId, Coin>::get(self.deref(), &utxo_id)?;
+                    let after = match after {
+                        Some(after) => after.into_owned(),
+                        None => continue,
+                    };
+                    if before.is_none() {
+                        diff.created_coins.push((utxo_id, after.clone()));
+                    }
+                    let was_spent =
+                        matches!(&before, Some(coin) if coin.status == CoinStatus::Spent);
+                    if after.status == CoinStatus::Spent && !was_spent {
+                        diff.spent_coins.push((utxo_id, after));
+                    }
+                }
+                BALANCES => {
+                    let (contract, asset_id) = contract_key(key)?;
+                    let asset_id = AssetId::new(asset_id);
+                    let balance = |db: &Database| {
+                        MerkleStorage::<ContractId, AssetId, Word>::get(db, &contract, &asset_id)
+                            .map(|balance| balance.map(|balance| *balance).unwrap_or_default())
+                    };
+                    let (before, after) = (balance(base)?, balance(self.deref())?);
+                    if before != after {
+                        diff.contract_balances.push(ContractBalanceChange {
+                            contract,
+                            asset_id,
+                            before,
+                            after,
+                        });
+                    }
+                }
+                CONTRACTS_STATE => {
+                    let (contract, key) = contract_key(key)?;
+                    let key = Bytes32::new(key);
+                    let slot = |db: &Database| {
+                        MerkleStorage::<ContractId, Bytes32, Bytes32>::get(db, &contract, &key)
+                            .map(|value| value.map(|value| *value))
+                    };
+                    let (before, after) = (slot(base)?, slot(self.deref())?);
+                    if before != after {
+                        diff.storage_slots.push(StorageSlotChange {
+                            contract,
+                            key,
+                            before,
+                            after,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(diff)
+    }
+}
+
+fn utxo_id_from_bytes(key: &[u8]) -> Result<UtxoId, Error> {
+    if key.len() != Bytes32::LEN + 1 {
+        return Err(Error::Codec);
+    }
+    let tx_id = Bytes32::new(key[..Bytes32::LEN].try_into().expect("checked length"));
+    Ok(UtxoId::new(tx_id, key[Bytes32::LEN]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::DerefMut;
+
+    fn coin(amount: Word, status: CoinStatus) -> Coin {
+        Coin {
+            owner: Default::default(),
+            amount,
+            asset_id: Default::default(),
+            maturity: Default::default(),
+            status,
+            block_created: Default::default(),
+        }
+    }
+
+    #[test]
+    fn diff_holds_the_changed_coins_balances_and_slots() {
+        let contract = ContractId::from([1; 32]);
+        let asset_id = AssetId::from([2; 32]);
+        let (slot, unchanged_slot) = (Bytes32::from([3; 32]), Bytes32::from([4; 32]));
+        let (spent, created) = (
+            UtxoId::new([5; 32].into(), 0),
+            UtxoId::new([6; 32].into(), 1),
+        );
+
+        let mut base = Database::default();
+        Storage::<UtxoId, Coin>::insert(&mut base, &spent, &coin(10, CoinStatus::Unspent)).unwrap();
+        MerkleStorage::<ContractId, AssetId, Word>::insert(&mut base, &contract, &asset_id, &100)
+            .unwrap();
+        MerkleStorage::<ContractId, Bytes32, Bytes32>::insert(
+            &mut base,
+            &contract,
+            &unchanged_slot,
+            &slot,
+        )
+        .unwrap();
+
+        let mut transaction = base.transaction();
+        let db = transaction.deref_mut();
+        Storage::<UtxoId, Coin>::insert(db, &spent, &coin(10, CoinStatus::Spent)).unwrap();
+        Storage::<UtxoId, Coin>::insert(db, &created, &coin(20, CoinStatus::Unspent)).unwrap();
+        MerkleStorage::<ContractId, AssetId, Word>::insert(db, &contract, &asset_id, &40).unwrap();
+        MerkleStorage::<ContractId, Bytes32, Bytes32>::insert(db, &contract, &slot, &slot).unwrap();
+        MerkleStorage::<ContractId, Bytes32, Bytes32>::insert(
+            db,
+            &contract,
+            &unchanged_slot,
+            &slot,
+        )
+        .unwrap();
+
+        let diff = transaction.state_diff(&base).unwrap();
+        let coins = |coins: &[(UtxoId, Coin)]| {
+            coins
+                .iter()
+                .map(|(utxo_id, coin)| (*utxo_id, coin.amount, coin.status))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            coins(&diff.spent_coins),
+            vec![(spent, 10, CoinStatus::Spent)]
+        );
+        assert_eq!(
+            coins(&diff.created_coins),
+            vec![(created, 20, CoinStatus::Unspent)]
+        );
+        assert_eq!(
+            diff.contract_balances,
+            vec![ContractBalanceChange {
+                contract,
+                asset_id,
+                before: 100,
+                after: 40,
+            }]
+        );
+        assert_eq!(
+            diff.storage_slots,
+            vec![StorageSlotChange {
+                contract,
+                key: slot,
+                before: None,
+                after: Some(slot),
+            }]
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{
+    database::{columns::TRANSACTION_TRACES, Database, KvStoreError},
+    executor::ExecutionTrace,
+};
+use fuel_core_interfaces::common::{fuel_storage::Storage, fuel_tx::Bytes32};
+use std::borrow::Cow;
+
+impl Storage<Bytes32, ExecutionTrace> for Database {
+    type Error = KvStoreError;
+
+    fn insert(
+        &mut self,
+        key: &Bytes32,
+        value: &ExecutionTrace,
+    ) -> Result<Option<ExecutionTrace>, KvStoreError> {
+        Database::insert(self, key.as_ref(), TRANSACTION_TRACES, value.clone()).map_err(Into::into)
+    }
+
+    fn remove(&mut self, key: &Bytes32) -> Result<Option<ExecutionTrace>, KvStoreError> {
+        Database::remove(self, key.as_ref(), TRANSACTION_TRACES).map_err(Into::into)
+    }
+
+    fn get(&self, key: &Bytes32) -> Result<Option<Cow<ExecutionTrace>>, KvStoreError> {
+        Database::get(self, key.as_ref(), TRANSACTION_TRACES).map_err(Into::into)
+    }
+
+    fn contains_key(&self, key: &Bytes32) -> Result<bool, KvStoreError> {
+        Database::exists(self, key.as_ref(), TRANSACTION_TRACES).map_err(Into::into)
+    }
+}
@@ -1,16 +1,19 @@
 use crate::{
     database::{
-        columns::{TRANSACTIONS, TRANSACTIONS_BY_OWNER_BLOCK_IDX, TRANSACTION_STATUS},
+        columns::{
+            TRANSACTIONS, TRANSACTIONS_BY_CONTRACT, TRANSACTIONS_BY_OWNER_BLOCK_IDX,
+            TRANSACTION_STATUS,
+        },
         Database, KvStoreError,
     },
     model::BlockHeight,
-    state::{Error, IterDirection},
+    state::{ColumnId, Error, IterDirection},
     tx_pool::TransactionStatus,
 };
 use fuel_core_interfaces::common::{
     fuel_storage::Storage,
     fuel_tx::{Bytes32, Transaction},
-    fuel_types::Address,
+    fuel_types::{Address, ContractId},
 };
 use std::{borrow::Cow, ops::Deref};
 
@@ -61,15 +64,7 @@ impl Database {
         start: Option<&OwnedTransactionIndexCursor>,
         direction: Option<IterDirection>,
     ) -> impl Iterator<Item = Result<(OwnedTransactionIndexCursor, Bytes32), Error>> + '_ {
-        let start =
-            start.map(|cursor| owned_tx_index_key(owner, cursor.block_height, cursor.tx_idx));
-        self.iter_all::<OwnedTransactionIndexKey, Bytes32>(
-            TRANSACTIONS_BY_OWNER_BLOCK_IDX,
-            Some(owner.to_vec()),
-            start,
-            direction,
-        )
-        .map(|res| res.map(|(key, tx_id)| (key.into(), tx_id)))
+        self.indexed_transactions(TRANSACTIONS_BY_OWNER_BLOCK_IDX, owner, start, direction)
     }
 
     pub fn record_tx_id_owner(
@@ -86,6 +81,51 @@ impl Database {
         )
     }
 
+    /// Iterates over a KV mapping of `[contract id + block height + tx idx] => transaction id`,
+    /// the same way as [`Database::owned_transactions`] does for owners.
+    pub fn contract_transactions(
+        &self,
+        contract: &ContractId,
+        start: Option<&OwnedTransactionIndexCursor>,
+        direction: Option<IterDirection>,
+    ) -> impl Iterator<Item = Result<(OwnedTransactionIndexCursor, Bytes32), Error>> + '_ {
+        self.indexed_transactions(TRANSACTIONS_BY_CONTRACT, contract, start, direction)
+    }
+
+    pub fn record_tx_id_contract(
+        &self,
+        contract: &ContractId,
+        block_height: BlockHeight,
+        tx_idx: TransactionIndex,
+        tx_id: &Bytes32,
+    ) -> Result<Option<Bytes32>, Error> {
+        self.insert(
+            owned_tx_index_key(contract, block_height, tx_idx),
+            TRANSACTIONS_BY_CONTRACT,
+            *tx_id,
+        )
+    }
+
+    /// Iterates over the transactions of an index `column` keyed by an owner or contract `prefix`,
+    /// sorted by their position on chain.
+    pub fn indexed_transactions(
+        &self,
+        column: ColumnId,
+        prefix: &[u8; 32],
+        start: Option<&OwnedTransactionIndexCursor>,
+        direction: Option<IterDirection>,
+    ) -> impl Iterator<Item = Result<(OwnedTransactionIndexCursor, Bytes32), Error>> + '_ {
+        let start =
+            start.map(|cursor| owned_tx_index_key(prefix, cursor.block_height, cursor.tx_idx));
+        self.iter_all::<OwnedTransactionIndexKey, Bytes32>(
+            column,
+            Some(prefix.to_vec()),
+            start,
+            direction,
+        )
+        .map(|res| res.map(|(key, tx_id)| (key.into(), tx_id)))
+    }
+
     pub fn update_tx_status(
         &self,
         tx_id: &Bytes32,
@@ -106,7 +146,7 @@ struct OwnedTransactionIndexKey {
 
 impl From<Vec<u8>> for OwnedTransactionIndexKey {
     fn from(bytes: Vec<u8>) -> Self {
-        // the first 32 bytes are the owner, which is already known when querying
+        // the first 32 bytes are the owner or contract, which is already known when querying
         let mut block_height_bytes: [u8; 4] = Default::default();
         block_height_bytes.copy_from_slice(&bytes[32..36]);
         let mut tx_idx_bytes: [u8; 4] = Default::default();
@@ -158,8 +198,8 @@ impl From<OwnedTransactionIndexCursor> for Vec<u8> {
     }
 }
 
-fn owned_tx_index_key(owner: &Address, height: BlockHeight, tx_idx: TransactionIndex) -> Vec<u8> {
-    // generate prefix to enable sorted indexing of transactions by owner
+fn owned_tx_index_key(owner: &[u8; 32], height: BlockHeight, tx_idx: TransactionIndex) -> Vec<u8> {
+    // generate prefix to enable sorted indexing of transactions by owner or contract
     // owner + block_height + tx_idx
     let mut key = Vec::with_capacity(40);
     key.extend(owner.as_ref());
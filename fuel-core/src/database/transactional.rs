@@ -1,5 +1,13 @@
-use crate::database::Database;
-use crate::state::in_memory::transaction::MemoryTransactionView;
+use crate::database::{
+    columns::{BALANCES, CONTRACTS_STATE, SPARSE_MERKLE_NODES, SPARSE_MERKLE_ROOTS},
+    Database,
+};
+use crate::state::{
+    in_memory::transaction::MemoryTransactionView, ColumnId, Error, WriteOperation,
+};
+use fuel_core_interfaces::common::fuel_vm::prelude::{
+    AssetId, Bytes32, ContractId, MerkleStorage, Word,
+};
 use std::{
     fmt::Debug,
     ops::{Deref, DerefMut},
@@ -47,6 +55,16 @@ impl Default for DatabaseTransaction {
 }
 
 impl DatabaseTransaction {
+    /// The column and key of every write made in this transaction
+    pub fn changed_keys(&self) -> Vec<(ColumnId, Vec<u8>)> {
+        self.changes.changed_keys()
+    }
+
+    /// Every write made in this transaction, ordered by column and key
+    pub fn writes(&self) -> Vec<WriteOperation> {
+        self.changes.writes()
+    }
+
     /// Commit all the changes in this transaction to the data source
     pub fn commit(self) -> crate::state::Result<()> {
         // TODO: should commit be fallible if this api is meant to be atomic?
@@ -54,6 +72,75 @@ impl DatabaseTransaction {
     }
 }
 
+impl Database {
+    /// Replay the writes of a transaction made over another view of the same state. The sparse
+    /// Merkle trees are updated from the contract state and balance writes rather than copied, as
+    /// their nodes depend on everything else in the tree.
+    pub fn apply_writes(&mut self, writes: Vec<WriteOperation>) -> Result<(), Error> {
+        for write in writes {
+            match write {
+                WriteOperation::Insert(_, SPARSE_MERKLE_NODES | SPARSE_MERKLE_ROOTS, _)
+                | WriteOperation::Remove(_, SPARSE_MERKLE_NODES | SPARSE_MERKLE_ROOTS) => {}
+                WriteOperation::Insert(key, CONTRACTS_STATE, value) => {
+                    let (contract, slot) = contract_key(&key)?;
+                    let value: Bytes32 = bincode::deserialize(&value).map_err(|_| Error::Codec)?;
+                    MerkleStorage::<ContractId, Bytes32, Bytes32>::insert(
+                        self,
+                        &contract,
+                        &Bytes32::new(slot),
+                        &value,
+                    )?;
+                }
+                WriteOperation::Remove(key, CONTRACTS_STATE) => {
+                    let (contract, slot) = contract_key(&key)?;
+                    MerkleStorage::<ContractId, Bytes32, Bytes32>::remove(
+                        self,
+                        &contract,
+                        &Bytes32::new(slot),
+                    )?;
+                }
+                WriteOperation::Insert(key, BALANCES, value) => {
+                    let (contract, asset_id) = contract_key(&key)?;
+                    let value: Word = bincode::deserialize(&value).map_err(|_| Error::Codec)?;
+                    MerkleStorage::<ContractId, AssetId, Word>::insert(
+                        self,
+                        &contract,
+                        &AssetId::new(asset_id),
+                        &value,
+                    )?;
+                }
+                WriteOperation::Remove(key, BALANCES) => {
+                    let (contract, asset_id) = contract_key(&key)?;
+                    MerkleStorage::<ContractId, AssetId, Word>::remove(
+                        self,
+                        &contract,
+                        &AssetId::new(asset_id),
+                    )?;
+                }
+                WriteOperation::Insert(key, column, value) => {
+                    self.data.put(key, column, value)?;
+                }
+                WriteOperation::Remove(key, column) => {
+                    self.data.delete(&key, column)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Split a key of the contract state or balances into the contract id and the slot or asset id.
+pub(super) fn contract_key(key: &[u8]) -> Result<(ContractId, [u8; 32]), Error> {
+    if key.len() != 2 * ContractId::LEN {
+        return Err(Error::Codec);
+    }
+    let (contract, key) = key.split_at(ContractId::LEN);
+    Ok((
+        ContractId::new(contract.try_into().expect("checked length")),
+        key.try_into().expect("checked length"),
+    ))
+}
+
 impl From<&Database> for DatabaseTransaction {
     fn from(source: &Database) -> Self {
         let data = Arc::new(MemoryTransactionView::new(source.data.clone()));
@@ -1,26 +1,33 @@
 use crate::{
     config::Config,
-    database::{transaction::TransactionIndex, Database, KvStoreError},
+    database::{
+        receipts::{LogIndexCursor, ReceiptIndex},
+        sparse_merkle::SparseMerkleTree,
+        transaction::TransactionIndex,
+        Database, KvStoreError,
+    },
     model::{BlockHeight, Coin, CoinStatus, FuelBlock, FuelBlockDb},
     tx_pool::TransactionStatus,
 };
 use chrono::Utc;
+pub use fuel_core_interfaces::validation::TransactionValidityError;
 use fuel_core_interfaces::{
     common::{
         fuel_asm::Word,
         fuel_merkle::{binary::MerkleTree, common::StorageMap},
         fuel_storage::Storage,
         fuel_tx::{
-            Address, AssetId, Bytes32, Input, Output, Receipt, Transaction, TxId, UtxoId,
-            ValidationError,
+            Address, AssetId, Bytes32, ConsensusParameters, Input, Output, Receipt, Transaction,
+            UtxoId,
         },
         fuel_types::{bytes::SerializableVec, ContractId},
         fuel_vm::{
             consts::REG_SP,
-            prelude::{Backtrace as FuelBacktrace, Interpreter, PredicateStorage},
+            prelude::{Backtrace as FuelBacktrace, Interpreter, StateTransition},
         },
     },
     model::FuelBlockHeader,
+    validation::TransactionValidator,
 };
 use std::{
     error::Error as StdError,
@@ -30,6 +37,17 @@ use std::{
 use thiserror::Error;
 use tracing::{debug, warn};
 
+mod parallel;
+mod replay;
+mod trace;
+
+pub use replay::BlockMismatch;
+pub use trace::{ExecutionTrace, TraceCall, TraceStep, MAX_TRACE_STEPS};
+
+/// The most times `Executor::estimate_gas` runs a transaction. The binary search over the gas
+/// limit needs at most this many runs for ranges of up to 2^31 gas.
+pub const MAX_GAS_ESTIMATE_RUNS: usize = 32;
+
 ///! The executor is used for block production and validation. Given a block, it will execute all
 /// the transactions contained in the block and persist changes to the underlying database as needed.
 /// In production mode, block fields like transaction commitments are set based on the executed txs.
@@ -48,14 +66,26 @@ pub struct Executor {
 }
 
 impl Executor {
+    /// Execute `txs` in a new block on top of the current chain, returning the executed block.
     #[tracing::instrument(skip(self))]
-    pub async fn submit_txs(&self, txs: Vec<Arc<Transaction>>) -> Result<(), Error> {
+    pub async fn submit_txs(&self, txs: Vec<Arc<Transaction>>) -> Result<FuelBlock, Error> {
         let db = self.database.clone();
 
-        for tx in txs.iter() {
-            // set status to submitted
-            db.update_tx_status(&tx.id(), TransactionStatus::Submitted { time: Utc::now() })?;
-        }
+        // stop at the first transaction that doesn't fit, the rest are left for later blocks
+        let mut gas_left = self.config.chain_conf.max_gas_per_block;
+        let mut bytes_left = self.config.chain_conf.max_block_size;
+        let txs = txs
+            .into_iter()
+            .take_while(|tx| {
+                let bytes = tx.metered_bytes_size() as u64;
+                if tx.gas_limit() > gas_left || bytes > bytes_left {
+                    return false;
+                }
+                gas_left -= tx.gas_limit();
+                bytes_left -= bytes;
+                true
+            })
+            .collect::<Vec<_>>();
 
         // setup and execute block
         let current_height = db.get_block_height()?.unwrap_or_default();
@@ -73,7 +103,7 @@ impl Executor {
         };
         // immediately execute block
         self.execute(&mut block, ExecutionMode::Production).await?;
-        Ok(())
+        Ok(block)
     }
 
     #[tracing::instrument(skip(self))]
@@ -84,7 +114,41 @@ impl Executor {
             ExecutionMode::Validation => block.id(),
         };
 
+        if mode == ExecutionMode::Production {
+            block.header.producer = self.config.block_producer.coinbase_recipient;
+        }
+
+        let gas = block.transactions_gas_limit();
+        if gas > self.config.chain_conf.max_gas_per_block {
+            return Err(Error::BlockGasLimitExceeded {
+                gas,
+                limit: self.config.chain_conf.max_gas_per_block,
+            });
+        }
+        let size = block.transactions_size();
+        if size > self.config.chain_conf.max_block_size {
+            return Err(Error::BlockSizeLimitExceeded {
+                size,
+                limit: self.config.chain_conf.max_block_size,
+            });
+        }
+
         let mut block_db_transaction = self.database.transaction();
+
+        // execute the transactions that don't conflict with each other ahead of the serial pass,
+        // which then merges their writes instead of running them again
+        let mut parallel_outcomes = if self.config.block_executor.parallel_execution {
+            self.execute_in_parallel(block, &mode, block_db_transaction.deref())
+                .await
+        } else {
+            vec![]
+        };
+
+        let params = self
+            .config
+            .chain_conf
+            .transaction_parameters_at(block.header.height);
+        let validator = self.validator(block.header.height);
         let mut storage = StorageMap::new();
         let mut txs_merkle = MerkleTree::new(&mut storage);
         let mut tx_status = vec![];
@@ -101,23 +165,11 @@ impl Executor {
                 return Err(Error::TransactionIdCollision(tx_id));
             }
 
-            self.verify_tx_predicates(tx)?;
-
-            if self.config.utxo_validation {
-                // validate transaction has at least one coin
-                self.verify_tx_has_at_least_one_coin(tx)?;
-                // validate utxos exist and maturity is properly set
-                self.verify_input_state(block_db_transaction.deref(), tx, block.header.height)?;
-                // validate transaction signature
-                tx.validate_input_signature()
-                    .map_err(TransactionValidityError::from)?;
-            }
+            // validate the transaction the same way the txpool does on submission
+            validator.validate(block_db_transaction.deref(), tx, block.header.height)?;
 
             self.compute_contract_input_utxo_ids(tx, &mode, block_db_transaction.deref())?;
 
-            // verify that the tx has enough gas to cover committed costs
-            self.verify_gas(tx)?;
-
             // index owners of inputs and outputs with tx-id, regardless of validity (hence block_tx instead of tx_db)
             self.persist_owners_index(
                 block.header.height,
@@ -126,31 +178,58 @@ impl Executor {
                 idx,
                 block_db_transaction.deref_mut(),
             )?;
+            self.persist_contracts_index(
+                block.header.height,
+                tx,
+                &tx_id,
+                idx,
+                block_db_transaction.deref_mut(),
+            )?;
 
-            // execute transaction
-            // setup database view that only lives for the duration of vm execution
-            let mut sub_block_db_commit = block_db_transaction.transaction();
-            let sub_db_view = sub_block_db_commit.deref_mut();
-            // execution vm
-            let mut vm = Interpreter::with_storage(
-                sub_db_view.clone(),
-                self.config.chain_conf.transaction_parameters,
-            );
-            let vm_result = vm
-                .transact(tx.clone())
-                .map_err(|error| Error::VmExecution {
-                    error,
-                    transaction_id: tx_id,
-                })?
-                .into_owned();
-
-            // only commit state changes if execution was a success
-            if !vm_result.should_revert() {
-                sub_block_db_commit.commit()?;
-            }
+            // the gas used by each call is only known by running the transaction again,
+            // single-stepping over the state it's executed on
+            let call_gas = if self.config.vm.store_traces {
+                self.call_gas(tx, block_db_transaction.deref(), params)
+            } else {
+                vec![]
+            };
+
+            // execute transaction, unless the parallel pass already ran the same transaction
+            let parallel_outcome = parallel_outcomes
+                .get_mut(idx)
+                .and_then(Option::take)
+                .filter(|outcome| outcome.tx == *tx);
+            let vm_result = match parallel_outcome {
+                Some(outcome) => {
+                    block_db_transaction.apply_writes(outcome.writes)?;
+                    outcome.result
+                }
+                None => {
+                    // setup database view that only lives for the duration of vm execution
+                    let mut sub_block_db_commit = block_db_transaction.transaction();
+                    let sub_db_view = sub_block_db_commit.deref_mut();
+                    // execution vm
+                    let mut vm = Interpreter::with_storage(sub_db_view.clone(), params);
+                    let vm_result = vm
+                        .transact(tx.clone())
+                        .map_err(|error| Error::VmExecution {
+                            error,
+                            transaction_id: tx_id,
+                        })?
+                        .into_owned();
+
+                    // only commit state changes if execution was a success
+                    if vm_result.should_revert() {
+                        self.log_backtrace(&vm, vm_result.receipts());
+                    } else {
+                        sub_block_db_commit.commit()?;
+                    }
+                    vm_result
+                }
+            };
 
             // update block commitment
-            let tx_fee = self.total_fee_paid(tx, vm_result.receipts())?;
+            let tx_fee = self.total_fee_paid(tx, vm_result.receipts(), &params)?;
             coinbase = coinbase.checked_add(tx_fee).ok_or(Error::FeeOverflow)?;
 
             // include the canonical serialization of the malleated tx into the commitment,
@@ -204,25 +283,31 @@ impl Executor {
                 block_db_transaction.deref_mut(),
             )?;
 
-            let status = if vm_result.should_revert() {
-                self.log_backtrace(&vm, vm_result.receipts());
-                // get reason for revert
-                let reason = vm_result
-                    .receipts()
-                    .iter()
-                    .find_map(|receipt| match receipt {
-                        // Format as `Revert($rA)`
-                        Receipt::Revert { ra, .. } => Some(format!("Revert({})", ra)),
-                        // Display PanicReason e.g. `OutOfGas`
-                        Receipt::Panic { reason, .. } => Some(format!("{}", reason.reason())),
-                        _ => None,
-                    })
-                    .unwrap_or_else(|| format!("{:?}", vm_result.state()));
+            if self.config.vm.store_traces {
+                self.persist_trace(
+                    &tx_id,
+                    vm_result.receipts(),
+                    call_gas,
+                    block_db_transaction.deref_mut(),
+                )?;
+            }
+
+            // logs of reverted transactions never took effect, so they aren't indexed
+            if !vm_result.should_revert() {
+                self.persist_logs_index(
+                    block.header.height,
+                    &tx_id,
+                    idx,
+                    vm_result.receipts(),
+                    block_db_transaction.deref_mut(),
+                )?;
+            }
 
+            let status = if vm_result.should_revert() {
                 TransactionStatus::Failed {
                     block_id: Default::default(),
                     time: block.header.time,
-                    reason,
+                    reason: revert_reason(&vm_result),
                     result: Some(*vm_result.state()),
                 }
             } else {
@@ -254,9 +339,46 @@ impl Executor {
             }
         }
 
-        let finalized_block_id = block.id();
+        // check or set the fees credited to the producer
+        match mode {
+            ExecutionMode::Production => {
+                block.header.fees = coinbase;
+            }
+            ExecutionMode::Validation => {
+                if block.header.fees != coinbase {
+                    return Err(Error::InvalidFeeAmount);
+                }
+            }
+        }
 
-        debug!("Block {:#x} fees: {}", pre_exec_block_id, coinbase);
+        // check or set the commitment to the state of every contract after the block
+        let state_root = block_db_transaction.sparse_merkle_root(&SparseMerkleTree::Contracts)?;
+        match mode {
+            ExecutionMode::Production => {
+                block.header.state_root = state_root;
+            }
+            ExecutionMode::Validation => {
+                if block.header.state_root != state_root {
+                    return Err(Error::InvalidStateRoot);
+                }
+            }
+        }
+
+        // check or set the root of the merkle tree over all previous block ids
+        let block_leaf_index = block_db_transaction.block_header_leaves_count()?;
+        let prev_root = block_db_transaction.block_header_merkle_root(block_leaf_index)?;
+        match mode {
+            ExecutionMode::Production => {
+                block.header.prev_root = prev_root;
+            }
+            ExecutionMode::Validation => {
+                if block.header.prev_root != prev_root {
+                    return Err(Error::InvalidPrevRoot);
+                }
+            }
+        }
+
+        let finalized_block_id = block.id();
 
         // check if block id doesn't match proposed block id
         if mode == ExecutionMode::Validation && pre_exec_block_id != finalized_block_id {
@@ -272,78 +394,162 @@ impl Executor {
             block_db_transaction.deref_mut(),
         )?;
 
+        // credit the fees to the producer, if there are any
+        debug!("Block {:#x} fees: {}", finalized_block_id, coinbase);
+        if coinbase > 0 {
+            Executor::insert_coin(
+                block.header.height.into(),
+                coinbase_utxo_id(&finalized_block_id),
+                &coinbase,
+                &AssetId::default(),
+                &block.header.producer,
+                block_db_transaction.deref_mut(),
+            )?;
+        }
+
+        block_db_transaction.append_block_header_merkle_leaf(
+            block_leaf_index,
+            block.header.height,
+            &finalized_block_id,
+        )?;
+
         // insert block into database
         Storage::<Bytes32, FuelBlockDb>::insert(
             block_db_transaction.deref_mut(),
             &finalized_block_id,
             &block.to_db_block(),
         )?;
+
+        // keep the previous values of changed state, so it can be queried at past heights
+        let changed_keys = block_db_transaction.changed_keys();
+        block_db_transaction.record_state_history(
+            block.header.height,
+            &self.database,
+            &changed_keys,
+        )?;
+        // keep every write of the block, including the state history, so it can be rolled back
+        let changed_keys = block_db_transaction.changed_keys();
+        block_db_transaction.record_block_undo_log(
+            block.header.height,
+            &self.database,
+            &changed_keys,
+        )?;
+        // drop the history of the blocks that left the retention window, after the undo log is
+        // recorded so rolling this block back doesn't restore it
+        if let Some(depth) = self.config.block_executor.history_depth {
+            if let Some(height) = block.header.height.checked_sub(depth) {
+                block_db_transaction.prune_history(height.into())?;
+            }
+        }
         block_db_transaction.commit()?;
         Ok(())
     }
 
-    // Waiting until accounts and genesis block setup is working
-    fn verify_input_state(
-        &self,
-        db: &Database,
-        transaction: &Transaction,
-        block_height: BlockHeight,
-    ) -> Result<(), TransactionValidityError> {
-        for input in transaction.inputs() {
-            match input {
-                Input::CoinSigned { utxo_id, .. } | Input::CoinPredicate { utxo_id, .. } => {
-                    if let Some(coin) = Storage::<UtxoId, Coin>::get(db, utxo_id)? {
-                        if coin.status == CoinStatus::Spent {
-                            return Err(TransactionValidityError::CoinAlreadySpent(*utxo_id));
-                        }
-                        if block_height < coin.block_created + coin.maturity {
-                            return Err(TransactionValidityError::CoinHasNotMatured(*utxo_id));
-                        }
-                    } else {
-                        return Err(TransactionValidityError::CoinDoesntExist(*utxo_id));
-                    }
-                }
-                Input::Contract { .. } => {}
+    /// Find the smallest gas limit `tx` can be executed with without reverting, up to the limit
+    /// its inputs can pay for. Every run of the transaction happens in a throwaway transaction
+    /// of the database, so nothing is persisted.
+    /// The search stops after `MAX_GAS_ESTIMATE_RUNS` runs, with the smallest limit found so far.
+    pub fn estimate_gas(&self, tx: &Transaction) -> Result<GasEstimate, Error> {
+        let height = self.next_block_height()?;
+        let params = self.config.chain_conf.transaction_parameters_at(height);
+        // a limit the inputs can't pay for at the gas price of the transaction fails regardless
+        // of the gas it uses
+        let max_gas = self
+            .validator(height)
+            .max_affordable_gas(tx)
+            .map_or(params.max_gas_per_tx, |gas| gas.min(params.max_gas_per_tx));
+        let vm_result = self.execute_with_gas_limit(tx, max_gas, &params)?;
+        if vm_result.should_revert() {
+            return Err(Error::RevertsWithMaxGas {
+                transaction_id: tx.id(),
+                reason: revert_reason(&vm_result),
+            });
+        }
+        let mut estimate = self.gas_estimate(max_gas, vm_result, &params)?;
+
+        // the gas used with the maximum limit is usually enough, otherwise the smallest limit is
+        // searched above it
+        let (mut low, mut high) = (estimate.gas_used, max_gas);
+        let mut gas_limit = low;
+        let mut runs = 1;
+        while low < high && runs < MAX_GAS_ESTIMATE_RUNS {
+            runs += 1;
+            let vm_result = self.execute_with_gas_limit(tx, gas_limit, &params)?;
+            if vm_result.should_revert() {
+                low = gas_limit + 1;
+            } else {
+                high = gas_limit;
+                estimate = self.gas_estimate(gas_limit, vm_result, &params)?;
             }
+            gas_limit = low + (high - low) / 2;
         }
+        Ok(estimate)
+    }
 
-        Ok(())
+    /// Run the vm over `tx` with its gas limit set to `gas_limit`, without persisting anything.
+    fn execute_with_gas_limit(
+        &self,
+        tx: &Transaction,
+        gas_limit: Word,
+        params: &ConsensusParameters,
+    ) -> Result<StateTransition, Error> {
+        let mut tx = tx.clone();
+        tx.set_gas_limit(gas_limit);
+        let db_transaction = self.database.transaction();
+        let mut vm = Interpreter::with_storage(db_transaction.deref().clone(), *params);
+        let transaction_id = tx.id();
+        let vm_result = vm
+            .transact(tx)
+            .map_err(|error| Error::VmExecution {
+                error,
+                transaction_id,
+            })?
+            .into_owned();
+        Ok(vm_result)
     }
 
-    /// Verify all the predicates of a tx.
-    pub fn verify_tx_predicates(&self, tx: &Transaction) -> Result<(), Error> {
-        // fail if tx contains any predicates when predicates are disabled
-        if !self.config.predicates {
-            let has_predicate = tx.inputs().iter().any(|input| input.is_coin_predicate());
-            if has_predicate {
-                return Err(Error::TransactionValidity(
-                    TransactionValidityError::PredicateExecutionDisabled(tx.id()),
-                ));
-            }
-        } else {
-            // otherwise attempt to validate any predicates if the feature flag is enabled
-            if !Interpreter::<PredicateStorage>::check_predicates(
-                tx.clone(),
-                self.config.chain_conf.transaction_parameters,
-            ) {
-                return Err(Error::TransactionValidity(
-                    TransactionValidityError::InvalidPredicate(tx.id()),
-                ));
-            }
-        }
-        Ok(())
+    fn gas_estimate(
+        &self,
+        gas_limit: Word,
+        vm_result: StateTransition,
+        params: &ConsensusParameters,
+    ) -> Result<GasEstimate, Error> {
+        let gas_used = vm_result
+            .receipts()
+            .iter()
+            .find_map(|receipt| match receipt {
+                Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let fee = self.total_fee_paid(vm_result.tx(), vm_result.receipts(), params)?;
+        Ok(GasEstimate {
+            gas_limit,
+            gas_used,
+            fee,
+        })
     }
 
-    /// Verify the transaction has at least one coin.
-    ///
-    /// TODO: This verification really belongs in fuel-tx, and can be removed once
-    ///       https://github.com/FuelLabs/fuel-tx/issues/118 is resolved.
-    fn verify_tx_has_at_least_one_coin(&self, tx: &Transaction) -> Result<(), Error> {
-        if tx.inputs().iter().filter(|input| input.is_coin()).count() == 0 {
-            Err(TransactionValidityError::NoCoinInput(tx.id()).into())
-        } else {
-            Ok(())
-        }
+    /// The validator of the transactions of the block at `block_height`.
+    fn validator(&self, block_height: BlockHeight) -> TransactionValidator {
+        TransactionValidator::new(
+            self.config
+                .chain_conf
+                .transaction_parameters_at(block_height),
+            self.config.utxo_validation,
+            self.config.predicates_at(block_height),
+        )
+    }
+
+    /// The transaction parameters of the block following the head of the chain, which
+    /// transactions executed outside of a block are run with.
+    fn next_block_parameters(&self) -> Result<ConsensusParameters, Error> {
+        let height = self.next_block_height()?;
+        Ok(self.config.chain_conf.transaction_parameters_at(height))
+    }
+
+    fn next_block_height(&self) -> Result<BlockHeight, Error> {
+        Ok(self.database.get_block_height()?.unwrap_or_default() + 1u32.into())
     }
 
     /// Mark inputs as spent
@@ -394,64 +600,37 @@ impl Executor {
         Ok(())
     }
 
-    /// verify that the transaction has enough gas to cover fees
-    fn verify_gas(&self, tx: &Transaction) -> Result<(), Error> {
-        if tx.gas_price() != 0 || tx.byte_price() != 0 {
-            let gas: Word = tx
-                .inputs()
-                .iter()
-                .filter_map(|input| match input {
-                    Input::CoinSigned {
-                        amount, asset_id, ..
-                    } if asset_id == &AssetId::default() => Some(amount),
-                    Input::CoinPredicate {
-                        amount, asset_id, ..
-                    } if asset_id == &AssetId::default() => Some(amount),
-                    _ => None,
-                })
-                .sum();
-            let spent_gas: Word = tx
-                .outputs()
-                .iter()
-                .filter_map(|output| match output {
-                    Output::Coin {
-                        amount, asset_id, ..
-                    } if asset_id == &AssetId::default() => Some(amount),
-                    Output::Withdrawal {
-                        amount, asset_id, ..
-                    } if asset_id == &AssetId::default() => Some(amount),
-                    _ => None,
-                })
-                .sum();
-            let factor = self
-                .config
-                .chain_conf
-                .transaction_parameters
-                .gas_price_factor as f64;
-            let byte_fees =
-                ((tx.metered_bytes_size() as Word * tx.byte_price()) as f64 / factor).ceil() as u64;
-            let gas_fees = ((tx.gas_limit() * tx.gas_price()) as f64 / factor).ceil() as u64;
-            let total_gas_required = spent_gas
-                .checked_add(byte_fees)
-                .ok_or(Error::FeeOverflow)?
-                .checked_add(gas_fees)
-                .ok_or(Error::FeeOverflow)?;
-            gas.checked_sub(total_gas_required)
-                .ok_or(Error::InsufficientFeeAmount {
-                    provided: gas,
-                    required: total_gas_required,
-                })?;
-        }
-
-        Ok(())
-    }
-
-    fn total_fee_paid(&self, tx: &Transaction, receipts: &[Receipt]) -> Result<Word, Error> {
-        let mut fee = tx.metered_bytes_size() as Word * tx.byte_price();
+    /// The fee the vm charged the transaction: the byte and gas limit costs it reserves up
+    /// front, minus the refund of the unused gas of scripts.
+    fn total_fee_paid(
+        &self,
+        tx: &Transaction,
+        receipts: &[Receipt],
+        params: &ConsensusParameters,
+    ) -> Result<Word, Error> {
+        let factor = params.gas_price_factor as f64;
+
+        let bytes = tx
+            .byte_price()
+            .checked_mul(tx.metered_bytes_size() as Word)
+            .ok_or(Error::FeeOverflow)?;
+        let bytes = (bytes as f64 / factor).ceil() as Word;
+        let gas = tx
+            .gas_price()
+            .checked_mul(tx.gas_limit())
+            .ok_or(Error::FeeOverflow)?;
+        let gas = (gas as f64 / factor).ceil() as Word;
+        let mut fee = bytes.checked_add(gas).ok_or(Error::FeeOverflow)?;
 
         for r in receipts {
             if let Receipt::ScriptResult { gas_used, .. } = r {
-                fee = fee.checked_add(*gas_used).ok_or(Error::FeeOverflow)?;
+                let unused_gas = tx.gas_limit().saturating_sub(*gas_used);
+                let refund = tx
+                    .gas_price()
+                    .checked_mul(unused_gas)
+                    .ok_or(Error::FeeOverflow)?;
+                let refund = (refund as f64 / factor).floor() as Word;
+                fee = fee.saturating_sub(refund);
             }
         }
 
@@ -611,6 +790,44 @@ impl Executor {
         Ok(())
     }
 
+    /// Record the call tree of the tx, so failed transactions can be inspected later
+    fn persist_trace(
+        &self,
+        tx_id: &Bytes32,
+        receipts: &[Receipt],
+        call_gas: Vec<Option<Word>>,
+        db: &mut Database,
+    ) -> Result<(), Error> {
+        let mut trace = ExecutionTrace::from_receipts(receipts);
+        trace.set_call_gas(call_gas);
+        if Storage::<Bytes32, ExecutionTrace>::insert(db, tx_id, &trace)?.is_some() {
+            return Err(Error::OutputAlreadyExists);
+        }
+        Ok(())
+    }
+
+    /// Index the `Log` and `LogData` receipts of the tx by their position and emitting contract
+    fn persist_logs_index(
+        &self,
+        block_height: BlockHeight,
+        tx_id: &Bytes32,
+        tx_idx: usize,
+        receipts: &[Receipt],
+        db: &mut Database,
+    ) -> Result<(), Error> {
+        for (receipt_idx, receipt) in receipts.iter().enumerate() {
+            if let Receipt::Log { .. } | Receipt::LogData { .. } = receipt {
+                let position = LogIndexCursor {
+                    block_height,
+                    tx_idx: tx_idx as TransactionIndex,
+                    receipt_idx: receipt_idx as ReceiptIndex,
+                };
+                db.record_log(&position, tx_id, receipt)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Index the tx id by owner for all of the inputs and outputs
     fn persist_owners_index(
         &self,
@@ -650,6 +867,38 @@ impl Executor {
         Ok(())
     }
 
+    /// Index the tx id by contract for all contract inputs and created contracts
+    fn persist_contracts_index(
+        &self,
+        block_height: BlockHeight,
+        tx: &Transaction,
+        tx_id: &Bytes32,
+        tx_idx: usize,
+        db: &mut Database,
+    ) -> Result<(), Error> {
+        let mut contracts = vec![];
+        for input in tx.inputs() {
+            if let Input::Contract { contract_id, .. } = input {
+                contracts.push(contract_id);
+            }
+        }
+
+        for output in tx.outputs() {
+            if let Output::ContractCreated { contract_id, .. } = output {
+                contracts.push(contract_id);
+            }
+        }
+
+        contracts.sort();
+        contracts.dedup();
+
+        for contract in contracts {
+            db.record_tx_id_contract(contract, block_height, tx_idx as TransactionIndex, tx_id)?;
+        }
+
+        Ok(())
+    }
+
     fn persist_transaction_status(
         &self,
         finalized_block_id: Bytes32,
@@ -672,37 +921,6 @@ impl Executor {
     }
 }
 
-#[derive(Debug, Error)]
-#[non_exhaustive]
-pub enum TransactionValidityError {
-    #[error("Coin input was already spent")]
-    CoinAlreadySpent(UtxoId),
-    #[error("Coin has not yet reached maturity")]
-    CoinHasNotMatured(UtxoId),
-    #[error("The specified coin doesn't exist")]
-    CoinDoesntExist(UtxoId),
-    #[error("Contract output index isn't valid: {0:#x}")]
-    InvalidContractInputIndex(UtxoId),
-    #[error("The transaction must have at least one coin input type: {0:#x}")]
-    NoCoinInput(TxId),
-    #[error("The transaction contains predicate inputs which aren't enabled: {0:#x}")]
-    PredicateExecutionDisabled(TxId),
-    #[error(
-        "The transaction contains a predicate which failed to validate: TransactionId({0:#x})"
-    )]
-    InvalidPredicate(TxId),
-    #[error("Transaction validity: {0:#?}")]
-    Validation(#[from] ValidationError),
-    #[error("Datastore error occurred")]
-    DataStoreError(Box<dyn StdError + Send + Sync>),
-}
-
-impl From<KvStoreError> for TransactionValidityError {
-    fn from(e: KvStoreError) -> Self {
-        Self::DataStoreError(Box::new(e))
-    }
-}
-
 #[derive(Error, Debug)]
 #[non_exhaustive]
 pub enum Error {
@@ -710,8 +928,6 @@ pub enum Error {
     TransactionIdCollision(Bytes32),
     #[error("output already exists")]
     OutputAlreadyExists,
-    #[error("Transaction doesn't include enough value to pay for gas: {provided} < {required}")]
-    InsufficientFeeAmount { provided: Word, required: Word },
     #[error("The computed fee caused an integer overflow")]
     FeeOverflow,
     #[error("Invalid transaction: {0}")]
@@ -734,12 +950,80 @@ pub enum Error {
     InvalidTransactionOutcome { transaction_id: Bytes32 },
     #[error("Transaction root is invalid")]
     InvalidTransactionRoot,
+    #[error("Root of the previous block ids is invalid")]
+    InvalidPrevRoot,
+    #[error("Root of the contract state is invalid")]
+    InvalidStateRoot,
     #[error("The amount of charged fees is invalid")]
     InvalidFeeAmount,
+    #[error(
+        "The transactions of the block have a gas limit of {gas}, over the block limit of {limit}"
+    )]
+    BlockGasLimitExceeded { gas: Word, limit: Word },
+    #[error("The transactions of the block are {size} bytes, over the block limit of {limit}")]
+    BlockSizeLimitExceeded { size: u64, limit: u64 },
     #[error("Block id is invalid")]
     InvalidBlockId,
     #[error("No matching utxo for contract id ${0:#x}")]
     ContractUtxoMissing(ContractId),
+    #[error("Transaction({transaction_id:#x}) reverts with the maximum gas limit: {reason}")]
+    RevertsWithMaxGas {
+        transaction_id: Bytes32,
+        reason: String,
+    },
+    #[error("Tracing instructions requires the node to be built with the `debug` feature")]
+    InstructionTracingUnavailable,
+    #[error("No block at height {0}")]
+    MissingBlock(BlockHeight),
+}
+
+/// The smallest gas limit a transaction can be executed with without reverting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasEstimate {
+    pub gas_limit: Word,
+    /// The gas used by the transaction with `gas_limit`.
+    pub gas_used: Word,
+    /// The fee paid by the transaction with `gas_limit`.
+    pub fee: Word,
+}
+
+/// Why a transaction reverted, from its `Revert` or `Panic` receipt.
+fn revert_reason(vm_result: &StateTransition) -> String {
+    vm_result
+        .receipts()
+        .iter()
+        .find_map(|receipt| match receipt {
+            // Format as `Revert($rA)`
+            Receipt::Revert { ra, .. } => Some(format!("Revert({})", ra)),
+            // Display PanicReason e.g. `OutOfGas`
+            Receipt::Panic { reason, .. } => Some(format!("{}", reason.reason())),
+            _ => None,
+        })
+        .unwrap_or_else(|| format!("{:?}", vm_result.state()))
+}
+
+/// The id of the coin the fees of the block are credited to.
+pub fn coinbase_utxo_id(block_id: &Bytes32) -> UtxoId {
+    UtxoId::new(*block_id, 0)
+}
+
+impl Error {
+    /// The id of the transaction the block failed on, if the error is tied to one.
+    pub fn transaction_id(&self) -> Option<Bytes32> {
+        match self {
+            Error::TransactionIdCollision(transaction_id)
+            | Error::MissingTransactionData { transaction_id, .. }
+            | Error::VmExecution { transaction_id, .. }
+            | Error::InvalidTransactionOutcome { transaction_id }
+            | Error::RevertsWithMaxGas { transaction_id, .. }
+            | Error::TransactionValidity(
+                TransactionValidityError::NoCoinInput(transaction_id)
+                | TransactionValidityError::PredicateExecutionDisabled(transaction_id)
+                | TransactionValidityError::InvalidPredicate(transaction_id),
+            ) => Some(*transaction_id),
+            _ => None,
+        }
+    }
 }
 
 impl From<FuelBacktrace> for Error {
@@ -763,8 +1047,7 @@ impl From<crate::state::Error> for Error {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::FuelBlockHeader;
-    use fuel_core_interfaces::common::fuel_tx::ConsensusParameters;
+    use crate::model::{FuelBlockHeader, ParameterUpgrade};
     use fuel_core_interfaces::common::{
         fuel_asm::Opcode,
         fuel_crypto::SecretKey,
@@ -772,7 +1055,7 @@ mod tests {
         fuel_types::{ContractId, Immediate12, Immediate18, Salt},
         fuel_vm::{
             consts::{REG_CGAS, REG_FP, REG_ONE, REG_ZERO},
-            prelude::{Call, CallFrame},
+            prelude::{Call, CallFrame, MerkleStorage},
             script_with_data_offset,
             util::test_helpers::TestBuilder as TxBuilder,
         },
@@ -905,7 +1188,7 @@ mod tests {
             .await;
         assert!(matches!(
             produce_result,
-            Err(Error::InsufficientFeeAmount { required, .. }) if required == (gas_limit as f64 / factor).ceil() as u64
+            Err(Error::TransactionValidity(TransactionValidityError::InsufficientFeeAmount { required, .. })) if required == (gas_limit as f64 / factor).ceil() as u64
         ));
 
         let verify_result = verifier
@@ -913,7 +1196,7 @@ mod tests {
             .await;
         assert!(matches!(
             verify_result,
-            Err(Error::InsufficientFeeAmount {required, ..}) if required == (gas_limit as f64 / factor).ceil() as u64
+            Err(Error::TransactionValidity(TransactionValidityError::InsufficientFeeAmount {required, ..})) if required == (gas_limit as f64 / factor).ceil() as u64
         ))
     }
 
@@ -1190,6 +1473,332 @@ mod tests {
         assert!(matches!(verify_result, Err(Error::InvalidTransactionRoot)))
     }
 
+    #[tokio::test]
+    async fn executor_invalidates_blocks_with_diverging_prev_root() {
+        let mut rng = StdRng::seed_from_u64(2322u64);
+        let producer = Executor {
+            database: Default::default(),
+            config: Config::local_node(),
+        };
+        let verifier = Executor {
+            database: Default::default(),
+            config: Config::local_node(),
+        };
+
+        let mut first_block = test_block(1);
+        producer
+            .execute(&mut first_block, ExecutionMode::Production)
+            .await
+            .unwrap();
+        verifier
+            .execute(&mut first_block, ExecutionMode::Validation)
+            .await
+            .unwrap();
+
+        let mut second_block = FuelBlock {
+            header: FuelBlockHeader {
+                height: 1u32.into(),
+                ..Default::default()
+            },
+            transactions: vec![],
+        };
+        producer
+            .execute(&mut second_block, ExecutionMode::Production)
+            .await
+            .unwrap();
+        // the root only covers the first block
+        assert_ne!(first_block.header.prev_root, second_block.header.prev_root);
+
+        // randomize the root of previous blocks
+        second_block.header.prev_root = rng.gen();
+
+        let verify_result = verifier
+            .execute(&mut second_block, ExecutionMode::Validation)
+            .await;
+
+        assert!(matches!(verify_result, Err(Error::InvalidPrevRoot)))
+    }
+
+    #[tokio::test]
+    async fn executor_invalidates_blocks_over_the_block_limits() {
+        let block = test_block(3);
+        let gas = block.transactions_gas_limit();
+        let size = block.transactions_size();
+
+        let mut config = Config::local_node();
+        config.chain_conf.max_gas_per_block = gas - 1;
+        let verifier = Executor {
+            database: Default::default(),
+            config,
+        };
+        let verify_result = verifier
+            .execute(&mut block.clone(), ExecutionMode::Validation)
+            .await;
+        assert!(matches!(
+            verify_result,
+            Err(Error::BlockGasLimitExceeded { gas: used, .. }) if used == gas
+        ));
+
+        let mut config = Config::local_node();
+        config.chain_conf.max_block_size = size - 1;
+        let verifier = Executor {
+            database: Default::default(),
+            config,
+        };
+        let verify_result = verifier
+            .execute(&mut block.clone(), ExecutionMode::Validation)
+            .await;
+        assert!(matches!(
+            verify_result,
+            Err(Error::BlockSizeLimitExceeded { size: used, .. }) if used == size
+        ));
+    }
+
+    #[tokio::test]
+    async fn produced_blocks_stop_at_the_block_limits() {
+        let txs = test_block(3).transactions;
+        let mut config = Config::local_node();
+        config.chain_conf.max_gas_per_block = txs[0].gas_limit() + txs[1].gas_limit();
+        let producer = Executor {
+            database: Default::default(),
+            config: config.clone(),
+        };
+        let block = producer
+            .submit_txs(txs.iter().cloned().map(Arc::new).collect())
+            .await
+            .unwrap();
+        assert_eq!(block.transactions.len(), 2);
+        assert_eq!(block.transactions[0].id(), txs[0].id());
+        assert_eq!(block.transactions[1].id(), txs[1].id());
+
+        let mut config = Config::local_node();
+        config.chain_conf.max_block_size = txs[0].metered_bytes_size() as u64;
+        let producer = Executor {
+            database: Default::default(),
+            config,
+        };
+        let block = producer
+            .submit_txs(txs.iter().cloned().map(Arc::new).collect())
+            .await
+            .unwrap();
+        assert_eq!(block.transactions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn executor_credits_fees_to_the_coinbase_recipient() {
+        let mut rng = StdRng::seed_from_u64(2322u64);
+        let recipient: Address = rng.gen();
+        let mut config = Config::local_node();
+        config.block_producer.coinbase_recipient = recipient;
+        let producer = Executor {
+            database: Default::default(),
+            config,
+        };
+        let verifier = Executor {
+            database: Default::default(),
+            config: Config::local_node(),
+        };
+
+        let tx = TxBuilder::new(2322u64)
+            .gas_limit(1000)
+            .gas_price(1)
+            .byte_price(1)
+            .coin_input(AssetId::default(), 10_000)
+            .change_output(AssetId::default())
+            .build();
+        let mut block = FuelBlock {
+            header: Default::default(),
+            transactions: vec![tx],
+        };
+        producer
+            .execute(&mut block, ExecutionMode::Production)
+            .await
+            .unwrap();
+
+        // the fees are what the transaction didn't get back as change
+        let change = match block.transactions[0].outputs()[0] {
+            Output::Change { amount, .. } => amount,
+            _ => panic!("expected a change output"),
+        };
+        assert!(change < 10_000);
+        assert_eq!(block.header.fees, 10_000 - change);
+        assert_eq!(block.header.producer, recipient);
+
+        let coin = Storage::<UtxoId, Coin>::get(&producer.database, &coinbase_utxo_id(&block.id()))
+            .unwrap()
+            .unwrap()
+            .into_owned();
+        assert_eq!(coin.owner, recipient);
+        assert_eq!(coin.amount, block.header.fees);
+        assert_eq!(coin.asset_id, AssetId::default());
+
+        // the verifier computes the same fees
+        let mut inflated = block.clone();
+        inflated.header.fees += 1;
+        let verify_result = verifier
+            .execute(&mut inflated, ExecutionMode::Validation)
+            .await;
+        assert!(matches!(verify_result, Err(Error::InvalidFeeAmount)));
+        verifier
+            .execute(&mut block, ExecutionMode::Validation)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn executor_creates_no_coin_for_blocks_without_fees() {
+        let producer = Executor {
+            database: Default::default(),
+            config: Config::local_node(),
+        };
+        let mut block = FuelBlock {
+            header: Default::default(),
+            transactions: vec![],
+        };
+        producer
+            .execute(&mut block, ExecutionMode::Production)
+            .await
+            .unwrap();
+
+        assert_eq!(block.header.fees, 0);
+        let coin = Storage::<UtxoId, Coin>::get(&producer.database, &coinbase_utxo_id(&block.id()))
+            .unwrap();
+        assert!(coin.is_none());
+    }
+
+    #[tokio::test]
+    async fn blocks_use_the_parameters_upgraded_at_their_height() {
+        let mut config = Config::local_node();
+        config.chain_conf.transaction_parameters.gas_price_factor = 1;
+        config.chain_conf.upgrades = vec![ParameterUpgrade {
+            height: 2u32.into(),
+            transaction_parameters: Some(ConsensusParameters {
+                gas_price_factor: 10,
+                ..config.chain_conf.transaction_parameters
+            }),
+            predicates: None,
+        }];
+        let producer = Executor {
+            database: Default::default(),
+            config,
+        };
+        let tx = |seed| {
+            TxBuilder::new(seed)
+                .byte_price(1)
+                .coin_input(AssetId::default(), 10_000)
+                .change_output(AssetId::default())
+                .build()
+        };
+        let (tx1, tx2) = (tx(1), tx(2));
+        let (size1, size2) = (
+            tx1.metered_bytes_size() as Word,
+            tx2.metered_bytes_size() as Word,
+        );
+
+        let first = producer.submit_txs(vec![Arc::new(tx1)]).await.unwrap();
+        let second = producer.submit_txs(vec![Arc::new(tx2)]).await.unwrap();
+        assert_eq!(second.header.height, 2u32.into());
+        assert_eq!(first.header.fees, size1);
+        assert_eq!(second.header.fees, size2.div_ceil(10));
+    }
+
+    #[tokio::test]
+    async fn executor_invalidates_blocks_with_diverging_state_root() {
+        let mut producer = Executor {
+            database: Default::default(),
+            config: Config::local_node(),
+        };
+        let verifier = Executor {
+            database: Default::default(),
+            config: Config::local_node(),
+        };
+
+        // only the producer knows about the storage of the contract
+        let contract_id = ContractId::from([1u8; 32]);
+        MerkleStorage::<ContractId, Bytes32, Bytes32>::insert(
+            &mut producer.database,
+            &contract_id,
+            &Bytes32::from([2u8; 32]),
+            &Bytes32::from([3u8; 32]),
+        )
+        .unwrap();
+
+        let mut block = test_block(1);
+        producer
+            .execute(&mut block, ExecutionMode::Production)
+            .await
+            .unwrap();
+        assert_eq!(
+            block.header.state_root,
+            producer
+                .database
+                .sparse_merkle_root(&SparseMerkleTree::Contracts)
+                .unwrap()
+        );
+        assert_ne!(block.header.state_root, Bytes32::zeroed());
+
+        let verify_result = verifier
+            .execute(&mut block, ExecutionMode::Validation)
+            .await;
+
+        assert!(matches!(verify_result, Err(Error::InvalidStateRoot)))
+    }
+
+    #[test]
+    fn gas_estimate_is_the_smallest_gas_limit_that_does_not_revert() {
+        let mut config = Config::local_node();
+        config.chain_conf.transaction_parameters.gas_price_factor = 1;
+        let executor = Executor {
+            database: Database::default(),
+            config,
+        };
+        let tx = TxBuilder::new(2322)
+            .gas_price(2)
+            .script([vec![Opcode::NOOP; 20], vec![Opcode::RET(REG_ONE)]].concat())
+            .coin_input(AssetId::default(), 1_000_000_000)
+            .change_output(AssetId::default())
+            .build();
+
+        let estimate = executor.estimate_gas(&tx).unwrap();
+        assert_eq!(estimate.gas_limit, estimate.gas_used);
+        let params = executor.config.chain_conf.transaction_parameters;
+        let vm_result = executor
+            .execute_with_gas_limit(&tx, estimate.gas_limit, &params)
+            .unwrap();
+        assert!(!vm_result.should_revert());
+        assert_eq!(
+            estimate.fee,
+            executor
+                .total_fee_paid(vm_result.tx(), vm_result.receipts(), &params)
+                .unwrap()
+        );
+        assert!(executor
+            .execute_with_gas_limit(&tx, estimate.gas_limit - 1, &params)
+            .unwrap()
+            .should_revert());
+
+        // the change of a priced transaction is only enough for its own gas limit
+        let exact_change = TxBuilder::new(2322)
+            .gas_price(2)
+            .gas_limit(1_000)
+            .script([vec![Opcode::NOOP; 20], vec![Opcode::RET(REG_ONE)]].concat())
+            .coin_input(AssetId::default(), 2_500)
+            .coin_output(AssetId::default(), 500)
+            .change_output(AssetId::default())
+            .build();
+        let estimate = executor.estimate_gas(&exact_change).unwrap();
+        assert!(estimate.gas_limit <= exact_change.gas_limit());
+        assert_eq!(estimate.gas_limit, estimate.gas_used);
+
+        let reverts = TxBuilder::new(2322)
+            .script(vec![Opcode::RVRT(REG_ONE)])
+            .build();
+        assert!(matches!(
+            executor.estimate_gas(&reverts),
+            Err(Error::RevertsWithMaxGas { reason, .. }) if reason == "Revert(1)"
+        ));
+    }
+
     // invalidate a block if a tx is missing at least one coin input
     #[tokio::test]
     async fn executor_invalidates_missing_coin_input() {
@@ -1615,4 +2224,373 @@ mod tests {
             assert!(maybe_utxo.is_none());
         }
     }
+
+    #[tokio::test]
+    async fn blocks_can_be_rolled_back() {
+        let database = Database::default();
+        let executor = Executor {
+            database: database.clone(),
+            config: Config::local_node(),
+        };
+        let tx1 = TxBuilder::new(1)
+            .coin_input(AssetId::default(), 100)
+            .coin_output(AssetId::default(), 100)
+            .build();
+        let tx2 = TxBuilder::new(2)
+            .coin_input(AssetId::default(), 200)
+            .coin_output(AssetId::default(), 200)
+            .build();
+        let (tx1_id, tx2_id) = (tx1.id(), tx2.id());
+
+        executor.submit_txs(vec![Arc::new(tx1)]).await.unwrap();
+        executor.submit_txs(vec![Arc::new(tx2)]).await.unwrap();
+        assert_eq!(database.get_block_height().unwrap(), Some(2u32.into()));
+
+        assert!(matches!(
+            database.rollback_to(3u32.into()),
+            Err(crate::state::Error::RollbackAboveChainHeight { .. })
+        ));
+
+        database.rollback_to(1u32.into()).unwrap();
+        assert_eq!(database.get_block_height().unwrap(), Some(1u32.into()));
+        assert!(Storage::<Bytes32, Transaction>::contains_key(&database, &tx1_id).unwrap());
+        assert!(!Storage::<Bytes32, Transaction>::contains_key(&database, &tx2_id).unwrap());
+        assert!(database.get_tx_status(&tx1_id).unwrap().is_some());
+        assert!(database.get_tx_status(&tx2_id).unwrap().is_none());
+        let coin = fuel_tx::UtxoId::new(tx2_id, 0);
+        assert!(Storage::<UtxoId, Coin>::get(&database, &coin)
+            .unwrap()
+            .is_none());
+
+        // the rolled back height can be produced again
+        let tx3 = TxBuilder::new(3)
+            .coin_input(AssetId::default(), 300)
+            .coin_output(AssetId::default(), 300)
+            .build();
+        let block = executor.submit_txs(vec![Arc::new(tx3)]).await.unwrap();
+        assert_eq!(block.header.height, 2u32.into());
+
+        database.rollback_to(0u32.into()).unwrap();
+        assert_eq!(database.get_block_height().unwrap(), None);
+        assert!(!Storage::<Bytes32, Transaction>::contains_key(&database, &tx1_id).unwrap());
+    }
+
+    #[tokio::test]
+    async fn history_beyond_the_retention_depth_is_pruned() {
+        let database = Database::default();
+        let mut config = Config::local_node();
+        config.block_executor.history_depth = Some(2);
+        let executor = Executor {
+            database: database.clone(),
+            config,
+        };
+        let mut tx_ids = vec![];
+        for i in 1..=4u64 {
+            let tx = TxBuilder::new(i)
+                .coin_input(AssetId::default(), 100 * i)
+                .coin_output(AssetId::default(), 100 * i)
+                .build();
+            tx_ids.push(tx.id());
+            executor.submit_txs(vec![Arc::new(tx)]).await.unwrap();
+        }
+
+        // blocks 1 and 2 left the window of the 2 latest blocks
+        assert_eq!(database.history_start().unwrap(), 2u32.into());
+        assert!(matches!(
+            database.rollback_to(1u32.into()),
+            Err(crate::state::Error::MissingUndoLog(_))
+        ));
+        // the state from the start of the window on can still be read
+        let coin_3 = fuel_tx::UtxoId::new(tx_ids[2], 0);
+        assert!(database
+            .coin_at_height(&coin_3, 2u32.into())
+            .unwrap()
+            .is_none());
+        assert!(database
+            .coin_at_height(&coin_3, 3u32.into())
+            .unwrap()
+            .is_some());
+
+        database.rollback_to(2u32.into()).unwrap();
+        assert_eq!(database.get_block_height().unwrap(), Some(2u32.into()));
+    }
+
+    #[tokio::test]
+    async fn replayed_blocks_are_compared_with_the_chain() {
+        let database = Database::default();
+        let executor = Executor {
+            database: database.clone(),
+            config: Config::local_node(),
+        };
+        let tx1 = TxBuilder::new(1)
+            .coin_input(AssetId::default(), 100)
+            .coin_output(AssetId::default(), 100)
+            .build();
+        let tx2 = TxBuilder::new(2)
+            .coin_input(AssetId::default(), 200)
+            .coin_output(AssetId::default(), 200)
+            .build();
+        let (tx1_id, tx2_id) = (tx1.id(), tx2.id());
+        executor.submit_txs(vec![Arc::new(tx1)]).await.unwrap();
+        let block = executor.submit_txs(vec![Arc::new(tx2)]).await.unwrap();
+
+        let mismatches = executor.replay(1u32.into(), 2u32.into()).await.unwrap();
+        assert!(mismatches.is_empty(), "{:?}", mismatches);
+        // nothing is written by the replay
+        assert_eq!(database.get_block_height().unwrap(), Some(2u32.into()));
+        assert!(matches!(
+            executor.replay(1u32.into(), 3u32.into()).await,
+            Err(Error::MissingBlock(_))
+        ));
+
+        let receipts = vec![Receipt::ret(Default::default(), 1, 0, 0)];
+        Storage::<Bytes32, Vec<Receipt>>::insert(&mut database.clone(), &tx2_id, &receipts)
+            .unwrap();
+        let mismatches = executor.replay(1u32.into(), 2u32.into()).await.unwrap();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].height, 2u32.into());
+        assert_eq!(mismatches[0].block_id, block.id());
+        assert!(mismatches[0].error.is_none());
+        assert_eq!(mismatches[0].transactions, vec![tx2_id]);
+
+        // a stored transaction that doesn't match the block is rejected
+        let tampered = TxBuilder::new(3)
+            .coin_input(AssetId::default(), 300)
+            .coin_output(AssetId::default(), 300)
+            .build();
+        Storage::<Bytes32, Transaction>::insert(&mut database.clone(), &tx1_id, &tampered).unwrap();
+        let mismatches = executor.replay(1u32.into(), 2u32.into()).await.unwrap();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].height, 1u32.into());
+        assert!(mismatches[0].error.is_some());
+    }
+
+    #[tokio::test]
+    async fn parallel_execution_matches_serial_execution() {
+        let mut rng = StdRng::seed_from_u64(2322);
+        // increments the word at the storage slot keyed by the contract id
+        let counter_code: Vec<u8> = vec![
+            Opcode::SRW(0x10, REG_FP),
+            Opcode::ADDI(0x10, 0x10, 1),
+            Opcode::SWW(REG_FP, 0x10),
+            Opcode::RET(REG_ONE),
+        ]
+        .into_iter()
+        .collect();
+        let (create_a, contract_a) = create_contract(counter_code.clone(), &mut rng);
+        let (create_b, contract_b) = create_contract(counter_code, &mut rng);
+
+        let db = Database::default();
+        let setup = Executor {
+            database: db.clone(),
+            config: Config::local_node(),
+        };
+        let mut first_block = FuelBlock {
+            header: Default::default(),
+            transactions: vec![create_a, create_b],
+        };
+        setup
+            .execute(&mut first_block, ExecutionMode::Production)
+            .await
+            .unwrap();
+
+        let call = |contract_id: ContractId, seed: u64| {
+            let (script, _) = script_with_data_offset!(
+                data_offset,
+                vec![
+                    Opcode::MOVI(0x10, data_offset),
+                    Opcode::CALL(0x10, REG_ZERO, REG_ZERO, REG_CGAS),
+                    Opcode::RET(REG_ONE),
+                ],
+                ConsensusParameters::DEFAULT.tx_offset()
+            );
+            TxBuilder::new(seed)
+                .gas_limit(1_000_000)
+                .script(script)
+                .script_data(Call::new(contract_id, 0, 0).to_bytes())
+                .contract_input(contract_id)
+                .coin_input(AssetId::default(), 100)
+                .contract_output(&contract_id)
+                .build()
+        };
+        let transfer = TxBuilder::new(4)
+            .coin_input(AssetId::default(), 100)
+            .coin_output(AssetId::default(), 100)
+            .build();
+        let mut spends_transfer = TxBuilder::new(5)
+            .coin_input(AssetId::default(), 100)
+            .coin_output(AssetId::default(), 100)
+            .build();
+        if let Transaction::Script { inputs, .. } = &mut spends_transfer {
+            if let Input::CoinSigned { utxo_id, .. } = &mut inputs[0] {
+                *utxo_id = UtxoId::new(transfer.id(), 0);
+            }
+        }
+        let reverts = TxBuilder::new(6)
+            .gas_limit(1_000_000)
+            .script(vec![Opcode::RVRT(REG_ONE)])
+            .contract_input(contract_b)
+            .contract_output(&contract_b)
+            .build();
+        let block = FuelBlock {
+            header: FuelBlockHeader {
+                height: 2u64.into(),
+                ..Default::default()
+            },
+            transactions: vec![
+                call(contract_a, 1),
+                call(contract_b, 2),
+                call(contract_a, 3),
+                transfer,
+                spends_transfer,
+                reverts,
+                call(contract_b, 7),
+            ],
+        };
+
+        let mut parallel_config = Config::local_node();
+        parallel_config.block_executor.parallel_execution = true;
+        let produce = |config: Config| {
+            let mut block = block.clone();
+            let database = db.transaction().deref_mut().clone();
+            async move {
+                let producer = Executor {
+                    database: database.clone(),
+                    config,
+                };
+                producer
+                    .execute(&mut block, ExecutionMode::Production)
+                    .await
+                    .unwrap();
+                (block, database)
+            }
+        };
+        let (serial_block, serial_db) = produce(Config::local_node()).await;
+        let (mut parallel_block, parallel_db) = produce(parallel_config.clone()).await;
+
+        assert_eq!(parallel_block.id(), serial_block.id());
+        assert_eq!(parallel_block.transactions, serial_block.transactions);
+        for tx in &serial_block.transactions {
+            let receipts = |db: &Database| {
+                Storage::<Bytes32, Vec<Receipt>>::get(db, &tx.id())
+                    .unwrap()
+                    .unwrap()
+                    .into_owned()
+            };
+            assert_eq!(receipts(&parallel_db), receipts(&serial_db));
+        }
+        for (contract, calls) in [(contract_a, 2), (contract_b, 2)] {
+            let counter = MerkleStorage::<ContractId, Bytes32, Bytes32>::get(
+                &parallel_db,
+                &contract,
+                &Bytes32::new(*contract),
+            )
+            .unwrap()
+            .unwrap()
+            .into_owned();
+            assert_eq!(counter[..8], (calls as Word).to_be_bytes());
+        }
+
+        // the block produced in parallel is valid when verified serially, and the other way around
+        for config in [Config::local_node(), parallel_config] {
+            let verifier = Executor {
+                database: db.transaction().deref_mut().clone(),
+                config,
+            };
+            verifier
+                .execute(&mut parallel_block, ExecutionMode::Validation)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn executor_stores_call_trees_of_transactions() {
+        let mut rng = StdRng::seed_from_u64(2322);
+        let (create, contract_id) = create_contract(
+            vec![Opcode::ADDI(0x10, REG_ZERO, 5), Opcode::RET(0x10)]
+                .into_iter()
+                .collect(),
+            &mut rng,
+        );
+        let (script, _) = script_with_data_offset!(
+            data_offset,
+            vec![
+                Opcode::MOVI(0x10, data_offset),
+                Opcode::CALL(0x10, REG_ZERO, REG_ZERO, REG_CGAS),
+                Opcode::RET(REG_ONE),
+            ],
+            ConsensusParameters::DEFAULT.tx_offset()
+        );
+        let call = TxBuilder::new(1)
+            .gas_limit(1_000_000)
+            .script(script)
+            .script_data(Call::new(contract_id, 0, 0).to_bytes())
+            .contract_input(contract_id)
+            .coin_input(AssetId::default(), 100)
+            .contract_output(&contract_id)
+            .build();
+
+        let mut config = Config::local_node();
+        config.vm.store_traces = true;
+        let executor = Executor {
+            database: Database::default(),
+            config,
+        };
+        let mut block = FuelBlock {
+            header: Default::default(),
+            transactions: vec![create, call.clone()],
+        };
+        executor
+            .execute(&mut block, ExecutionMode::Production)
+            .await
+            .unwrap();
+
+        let trace = Storage::<Bytes32, ExecutionTrace>::get(&executor.database, &call.id())
+            .unwrap()
+            .unwrap()
+            .into_owned();
+        assert_eq!(trace.calls.len(), 1);
+        assert_eq!(trace.calls[0].depth, 0);
+        assert!(matches!(trace.calls[0].call, Receipt::Call { to, .. } if to == contract_id));
+        assert!(matches!(
+            trace.calls[0].outcome,
+            Some(Receipt::Return { id, val: 5, .. }) if id == contract_id
+        ));
+        assert!(trace.steps.is_empty());
+        #[cfg(feature = "debug")]
+        assert!(matches!(trace.calls[0].gas_used, Some(gas) if gas > 0));
+        #[cfg(not(feature = "debug"))]
+        assert_eq!(trace.calls[0].gas_used, None);
+
+        let receipts = Storage::<Bytes32, Vec<Receipt>>::get(&executor.database, &call.id())
+            .unwrap()
+            .unwrap()
+            .into_owned();
+        let traced = executor.trace(&call, &receipts, true);
+        #[cfg(feature = "debug")]
+        {
+            let traced = traced.unwrap();
+            let script = ContractId::default();
+            let steps = traced
+                .steps
+                .iter()
+                .map(|step| (step.contract, step.instruction))
+                .collect_vec();
+            assert!(matches!(steps[0], (contract, Opcode::MOVI(0x10, _)) if contract == script));
+            assert_eq!(
+                steps[1..],
+                [
+                    (script, Opcode::CALL(0x10, REG_ZERO, REG_ZERO, REG_CGAS)),
+                    (contract_id, Opcode::ADDI(0x10, REG_ZERO, 5)),
+                    (contract_id, Opcode::RET(0x10)),
+                    (script, Opcode::RET(REG_ONE)),
+                ]
+            );
+            assert!(traced.steps[2].registers.contains(&(0x10, 5)));
+            assert!(matches!(traced.calls[0].gas_used, Some(gas) if gas > 0));
+        }
+        #[cfg(not(feature = "debug"))]
+        assert!(matches!(traced, Err(Error::InstructionTracingUnavailable)));
+    }
 }
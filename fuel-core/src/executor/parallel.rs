@@ -0,0 +1,270 @@
+//! Parallel execution of the transactions of a block. The transactions are partitioned into
+//! groups that can't observe each other's writes, and each group is run by the VM on a worker
+//! thread over the state at the start of the block, with at most one worker per core. The serial
+//! pass of the executor then merges the writes of every transaction in block order instead of
+//! running it again, so the outcome is the same as executing the whole block serially.
+
+use super::{ExecutionMode, Executor};
+use crate::{
+    database::Database,
+    model::{BlockHeight, FuelBlock},
+    state::WriteOperation,
+};
+use fuel_core_interfaces::common::{
+    fuel_tx::{Input, Output, Transaction, UtxoId},
+    fuel_types::ContractId,
+    fuel_vm::prelude::{Interpreter, StateTransition},
+};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    num::NonZeroUsize,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+    thread,
+};
+use tokio::sync::Semaphore;
+
+/// The outcome of a transaction executed by the parallel pass.
+pub(super) struct ParallelOutcome {
+    /// The transaction as it was given to the VM, with the contract input utxo ids set.
+    pub tx: Transaction,
+    pub result: StateTransition,
+    /// The writes of the VM, empty if the transaction reverted.
+    pub writes: Vec<WriteOperation>,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum Resource {
+    Coin(UtxoId),
+    Contract(ContractId),
+}
+
+/// Partition `txs` into groups of indexes, in block order, such that transactions of different
+/// groups never spend the same coin, spend each other's outputs or use the same contract.
+pub(super) fn independent_groups(txs: &[Transaction]) -> Vec<Vec<usize>> {
+    fn find(parents: &mut [usize], idx: usize) -> usize {
+        let mut root = idx;
+        while parents[root] != root {
+            root = parents[root];
+        }
+        parents[idx] = root;
+        root
+    }
+
+    let mut parents: Vec<usize> = (0..txs.len()).collect();
+    let mut users = HashMap::new();
+    for (idx, tx) in txs.iter().enumerate() {
+        let tx_id = tx.id();
+        let inputs = tx.inputs().iter().map(|input| match input {
+            Input::CoinSigned { utxo_id, .. } | Input::CoinPredicate { utxo_id, .. } => {
+                Resource::Coin(*utxo_id)
+            }
+            Input::Contract { contract_id, .. } => Resource::Contract(*contract_id),
+        });
+        let outputs = tx
+            .outputs()
+            .iter()
+            .enumerate()
+            .map(|(output_index, output)| match output {
+                Output::ContractCreated { contract_id, .. } => Resource::Contract(*contract_id),
+                _ => Resource::Coin(UtxoId::new(tx_id, output_index as u8)),
+            });
+        let static_contracts = match tx {
+            Transaction::Create {
+                static_contracts, ..
+            } => static_contracts.as_slice(),
+            Transaction::Script { .. } => &[],
+        };
+        let static_contracts = static_contracts.iter().copied().map(Resource::Contract);
+
+        for resource in inputs.chain(outputs).chain(static_contracts) {
+            match users.entry(resource) {
+                Entry::Occupied(user) => {
+                    let root = find(&mut parents, *user.get());
+                    let tx_root = find(&mut parents, idx);
+                    parents[tx_root.max(root)] = tx_root.min(root);
+                }
+                Entry::Vacant(user) => {
+                    user.insert(idx);
+                }
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = vec![];
+    let mut group_of_root = HashMap::new();
+    for idx in 0..txs.len() {
+        let root = find(&mut parents, idx);
+        let group = *group_of_root.entry(root).or_insert_with(|| {
+            groups.push(vec![]);
+            groups.len() - 1
+        });
+        groups[group].push(idx);
+    }
+    groups
+}
+
+impl Executor {
+    /// Execute every group of independent transactions of `block` on a worker thread of its own,
+    /// over a view of `db`, running as many groups at once as there are cores. The outcomes are
+    /// indexed like the transactions of the block. Transactions that couldn't be executed, along
+    /// with the rest of their group, have no outcome and are left to the serial pass, which also
+    /// reports their errors.
+    pub(super) async fn execute_in_parallel(
+        &self,
+        block: &FuelBlock,
+        mode: &ExecutionMode,
+        db: &Database,
+    ) -> Vec<Option<ParallelOutcome>> {
+        let groups = independent_groups(&block.transactions);
+        if groups.len() < 2 {
+            return vec![];
+        }
+
+        // run no more groups at once than there are cores, a block may have as many groups as
+        // transactions
+        let max_workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let permits = Arc::new(Semaphore::new(max_workers));
+        let mut workers = Vec::with_capacity(groups.len());
+        for group in groups {
+            let permit = permits
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("the semaphore is never closed");
+            let executor = Executor {
+                database: db.clone(),
+                config: self.config.clone(),
+            };
+            let txs = group
+                .into_iter()
+                .map(|idx| (idx, block.transactions[idx].clone()))
+                .collect::<Vec<_>>();
+            let mode = mode.clone();
+            let height = block.header.height;
+            let mut group_db = db.transaction();
+            workers.push(tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                executor.execute_group(txs, &mode, height, group_db.deref_mut())
+            }));
+        }
+
+        let mut outcomes = (0..block.transactions.len())
+            .map(|_| None)
+            .collect::<Vec<_>>();
+        for worker in workers {
+            // the transactions of a worker that panicked are left to the serial pass
+            if let Ok(group_outcomes) = worker.await {
+                for (idx, outcome) in group_outcomes {
+                    outcomes[idx] = Some(outcome);
+                }
+            }
+        }
+        outcomes
+    }
+
+    /// Run the transactions of a group in order over `db`, stopping at the first one that can't
+    /// be executed.
+    fn execute_group(
+        &self,
+        txs: Vec<(usize, Transaction)>,
+        mode: &ExecutionMode,
+        block_height: BlockHeight,
+        db: &mut Database,
+    ) -> Vec<(usize, ParallelOutcome)> {
+        let params = self
+            .config
+            .chain_conf
+            .transaction_parameters_at(block_height);
+        let mut outcomes = vec![];
+        for (idx, mut tx) in txs {
+            let tx_id = tx.id();
+            if self
+                .compute_contract_input_utxo_ids(&mut tx, mode, db)
+                .is_err()
+            {
+                break;
+            }
+
+            let sub_db_commit = db.transaction();
+            let mut vm = Interpreter::with_storage(sub_db_commit.deref().clone(), params);
+            let result = match vm.transact(tx.clone()) {
+                Ok(result) => result.into_owned(),
+                Err(_) => break,
+            };
+
+            let writes = if result.should_revert() {
+                self.log_backtrace(&vm, result.receipts());
+                vec![]
+            } else {
+                let writes = sub_db_commit.writes();
+                if sub_db_commit.commit().is_err() {
+                    break;
+                }
+                writes
+            };
+
+            // later transactions of the group may use the contract outputs
+            if self
+                .persist_outputs(block_height, result.tx(), &tx_id, db)
+                .is_err()
+            {
+                break;
+            }
+
+            outcomes.push((idx, ParallelOutcome { tx, result, writes }));
+        }
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_core_interfaces::common::fuel_vm::util::test_helpers::TestBuilder as TxBuilder;
+
+    fn spending(mut tx: Transaction, coin: UtxoId) -> Transaction {
+        if let Transaction::Script { inputs, .. } = &mut tx {
+            if let Input::CoinSigned { utxo_id, .. } = &mut inputs[0] {
+                *utxo_id = coin;
+            }
+        }
+        tx
+    }
+
+    #[test]
+    fn transactions_are_grouped_by_shared_coins_and_contracts() {
+        let contract = ContractId::from([1; 32]);
+        let uses_contract = TxBuilder::new(1)
+            .contract_input(contract)
+            .contract_output(&contract)
+            .build();
+        let independent = TxBuilder::new(2).coin_input(Default::default(), 10).build();
+        let also_uses_contract = TxBuilder::new(3)
+            .contract_input(contract)
+            .contract_output(&contract)
+            .build();
+        let creates_coin = TxBuilder::new(4)
+            .coin_input(Default::default(), 10)
+            .coin_output(Default::default(), 10)
+            .build();
+        let spends_created_coin = spending(
+            TxBuilder::new(5).coin_input(Default::default(), 10).build(),
+            UtxoId::new(creates_coin.id(), 0),
+        );
+        let double_spend = spending(
+            TxBuilder::new(6).coin_input(Default::default(), 10).build(),
+            *independent.inputs()[0].utxo_id(),
+        );
+
+        let groups = independent_groups(&[
+            uses_contract,
+            independent,
+            also_uses_contract,
+            creates_coin,
+            spends_created_coin,
+            double_spend,
+        ]);
+        assert_eq!(groups, vec![vec![0, 2], vec![1, 5], vec![3, 4]]);
+    }
+}
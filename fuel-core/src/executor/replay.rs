@@ -0,0 +1,109 @@
+//! Re-execution of stored blocks, to check that executing the chain again gives the same
+//! results, e.g. after upgrading the VM.
+
+use super::{Error, ExecutionMode, Executor};
+use crate::{
+    database::Database,
+    model::{BlockHeight, FuelBlock, FuelBlockDb},
+};
+use fuel_core_interfaces::common::{
+    fuel_storage::Storage,
+    fuel_tx::{Bytes32, Receipt, Transaction},
+};
+use std::{borrow::Cow, ops::Deref};
+
+/// A stored block whose re-execution doesn't match the chain.
+#[derive(Debug)]
+pub struct BlockMismatch {
+    pub height: BlockHeight,
+    pub block_id: Bytes32,
+    /// Why the block was rejected, e.g. its transactions root differs.
+    pub error: Option<Error>,
+    /// The transactions of the block whose status or receipts differ from the stored ones.
+    pub transactions: Vec<Bytes32>,
+}
+
+impl Executor {
+    /// Re-execute the stored blocks from `from` to `to` in validation mode, over a fork of the
+    /// state before `from`, and return the blocks that don't match the chain. Nothing is
+    /// committed. The replay stops at the first rejected block, since the blocks after it can't
+    /// be executed without it.
+    pub async fn replay(
+        &self,
+        from: BlockHeight,
+        to: BlockHeight,
+    ) -> Result<Vec<BlockMismatch>, Error> {
+        let fork = self.database.fork_at((*from).saturating_sub(1).into())?;
+        let replayer = Executor {
+            database: fork.deref().clone(),
+            config: self.config.clone(),
+        };
+
+        let mut mismatches = vec![];
+        for height in *from..=*to {
+            let height = BlockHeight::from(height);
+            let block_id = self
+                .database
+                .get_block_id(height)?
+                .ok_or(Error::MissingBlock(height))?;
+            let block = Storage::<Bytes32, FuelBlockDb>::get(&self.database, &block_id)?
+                .ok_or(Error::MissingBlock(height))?
+                .into_owned();
+            let transactions = block
+                .transactions
+                .iter()
+                .map(|tx_id| {
+                    Storage::<Bytes32, Transaction>::get(&self.database, tx_id)?
+                        .map(Cow::into_owned)
+                        .ok_or(Error::MissingTransactionData {
+                            block_id,
+                            transaction_id: *tx_id,
+                        })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            let mut replayed = FuelBlock {
+                header: block.headers,
+                transactions,
+            };
+            if let Err(error) = replayer
+                .execute(&mut replayed, ExecutionMode::Validation)
+                .await
+            {
+                mismatches.push(BlockMismatch {
+                    height,
+                    block_id,
+                    error: Some(error),
+                    transactions: vec![],
+                });
+                break;
+            }
+
+            let mut transactions = vec![];
+            for tx_id in block.transactions {
+                if outcome_differs(&self.database, &replayer.database, &tx_id)? {
+                    transactions.push(tx_id);
+                }
+            }
+            if !transactions.is_empty() {
+                mismatches.push(BlockMismatch {
+                    height,
+                    block_id,
+                    error: None,
+                    transactions,
+                });
+            }
+        }
+        Ok(mismatches)
+    }
+}
+
+/// Whether the status or the receipts of the transaction differ between the two databases.
+fn outcome_differs(chain: &Database, replayed: &Database, tx_id: &Bytes32) -> Result<bool, Error> {
+    let receipts = |db: &Database| {
+        Storage::<Bytes32, Vec<Receipt>>::get(db, tx_id)
+            .map(|receipts| receipts.map(Cow::into_owned))
+    };
+    Ok(receipts(chain)? != receipts(replayed)?
+        || chain.get_tx_status(tx_id)? != replayed.get_tx_status(tx_id)?)
+}
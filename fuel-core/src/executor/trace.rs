@@ -0,0 +1,336 @@
+//! Structured traces of the execution of a transaction. The call tree is rebuilt from the
+//! `Call`, `Return`, `Revert` and `Panic` receipts of the transaction, so it can be recorded for
+//! every executed transaction. The per-instruction trace and the gas used by each call require
+//! running the transaction again with the VM single-stepping, which is only available with the
+//! `debug` feature.
+
+use super::{Error, Executor};
+use crate::database::Database;
+use fuel_core_interfaces::common::{
+    fuel_asm::{Opcode, Word},
+    fuel_tx::{ConsensusParameters, Receipt, Transaction},
+    fuel_types::ContractId,
+};
+use serde::{Deserialize, Serialize};
+
+/// The most instructions recorded for a single transaction, to bound the size of a trace.
+pub const MAX_TRACE_STEPS: usize = 100_000;
+
+/// The execution trace of a transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    /// The gas used by the script of the transaction.
+    pub gas_used: Word,
+    /// The calls made by the transaction, depth first in the order they were made.
+    pub calls: Vec<TraceCall>,
+    /// The executed instructions, empty unless instructions were traced.
+    pub steps: Vec<TraceStep>,
+}
+
+/// A contract call made while executing a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceCall {
+    /// The number of calls enclosing this one, 0 for calls made by the script.
+    pub depth: u32,
+    /// The `Call` receipt of the call.
+    pub call: Receipt,
+    /// The `Return`, `ReturnData`, `Revert` or `Panic` receipt ending the call, if any.
+    pub outcome: Option<Receipt>,
+    /// The gas used by the call, including the calls it made. Only known with the `debug`
+    /// feature.
+    pub gas_used: Option<Word>,
+}
+
+/// An instruction executed by the VM.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceStep {
+    /// The contract the instruction belongs to, zeroed for the script.
+    pub contract: ContractId,
+    /// The offset of the instruction from the start of the script or contract code.
+    pub pc: Word,
+    pub instruction: Opcode,
+    /// The registers the instruction changed, with their new values.
+    pub registers: Vec<(u8, Word)>,
+}
+
+impl ExecutionTrace {
+    /// Rebuild the call tree of a transaction from its receipts.
+    pub fn from_receipts(receipts: &[Receipt]) -> Self {
+        let mut calls: Vec<TraceCall> = vec![];
+        let mut gas_used = 0;
+        // indexes of the calls that haven't returned yet
+        let mut open_calls = vec![];
+        for receipt in receipts {
+            match receipt {
+                Receipt::Call { .. } => {
+                    open_calls.push(calls.len());
+                    calls.push(TraceCall {
+                        depth: open_calls.len() as u32 - 1,
+                        call: receipt.clone(),
+                        outcome: None,
+                        gas_used: None,
+                    });
+                }
+                Receipt::Return { .. } | Receipt::ReturnData { .. } => {
+                    // returns of the script itself don't end any call
+                    if let Some(idx) = open_calls.pop() {
+                        calls[idx].outcome = Some(receipt.clone());
+                    }
+                }
+                // the whole transaction is reverted, so the enclosing calls never return
+                Receipt::Revert { .. } | Receipt::Panic { .. } => {
+                    if let Some(idx) = open_calls.last() {
+                        calls[*idx].outcome = Some(receipt.clone());
+                    }
+                }
+                Receipt::ScriptResult { gas_used: used, .. } => gas_used = *used,
+                _ => {}
+            }
+        }
+        Self {
+            gas_used,
+            calls,
+            steps: vec![],
+        }
+    }
+
+    /// Set the gas used by each call, given in the order of the calls.
+    pub fn set_call_gas(&mut self, gas_used: Vec<Option<Word>>) {
+        for (call, gas_used) in self.calls.iter_mut().zip(gas_used) {
+            call.gas_used = gas_used;
+        }
+    }
+}
+
+impl Executor {
+    /// Trace `tx` from the `receipts` of its execution. If `instructions` is set, the transaction
+    /// is run again single-stepping over a throwaway transaction of the database, which must hold
+    /// the state `tx` was executed on.
+    pub fn trace(
+        &self,
+        tx: &Transaction,
+        receipts: &[Receipt],
+        instructions: bool,
+    ) -> Result<ExecutionTrace, Error> {
+        let mut trace = ExecutionTrace::from_receipts(receipts);
+        if instructions {
+            let params = self.next_block_parameters()?;
+            let (steps, gas_used) = self.step_through(tx, &self.database, params, true)?;
+            trace.set_call_gas(gas_used);
+            trace.steps = steps;
+        }
+        Ok(trace)
+    }
+
+    /// The gas used by each call of `tx`, in the order of the `Call` receipts, found by running
+    /// it single-stepping over a throwaway transaction of `db`, which must hold the state `tx` is
+    /// executed on. Empty without the `debug` feature or if the transaction can't be executed.
+    pub(super) fn call_gas(
+        &self,
+        tx: &Transaction,
+        db: &Database,
+        params: ConsensusParameters,
+    ) -> Vec<Option<Word>> {
+        self.step_through(tx, db, params, false)
+            .map(|(_, gas_used)| gas_used)
+            .unwrap_or_default()
+    }
+
+    /// Run `tx` one instruction at a time over a throwaway transaction of `db`, returning the gas
+    /// used by each call, in the order of the `Call` receipts, and the executed instructions if
+    /// `record_steps` is set.
+    #[cfg(feature = "debug")]
+    fn step_through(
+        &self,
+        tx: &Transaction,
+        db: &Database,
+        params: ConsensusParameters,
+        record_steps: bool,
+    ) -> Result<(Vec<TraceStep>, Vec<Option<Word>>), Error> {
+        use fuel_core_interfaces::common::fuel_vm::{
+            consts::{REG_GGAS, REG_IS, REG_PC},
+            prelude::{Breakpoint, Call, Instruction, Interpreter, ProgramState},
+            state::ExecuteState,
+        };
+        use std::ops::Deref;
+
+        let db_transaction = db.transaction();
+        let mut vm = Interpreter::with_storage(db_transaction.deref().clone(), params);
+        // break before the first instruction of the script, which is then run one instruction at
+        // a time
+        vm.set_single_stepping(true);
+        let state = vm
+            .transact(tx.clone())
+            .map(|result| *result.state())
+            .map_err(|error| Error::VmExecution {
+                error,
+                transaction_id: tx.id(),
+            })?;
+        vm.set_single_stepping(false);
+
+        let mut steps = vec![];
+        let mut gas_used = vec![];
+        // (index, contract, remaining global gas on entry) of the calls that haven't returned yet
+        let mut open_calls: Vec<(usize, ContractId, Word)> = vec![];
+        let mut seen_receipts = vm.receipts().len();
+        // transactions without a script have no instructions
+        let mut running = matches!(state, ProgramState::RunProgram(_));
+        // without recording steps the run is only bounded by the gas limit of the transaction
+        while running && (!record_steps || steps.len() < MAX_TRACE_STEPS) {
+            let registers = vm.registers().to_vec();
+            let pc = registers[REG_PC] as usize;
+            let word = match vm.memory().get(pc..pc + Instruction::LEN) {
+                Some(bytes) => u32::from_be_bytes(bytes.try_into().expect("Slice of 4 bytes")),
+                None => break,
+            };
+            let opcode = Opcode::from(word);
+
+            // the VM runs calls to the end on its own, so a breakpoint on the entry of the callee
+            // is needed to step into it
+            let callee = match opcode {
+                Opcode::CALL(ra, ..) => vm
+                    .memory()
+                    .get(registers[ra] as usize..)
+                    .and_then(|bytes| Call::try_from(bytes).ok())
+                    .map(|call| Breakpoint::new(*call.to(), 0)),
+                _ => None,
+            };
+            if let Some(breakpoint) = callee {
+                vm.set_breakpoint(breakpoint);
+            }
+            let result = vm.instruction(Instruction::from(word));
+            if let Some(breakpoint) = &callee {
+                vm.remove_breakpoint(breakpoint);
+            }
+
+            let contract = open_calls
+                .last()
+                .map(|(_, contract, _)| *contract)
+                .unwrap_or_default();
+            running = match result {
+                Ok(ExecuteState::Proceed) | Ok(ExecuteState::DebugEvent(_)) => true,
+                // returning from a call continues in the caller
+                Ok(ExecuteState::Return(_)) | Ok(ExecuteState::ReturnData(_)) => {
+                    !open_calls.is_empty()
+                }
+                // the script returned or reverted, or the transaction panicked
+                _ => false,
+            };
+            if record_steps {
+                steps.push(TraceStep {
+                    contract,
+                    pc: registers[REG_PC] - registers[REG_IS],
+                    instruction: opcode,
+                    registers: registers
+                        .iter()
+                        .zip(vm.registers())
+                        .enumerate()
+                        .filter(|(_, (before, after))| before != after)
+                        .map(|(idx, (_, after))| (idx as u8, *after))
+                        .collect(),
+                });
+            }
+
+            let remaining_gas = vm.registers()[REG_GGAS];
+            for receipt in &vm.receipts()[seen_receipts..] {
+                match receipt {
+                    Receipt::Call { to, .. } => {
+                        open_calls.push((gas_used.len(), *to, remaining_gas));
+                        gas_used.push(None);
+                    }
+                    Receipt::Return { .. } | Receipt::ReturnData { .. } => {
+                        if let Some((idx, _, gas)) = open_calls.pop() {
+                            gas_used[idx] = Some(gas - remaining_gas);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            seen_receipts = vm.receipts().len();
+        }
+
+        // calls that are still open were reverted along with the transaction
+        if !running {
+            let remaining_gas = vm.registers()[REG_GGAS];
+            for (idx, _, gas) in open_calls {
+                gas_used[idx] = Some(gas - remaining_gas);
+            }
+        }
+        Ok((steps, gas_used))
+    }
+
+    #[cfg(not(feature = "debug"))]
+    fn step_through(
+        &self,
+        _: &Transaction,
+        _: &Database,
+        _: ConsensusParameters,
+        _: bool,
+    ) -> Result<(Vec<TraceStep>, Vec<Option<Word>>), Error> {
+        Err(Error::InstructionTracingUnavailable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_core_interfaces::common::{
+        fuel_asm::PanicReason, fuel_tx::ScriptExecutionResult, fuel_vm::prelude::InstructionResult,
+    };
+
+    fn call(to: ContractId) -> Receipt {
+        Receipt::call(
+            Default::default(),
+            to,
+            0,
+            Default::default(),
+            100,
+            0,
+            0,
+            0,
+            0,
+        )
+    }
+
+    #[test]
+    fn call_tree_is_rebuilt_from_receipts() {
+        let (outer, inner, other) = (
+            ContractId::from([1; 32]),
+            ContractId::from([2; 32]),
+            ContractId::from([3; 32]),
+        );
+        let inner_return = Receipt::ret(inner, 7, 0, 0);
+        let outer_return = Receipt::ret(outer, 1, 0, 0);
+        let panic = Receipt::panic(
+            other,
+            InstructionResult::error(PanicReason::OutOfGas, Opcode::RET(0x10).into()),
+            0,
+            0,
+        );
+        let receipts = vec![
+            call(outer),
+            call(inner),
+            inner_return.clone(),
+            outer_return.clone(),
+            call(other),
+            panic.clone(),
+            Receipt::script_result(ScriptExecutionResult::Panic, 1234),
+        ];
+
+        let trace = ExecutionTrace::from_receipts(&receipts);
+        assert_eq!(trace.gas_used, 1234);
+        let calls = trace
+            .calls
+            .into_iter()
+            .map(|call| (call.depth, call.call, call.outcome))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            calls,
+            vec![
+                (0, call(outer), Some(outer_return)),
+                (1, call(inner), Some(inner_return)),
+                (0, call(other), Some(panic)),
+            ]
+        );
+    }
+}
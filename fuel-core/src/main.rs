@@ -6,8 +6,12 @@ mod args;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let mut opt = args::Opt::parse();
+    if let Some(command) = opt.command.take() {
+        return command.exec().await;
+    }
     // load configuration
-    let config = args::Opt::parse().exec()?;
+    let config = opt.exec()?;
     // log fuel-core version
     info!("Fuel Core version v{}", env!("CARGO_PKG_VERSION"));
     trace!("Initializing in TRACE mode.");
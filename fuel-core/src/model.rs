@@ -1,5 +1,5 @@
 pub use fuel_core_interfaces::model::{
-    BlockHeight, Coin, CoinStatus, FuelBlock, FuelBlockDb, FuelBlockHeader,
+    BlockHeight, Coin, CoinStatus, FuelBlock, FuelBlockDb, FuelBlockHeader, ParameterUpgrade,
 };
 
 pub type Hash = [u8; 32];
@@ -1,4 +1,7 @@
-use async_graphql::{EmptySubscription, MergedObject, Schema, SchemaBuilder};
+use crate::{database::Database, model::BlockHeight, schema::scalars::U64};
+use async_graphql::{MergedObject, MergedSubscription, Schema, SchemaBuilder};
+use futures::{stream, Stream};
+use tokio::sync::broadcast::{self, error::RecvError};
 
 pub mod balance;
 pub mod block;
@@ -7,9 +10,11 @@ pub mod coin;
 pub mod contract;
 pub mod dap;
 pub mod health;
+pub mod log;
 pub mod node_info;
 pub mod scalars;
 pub mod tx;
+pub mod txpool;
 
 #[derive(MergedObject, Default)]
 pub struct Query(
@@ -22,23 +27,74 @@ pub struct Query(
     coin::CoinQuery,
     contract::ContractQuery,
     contract::ContractBalanceQuery,
+    contract::ContractStorageQuery,
     node_info::NodeQuery,
+    txpool::TxPoolQuery,
+    log::LogQuery,
 );
 
 #[derive(MergedObject, Default)]
 pub struct Mutation(dap::DapMutation, tx::TxMutation);
 
-// Placeholder for when we need to add subscriptions
-// #[derive(MergedSubscription, Default)]
-// pub struct Subscription();
+#[derive(MergedSubscription, Default)]
+pub struct Subscription(
+    block::BlockSubscription,
+    tx::TxStatusSubscription,
+    coin::CoinSubscription,
+);
 
-pub type CoreSchema = Schema<Query, Mutation, EmptySubscription>;
+pub type CoreSchema = Schema<Query, Mutation, Subscription>;
 
-pub fn build_schema() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
+pub fn build_schema() -> SchemaBuilder<Query, Mutation, Subscription> {
     Schema::build_with_ignore_name_conflicts(
         Query::default(),
         Mutation::default(),
-        EmptySubscription::default(),
+        Subscription::default(),
         ["TransactionConnection"],
     )
 }
+
+/// Turn a broadcast subscription into a stream of events. Subscribers that fall behind
+/// skip the events they missed instead of failing.
+pub(crate) fn broadcast_stream<T>(receiver: broadcast::Receiver<T>) -> impl Stream<Item = T>
+where
+    T: Clone + Send + 'static,
+{
+    stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((event, receiver)),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+/// Resolve the `atHeight` argument of queries that can read state at a past block height.
+pub(crate) fn historical_height(
+    db: &Database,
+    at_height: Option<U64>,
+) -> async_graphql::Result<Option<BlockHeight>> {
+    match at_height {
+        Some(height) => {
+            let current = db.get_block_height()?.unwrap_or_default();
+            let height = BlockHeight::from(height.0);
+            if height > current {
+                return Err(async_graphql::Error::new(format!(
+                    "Block height {} hasn't been reached yet, the current height is {}",
+                    height, current
+                )));
+            }
+            let start = db.history_start()?;
+            if height < start {
+                return Err(async_graphql::Error::new(format!(
+                    "The state before block height {} has been pruned",
+                    start
+                )));
+            }
+            Ok(Some(height))
+        }
+        None => Ok(None),
+    }
+}
@@ -1,13 +1,15 @@
 use crate::database::{Database, KvStoreError};
-use crate::model::{Coin as CoinModel, CoinStatus};
-use crate::schema::scalars::{Address, AssetId, U64};
-use crate::state::{Error, IterDirection};
+use crate::schema::{
+    historical_height,
+    scalars::{Address, AssetId, U64},
+};
+use crate::state::IterDirection;
 use async_graphql::InputObject;
 use async_graphql::{
     connection::{query, Connection, Edge, EmptyFields},
     Context, Object,
 };
-use fuel_core_interfaces::common::{fuel_storage::Storage, fuel_tx, fuel_types};
+use fuel_core_interfaces::common::{fuel_tx, fuel_types};
 use itertools::Itertools;
 
 pub struct Balance {
@@ -47,87 +49,44 @@ impl BalanceQuery {
         ctx: &Context<'_>,
         #[graphql(desc = "address of the owner")] owner: Address,
         #[graphql(desc = "asset_id of the coin")] asset_id: AssetId,
+        #[graphql(
+            desc = "read the balance at the end of this block instead of the latest. Nodes run with `--history-depth` only keep the state of that many latest blocks"
+        )]
+        at_height: Option<U64>,
     ) -> async_graphql::Result<Balance> {
         let db = ctx.data_unchecked::<Database>();
-
-        let balance = db
-            .owned_coins(owner.into(), None, None)
-            .map(|res| -> Result<_, Error> {
-                let id = res?;
-                Storage::<fuel_tx::UtxoId, CoinModel>::get(db, &id)
-                    .transpose()
-                    .ok_or(KvStoreError::NotFound)?
-                    .map_err(Into::into)
-            })
-            .filter_ok(|coin| {
-                coin.status == CoinStatus::Unspent && coin.asset_id == asset_id.into()
-            })
-            .try_fold(
-                Balance {
-                    owner: owner.into(),
-                    amount: 0u64,
-                    asset_id: asset_id.into(),
-                },
-                |mut balance, res| -> Result<_, Error> {
-                    let coin = res?;
-
-                    // Increase the balance
-                    balance.amount += coin.amount;
-
-                    Ok(balance)
-                },
-            )?;
-
-        Ok(balance)
+        let owner = owner.into();
+        let asset_id = asset_id.into();
+
+        let amount = match historical_height(db, at_height)? {
+            Some(height) => db.owned_balance_at_height(&owner, &asset_id, height)?,
+            None => db.owned_balance(&owner, &asset_id)?,
+        };
+
+        Ok(Balance {
+            owner,
+            amount,
+            asset_id,
+        })
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn balances(
         &self,
         ctx: &Context<'_>,
         filter: BalanceFilterInput,
+        #[graphql(
+            desc = "read the balances at the end of this block instead of the latest. Nodes run with `--history-depth` only keep the state of that many latest blocks"
+        )]
+        at_height: Option<U64>,
         first: Option<i32>,
         after: Option<String>,
         last: Option<i32>,
         before: Option<String>,
     ) -> async_graphql::Result<Connection<AssetId, Balance, EmptyFields, EmptyFields>> {
         let db = ctx.data_unchecked::<Database>();
-
-        let balances = db
-            .owned_coins(filter.owner.into(), None, None)
-            .map(|res| -> Result<_, Error> {
-                let id = res?;
-                Storage::<fuel_tx::UtxoId, CoinModel>::get(db, &id)
-                    .transpose()
-                    .ok_or(KvStoreError::NotFound)?
-                    .map_err(Into::into)
-            })
-            .filter_ok(|coin| coin.status == CoinStatus::Unspent)
-            .try_fold(
-                vec![] as Vec<Balance>,
-                |mut balances, res| -> Result<_, Error> {
-                    let coin = res?;
-
-                    // Get or create the balance for the asset
-                    let balance = if let Some(i) =
-                        balances.iter().position(|b| b.asset_id == coin.asset_id)
-                    {
-                        &mut balances[i]
-                    } else {
-                        let balance = Balance {
-                            owner: filter.owner.into(),
-                            amount: 0,
-                            asset_id: coin.asset_id,
-                        };
-                        balances.push(balance);
-                        balances.last_mut().unwrap()
-                    };
-
-                    // Increase the balance
-                    balance.amount += coin.amount;
-
-                    Ok(balances)
-                },
-            )?;
+        let owner: fuel_types::Address = filter.owner.into();
+        let at_height = historical_height(db, at_height)?;
 
         query(
             after,
@@ -157,16 +116,12 @@ impl BalanceQuery {
                     end = after;
                 }
 
-                let mut balances = balances.into_iter();
-                if direction == IterDirection::Reverse {
-                    balances = balances.rev().collect::<Vec<Balance>>().into_iter();
-                }
-                if let Some(start) = start {
-                    balances = balances
-                        .skip_while(|balance| balance.asset_id == start)
-                        .collect::<Vec<Balance>>()
-                        .into_iter();
-                }
+                let mut balances: Box<dyn Iterator<Item = Result<_, _>>> = match at_height {
+                    Some(height) => {
+                        Box::new(db.owned_balances_at_height(owner, start, Some(direction), height))
+                    }
+                    None => Box::new(db.owned_balances(owner, start, Some(direction))),
+                };
                 let mut started = None;
                 if start.is_some() {
                     // skip initial result
@@ -175,17 +130,24 @@ impl BalanceQuery {
 
                 // take desired amount of results
                 let balances = balances
-                    .take_while(|balance| {
+                    .take_while(|r| {
                         // take until we've reached the end
-                        if let Some(end) = end.as_ref() {
-                            if balance.asset_id == *end {
+                        if let (Ok((asset_id, _)), Some(end)) = (r, end.as_ref()) {
+                            if asset_id == end {
                                 return false;
                             }
                         }
                         true
                     })
-                    .take(records_to_fetch);
-                let mut balances: Vec<Balance> = balances.collect();
+                    .take(records_to_fetch)
+                    .map(|res| {
+                        res.map(|(asset_id, amount)| Balance {
+                            owner,
+                            amount,
+                            asset_id,
+                        })
+                    });
+                let mut balances: Vec<Balance> = balances.try_collect()?;
                 if direction == IterDirection::Reverse {
                     balances.reverse();
                 }
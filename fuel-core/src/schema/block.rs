@@ -1,22 +1,28 @@
 use crate::database::Database;
+use crate::executor::coinbase_utxo_id;
 use crate::schema::{
-    scalars::{BlockId, U64},
+    broadcast_stream,
+    coin::Coin,
+    scalars::{BlockId, Bytes32, U64},
     tx::types::Transaction,
 };
 use crate::{
     database::KvStoreError,
-    model::{BlockHeight, FuelBlockDb},
+    model::{BlockHeight, Coin as CoinModel, FuelBlockDb},
     state::IterDirection,
 };
 use async_graphql::{
     connection::{query, Connection, Edge, EmptyFields},
-    Context, Object,
+    Context, Object, Subscription,
 };
 use chrono::{DateTime, Utc};
+use fuel_block_importer::Service as BlockImporterService;
 use fuel_core_interfaces::common::{fuel_storage::Storage, fuel_tx, fuel_types};
+use futures::{Stream, StreamExt};
 use itertools::Itertools;
 use std::borrow::Cow;
 use std::convert::TryInto;
+use std::sync::Arc;
 
 use super::scalars::Address;
 
@@ -54,6 +60,71 @@ impl Block {
     async fn producer(&self) -> Address {
         self.0.headers.producer.into()
     }
+
+    /// Merkle root of the ids of all previous blocks.
+    async fn prev_root(&self) -> Bytes32 {
+        self.0.headers.prev_root.into()
+    }
+
+    /// Sparse Merkle root of the state and balances of every contract after the block.
+    async fn state_root(&self) -> Bytes32 {
+        self.0.headers.state_root.into()
+    }
+
+    /// Fees paid by the transactions of the block.
+    async fn fees(&self) -> U64 {
+        self.0.headers.fees.into()
+    }
+
+    /// The coin the fees were credited to the producer with, if there were any fees.
+    async fn coinbase(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Coin>> {
+        let db = ctx.data_unchecked::<Database>();
+        let utxo_id = coinbase_utxo_id(&self.0.id());
+        let coin = Storage::<fuel_tx::UtxoId, CoinModel>::get(db, &utxo_id)?
+            .map(|coin| Coin(utxo_id, coin.into_owned()));
+        Ok(coin)
+    }
+}
+
+/// Proof that a block id is a leaf of the binary Merkle tree whose root is the `prevRoot` of a
+/// later block.
+pub struct BlockHeaderProof {
+    block_id: fuel_types::Bytes32,
+    root_block: FuelBlockDb,
+    index: u64,
+    leaves_count: u64,
+    proof_set: Vec<fuel_types::Bytes32>,
+}
+
+#[Object]
+impl BlockHeaderProof {
+    async fn block_id(&self) -> BlockId {
+        self.block_id.into()
+    }
+
+    async fn root_block_id(&self) -> BlockId {
+        self.root_block.id().into()
+    }
+
+    /// The `prevRoot` of the root block.
+    async fn prev_root(&self) -> Bytes32 {
+        self.root_block.headers.prev_root.into()
+    }
+
+    /// Index of the block's leaf.
+    async fn index(&self) -> U64 {
+        self.index.into()
+    }
+
+    /// Number of leaves in the tree.
+    async fn leaves_count(&self) -> U64 {
+        self.leaves_count.into()
+    }
+
+    /// Hashes of the sibling nodes from the leaf up to the root.
+    async fn proof_set(&self) -> Vec<Bytes32> {
+        self.proof_set.iter().copied().map(Into::into).collect()
+    }
 }
 
 #[derive(Default)]
@@ -94,6 +165,43 @@ impl BlockQuery {
         Ok(block)
     }
 
+    /// Proof that a block is included in the `prevRoot` of a later block.
+    async fn block_header_proof(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "ID of the block to prove")] id: BlockId,
+        #[graphql(desc = "ID of the later block whose prevRoot is the root of the proof")]
+        root_id: BlockId,
+    ) -> async_graphql::Result<BlockHeaderProof> {
+        let db = ctx.data_unchecked::<Database>();
+        let block_leaf_index = |id: fuel_types::Bytes32| -> async_graphql::Result<_> {
+            let block = Storage::<fuel_types::Bytes32, FuelBlockDb>::get(db, &id)?
+                .ok_or("Block not found")?
+                .into_owned();
+            let index = db
+                .block_header_leaf_index(block.headers.height)?
+                .ok_or(KvStoreError::NotFound)?;
+            Ok((block, index))
+        };
+
+        let (block, index) = block_leaf_index(id.into())?;
+        let (root_block, leaves_count) = block_leaf_index(root_id.into())?;
+        if index >= leaves_count {
+            return Err(async_graphql::Error::new(
+                "The root block must come after the proven block",
+            ));
+        }
+        let proof_set = db.block_header_merkle_proof(index, leaves_count)?;
+
+        Ok(BlockHeaderProof {
+            block_id: block.id(),
+            root_block,
+            index,
+            leaves_count,
+            proof_set,
+        })
+    }
+
     async fn blocks(
         &self,
         ctx: &Context<'_>,
@@ -175,3 +283,15 @@ impl BlockQuery {
         .await
     }
 }
+
+#[derive(Default)]
+pub struct BlockSubscription;
+
+#[Subscription]
+impl BlockSubscription {
+    /// Blocks as they are imported by the node.
+    async fn blocks(&self, ctx: &Context<'_>) -> impl Stream<Item = Block> {
+        let block_importer = ctx.data_unchecked::<Arc<BlockImporterService>>();
+        broadcast_stream(block_importer.subscribe()).map(|event| Block(event.block().to_db_block()))
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    config::Config, database::Database, model::FuelBlockDb, schema::block::Block,
+    config::Config,
+    database::Database,
+    model::{BlockHeight, FuelBlockDb},
+    schema::block::Block,
     schema::scalars::U64,
 };
 use async_graphql::{Context, Object};
@@ -91,11 +94,20 @@ impl ChainInfo {
     async fn consensus_parameters(
         &self,
         ctx: &Context<'_>,
+        #[graphql(desc = "Height of the block the parameters apply to, the next block by default")]
+        height: Option<U64>,
     ) -> async_graphql::Result<ConsensusParameters> {
         let config = ctx.data_unchecked::<Config>();
+        let height = match height {
+            Some(height) => BlockHeight::from(u64::from(height)),
+            None => {
+                let db = ctx.data_unchecked::<Database>();
+                db.get_block_height()?.unwrap_or_default() + 1u32.into()
+            }
+        };
 
         Ok(ConsensusParameters(
-            config.chain_conf.transaction_parameters,
+            config.chain_conf.transaction_parameters_at(height),
         ))
     }
 }
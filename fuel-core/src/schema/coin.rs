@@ -2,18 +2,24 @@ use crate::{
     coin_query::{random_improve, SpendQueryElement},
     config::Config,
     database::{Database, KvStoreError},
-    schema::scalars::{Address, AssetId, UtxoId, U64},
-    state::IterDirection,
+    schema::{
+        broadcast_stream, historical_height,
+        scalars::{Address, AssetId, UtxoId, U64},
+    },
+    state::{Error, IterDirection},
 };
 use async_graphql::{
     connection::{query, Connection, Edge, EmptyFields},
-    Context, Enum, InputObject, Object,
+    Context, Enum, InputObject, Object, Subscription,
 };
+use fuel_block_importer::Service as BlockImporterService;
 use fuel_core_interfaces::{
     common::{fuel_storage::Storage, fuel_tx},
     model::{Coin as CoinModel, CoinStatus as CoinStatusModel},
 };
+use futures::{stream, Stream, StreamExt};
 use itertools::Itertools;
+use std::sync::Arc;
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq)]
 #[graphql(remote = "CoinStatusModel")]
@@ -22,7 +28,7 @@ pub enum CoinStatus {
     Spent,
 }
 
-pub struct Coin(fuel_tx::UtxoId, CoinModel);
+pub struct Coin(pub(crate) fuel_tx::UtxoId, pub(crate) CoinModel);
 
 #[Object]
 impl Coin {
@@ -80,24 +86,37 @@ impl CoinQuery {
         &self,
         ctx: &Context<'_>,
         #[graphql(desc = "The ID of the coin")] utxo_id: UtxoId,
+        #[graphql(
+            desc = "read the coin at the end of this block instead of the latest. Nodes run with `--history-depth` only keep the state of that many latest blocks"
+        )]
+        at_height: Option<U64>,
     ) -> async_graphql::Result<Option<Coin>> {
         let utxo_id = utxo_id.0;
         let db = ctx.data_unchecked::<Database>().clone();
-        let block = Storage::<fuel_tx::UtxoId, CoinModel>::get(&db, &utxo_id)?
-            .map(|coin| Coin(utxo_id, coin.into_owned()));
-        Ok(block)
+        let coin = match historical_height(&db, at_height)? {
+            Some(height) => db.coin_at_height(&utxo_id, height)?,
+            None => Storage::<fuel_tx::UtxoId, CoinModel>::get(&db, &utxo_id)?
+                .map(|coin| coin.into_owned()),
+        };
+        Ok(coin.map(|coin| Coin(utxo_id, coin)))
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn coins(
         &self,
         ctx: &Context<'_>,
         filter: CoinFilterInput,
+        #[graphql(
+            desc = "read the coins at the end of this block instead of the latest. Nodes run with `--history-depth` only keep the state of that many latest blocks"
+        )]
+        at_height: Option<U64>,
         first: Option<i32>,
         after: Option<String>,
         last: Option<i32>,
         before: Option<String>,
     ) -> async_graphql::Result<Connection<UtxoId, Coin, EmptyFields, EmptyFields>> {
         let db = ctx.data_unchecked::<Database>();
+        let at_height = historical_height(db, at_height)?;
 
         query(
             after,
@@ -129,7 +148,16 @@ impl CoinQuery {
 
                 let owner: fuel_tx::Address = filter.owner.into();
 
-                let mut coin_ids = db.owned_coins(owner, start, Some(direction));
+                let mut coin_ids: Box<dyn Iterator<Item = Result<fuel_tx::UtxoId, Error>>> =
+                    match filter.asset_id {
+                        Some(asset_id) => Box::new(db.owned_coins_by_asset_id(
+                            owner,
+                            asset_id.into(),
+                            start,
+                            Some(direction),
+                        )),
+                        None => Box::new(db.owned_coins(owner, start, Some(direction))),
+                    };
                 let mut started = None;
                 if start.is_some() {
                     // skip initial result
@@ -154,23 +182,29 @@ impl CoinQuery {
                 }
 
                 // TODO: do a batch get instead
-                let coins: Vec<Coin> = coins
-                    .into_iter()
-                    .map(|id| {
-                        Storage::<fuel_tx::UtxoId, CoinModel>::get(db, &id)
-                            .transpose()
-                            .ok_or(KvStoreError::NotFound)?
-                            .map(|coin| Coin(id, coin.into_owned()))
-                    })
-                    .try_collect()?;
-
-                // filter coins by asset ID
-                let mut coins = coins;
-                if let Some(asset_id) = filter.asset_id {
-                    coins.retain(|coin| coin.1.asset_id == asset_id.0);
-                }
+                let coins: Vec<Coin> = match at_height {
+                    // the owner indexes only grow, coins created after the height are skipped
+                    Some(height) => coins
+                        .into_iter()
+                        .filter_map(|id| {
+                            db.coin_at_height(&id, height)
+                                .map(|coin| coin.map(|coin| Coin(id, coin)))
+                                .transpose()
+                        })
+                        .try_collect()?,
+                    None => coins
+                        .into_iter()
+                        .map(|id| {
+                            Storage::<fuel_tx::UtxoId, CoinModel>::get(db, &id)
+                                .transpose()
+                                .ok_or(KvStoreError::NotFound)?
+                                .map(|coin| Coin(id, coin.into_owned()))
+                        })
+                        .try_collect()?,
+                };
 
                 // filter coins by status
+                let mut coins = coins;
                 coins.retain(|coin| coin.1.status == CoinStatusModel::Unspent);
 
                 let mut connection =
@@ -208,13 +242,19 @@ impl CoinQuery {
             .iter()
             .map(|e| (owner, e.asset_id.0, e.amount.0))
             .collect();
-        let max_inputs: u64 =
-            max_inputs.unwrap_or(config.chain_conf.transaction_parameters.max_inputs);
+        let db = ctx.data_unchecked::<Database>();
+
+        // the coins are spent in the next block at the earliest
+        let next_height = db.get_block_height()?.unwrap_or_default() + 1u32.into();
+        let max_inputs: u64 = max_inputs.unwrap_or(
+            config
+                .chain_conf
+                .transaction_parameters_at(next_height)
+                .max_inputs,
+        );
         let excluded_ids: Option<Vec<fuel_tx::UtxoId>> =
             excluded_ids.map(|ids| ids.into_iter().map(|id| id.0).collect());
 
-        let db = ctx.data_unchecked::<Database>();
-
         let coins = random_improve(db, &spend_query, max_inputs, excluded_ids.as_ref())?
             .into_iter()
             .map(|(id, coin)| Coin(id, coin))
@@ -223,3 +263,52 @@ impl CoinQuery {
         Ok(coins)
     }
 }
+
+#[derive(Default)]
+pub struct CoinSubscription;
+
+#[Subscription]
+impl CoinSubscription {
+    /// Coins created for `owner` by the transactions of newly imported blocks.
+    async fn coins_received(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Address of the owner")] owner: Address,
+    ) -> impl Stream<Item = async_graphql::Result<Coin>> {
+        let db = ctx.data_unchecked::<Database>().clone();
+        let block_importer = ctx.data_unchecked::<Arc<BlockImporterService>>();
+        let owner: fuel_tx::Address = owner.0;
+
+        broadcast_stream(block_importer.subscribe()).flat_map(move |event| {
+            let coins: Vec<async_graphql::Result<Coin>> = event
+                .block()
+                .transactions
+                .iter()
+                .flat_map(|tx| {
+                    let tx_id = tx.id();
+                    tx.outputs()
+                        .iter()
+                        .enumerate()
+                        .filter_map(move |(idx, output)| match output {
+                            fuel_tx::Output::Coin { to, .. }
+                            | fuel_tx::Output::Change { to, .. }
+                            | fuel_tx::Output::Variable { to, .. }
+                                if *to == owner =>
+                            {
+                                Some(fuel_tx::UtxoId::new(tx_id, idx as u8))
+                            }
+                            _ => None,
+                        })
+                })
+                // outputs without any value don't create coins
+                .filter_map(|utxo_id| {
+                    Storage::<fuel_tx::UtxoId, CoinModel>::get(&db, &utxo_id)
+                        .map_err(Into::into)
+                        .transpose()
+                        .map(|coin| coin.map(|coin| Coin(utxo_id, coin.into_owned())))
+                })
+                .collect();
+            stream::iter(coins)
+        })
+    }
+}
@@ -1,5 +1,11 @@
-use crate::database::{Database, KvStoreError};
-use crate::schema::scalars::{AssetId, ContractId, HexString, Salt, U64};
+use crate::database::{
+    sparse_merkle::{self, SparseMerkleTree},
+    Database, KvStoreError,
+};
+use crate::schema::{
+    historical_height,
+    scalars::{AssetId, Bytes32, ContractId, HexString, Salt, U64},
+};
 use crate::state::IterDirection;
 use anyhow::anyhow;
 use async_graphql::{
@@ -7,8 +13,9 @@ use async_graphql::{
     Context, InputObject, Object,
 };
 use fuel_core_interfaces::common::{
-    fuel_storage::Storage, fuel_tx, fuel_types, fuel_vm,
-    fuel_vm::prelude::Contract as FuelVmContract,
+    fuel_storage::Storage,
+    fuel_tx, fuel_types, fuel_vm,
+    fuel_vm::prelude::{Contract as FuelVmContract, MerkleStorage},
 };
 use std::iter::IntoIterator;
 
@@ -106,6 +113,10 @@ impl ContractBalanceQuery {
         ctx: &Context<'_>,
         contract: ContractId,
         asset: AssetId,
+        #[graphql(
+            desc = "read the balance at the end of this block instead of the latest. Nodes run with `--history-depth` only keep the state of that many latest blocks"
+        )]
+        at_height: Option<U64>,
     ) -> async_graphql::Result<ContractBalance> {
         let contract_id: fuel_types::ContractId = contract.0;
 
@@ -113,12 +124,16 @@ impl ContractBalanceQuery {
 
         let asset_id: fuel_types::AssetId = asset.into();
 
-        let result = fuel_vm::storage::InterpreterStorage::merkle_contract_asset_id_balance(
-            &db,
-            &contract_id,
-            &asset_id,
-        );
-        let balance = result.unwrap().unwrap_or_default();
+        let balance = match historical_height(&db, at_height)? {
+            Some(height) => db.contract_balance_at_height(&contract_id, &asset_id, height)?,
+            None => fuel_vm::storage::InterpreterStorage::merkle_contract_asset_id_balance(
+                &db,
+                &contract_id,
+                &asset_id,
+            )
+            .unwrap(),
+        }
+        .unwrap_or_default();
 
         Ok(ContractBalance {
             contract: contract.into(),
@@ -212,3 +227,275 @@ impl ContractBalanceQuery {
         .await
     }
 }
+
+pub struct ContractStorageSlot {
+    contract: fuel_types::ContractId,
+    key: fuel_types::Bytes32,
+    value: fuel_types::Bytes32,
+}
+
+#[Object]
+impl ContractStorageSlot {
+    async fn contract(&self) -> ContractId {
+        self.contract.into()
+    }
+
+    async fn key(&self) -> Bytes32 {
+        self.key.into()
+    }
+
+    async fn value(&self) -> Bytes32 {
+        self.value.into()
+    }
+}
+
+/// Proof of a key of a sparse Merkle tree, or of its absence.
+pub struct SparseMerkleProof(sparse_merkle::SparseMerkleProof);
+
+#[Object]
+impl SparseMerkleProof {
+    /// Key of the leaf the path of the proven key ends in, if it doesn't end in an empty
+    /// subtree. It is the proven key itself if the key is in the tree.
+    async fn leaf_key(&self) -> Option<Bytes32> {
+        self.0.leaf.map(|(key, _)| key.into())
+    }
+
+    /// Hash of the data of that leaf.
+    async fn leaf_data_hash(&self) -> Option<Bytes32> {
+        self.0.leaf.map(|(_, data_hash)| data_hash.into())
+    }
+
+    /// Hashes of the sibling nodes from the leaf up to the root.
+    async fn proof_set(&self) -> Vec<Bytes32> {
+        self.0.proof_set.iter().copied().map(Into::into).collect()
+    }
+}
+
+/// Proof of the value of a storage slot, through the storage root of the contract, up to the
+/// state root of the latest block.
+pub struct ContractStorageSlotProof {
+    contract: fuel_types::ContractId,
+    key: fuel_types::Bytes32,
+    value: Option<fuel_types::Bytes32>,
+    storage_root: fuel_types::Bytes32,
+    balances_root: fuel_types::Bytes32,
+    state_root: fuel_types::Bytes32,
+    block_height: u64,
+    slot_proof: sparse_merkle::SparseMerkleProof,
+    contract_proof: sparse_merkle::SparseMerkleProof,
+}
+
+#[Object]
+impl ContractStorageSlotProof {
+    async fn contract(&self) -> ContractId {
+        self.contract.into()
+    }
+
+    async fn key(&self) -> Bytes32 {
+        self.key.into()
+    }
+
+    /// The value of the slot, or null if the proof is of its absence.
+    async fn value(&self) -> Option<Bytes32> {
+        self.value.map(Into::into)
+    }
+
+    /// Root of the storage tree of the contract.
+    async fn storage_root(&self) -> Bytes32 {
+        self.storage_root.into()
+    }
+
+    /// Root of the balances tree of the contract.
+    async fn balances_root(&self) -> Bytes32 {
+        self.balances_root.into()
+    }
+
+    /// The `stateRoot` of the block at `blockHeight`.
+    async fn state_root(&self) -> Bytes32 {
+        self.state_root.into()
+    }
+
+    async fn block_height(&self) -> U64 {
+        self.block_height.into()
+    }
+
+    /// Proof of the slot in the storage tree of the contract.
+    async fn slot_proof(&self) -> SparseMerkleProof {
+        SparseMerkleProof(self.slot_proof.clone())
+    }
+
+    /// Proof of the storage and balances roots of the contract in the state tree.
+    async fn contract_proof(&self) -> SparseMerkleProof {
+        SparseMerkleProof(self.contract_proof.clone())
+    }
+}
+
+#[derive(Default)]
+pub struct ContractStorageQuery;
+
+#[Object]
+impl ContractStorageQuery {
+    /// The value of a storage slot of the contract, if the slot was ever written.
+    async fn contract_storage_slot(
+        &self,
+        ctx: &Context<'_>,
+        contract: ContractId,
+        key: Bytes32,
+        #[graphql(
+            desc = "read the slot at the end of this block instead of the latest. Nodes run with `--history-depth` only keep the state of that many latest blocks"
+        )]
+        at_height: Option<U64>,
+    ) -> async_graphql::Result<Option<ContractStorageSlot>> {
+        let db = ctx.data_unchecked::<Database>();
+        let contract = contract.0;
+        let key = key.0;
+
+        let value = match historical_height(db, at_height)? {
+            Some(height) => db.contract_storage_at_height(&contract, &key, height)?,
+            None => MerkleStorage::<
+                fuel_types::ContractId,
+                fuel_types::Bytes32,
+                fuel_types::Bytes32,
+            >::get(db, &contract, &key)?
+            .map(|value| value.into_owned()),
+        };
+        Ok(value.map(|value| ContractStorageSlot {
+            contract,
+            key,
+            value,
+        }))
+    }
+
+    /// Proof of the value of a storage slot of the contract, or of its absence, against the
+    /// state root of the latest block.
+    async fn contract_storage_slot_proof(
+        &self,
+        ctx: &Context<'_>,
+        contract: ContractId,
+        key: Bytes32,
+    ) -> async_graphql::Result<ContractStorageSlotProof> {
+        let db = ctx.data_unchecked::<Database>();
+        let contract = contract.0;
+        let key = key.0;
+        let state_tree = SparseMerkleTree::ContractState(contract);
+        let balances_tree = SparseMerkleTree::ContractBalances(contract);
+
+        let value =
+            MerkleStorage::<fuel_types::ContractId, fuel_types::Bytes32, fuel_types::Bytes32>::get(
+                db, &contract, &key,
+            )?
+            .map(|value| value.into_owned());
+        Ok(ContractStorageSlotProof {
+            contract,
+            key,
+            value,
+            storage_root: db.sparse_merkle_root(&state_tree)?,
+            balances_root: db.sparse_merkle_root(&balances_tree)?,
+            state_root: db.sparse_merkle_root(&SparseMerkleTree::Contracts)?,
+            block_height: db.get_block_height()?.unwrap_or_default().into(),
+            slot_proof: db.sparse_merkle_proof(&state_tree, &key)?,
+            contract_proof: db.sparse_merkle_proof(
+                &SparseMerkleTree::Contracts,
+                &fuel_types::Bytes32::new(*contract),
+            )?,
+        })
+    }
+
+    /// The storage slots of the contract, sorted by their key.
+    #[allow(clippy::too_many_arguments)]
+    async fn contract_storage(
+        &self,
+        ctx: &Context<'_>,
+        contract: ContractId,
+        #[graphql(
+            desc = "read the slots at the end of this block instead of the latest. Nodes run with `--history-depth` only keep the state of that many latest blocks"
+        )]
+        at_height: Option<U64>,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+    ) -> async_graphql::Result<Connection<Bytes32, ContractStorageSlot, EmptyFields, EmptyFields>>
+    {
+        let db = ctx.data_unchecked::<Database>().clone();
+        let contract = contract.0;
+        let at_height = historical_height(&db, at_height)?;
+
+        query(
+            after,
+            before,
+            first,
+            last,
+            |after: Option<Bytes32>, before: Option<Bytes32>, first, last| async move {
+                let (records_to_fetch, direction) = if let Some(first) = first {
+                    (first, IterDirection::Forward)
+                } else if let Some(last) = last {
+                    (last, IterDirection::Reverse)
+                } else {
+                    (0, IterDirection::Forward)
+                };
+
+                if (first.is_some() && before.is_some())
+                    || (after.is_some() && before.is_some())
+                    || (last.is_some() && after.is_some())
+                {
+                    return Err(anyhow!("Wrong argument combination"));
+                }
+
+                let start = if direction == IterDirection::Forward {
+                    after
+                } else {
+                    before
+                };
+
+                let start_key = start.map(|key| key.0);
+                let mut slots_iter: Box<dyn Iterator<Item = Result<_, _>>> = match at_height {
+                    Some(height) => Box::new(db.contract_storage_slots_at_height(
+                        contract,
+                        start_key,
+                        Some(direction),
+                        height,
+                    )),
+                    None => Box::new(db.contract_storage(contract, start_key, Some(direction))),
+                };
+
+                let mut started = None;
+                if start.is_some() {
+                    started = slots_iter.next();
+                }
+
+                let mut slots = slots_iter
+                    .take(records_to_fetch + 1)
+                    .map(|slot| {
+                        let (key, value) = slot?;
+
+                        Ok(ContractStorageSlot {
+                            contract,
+                            key,
+                            value,
+                        })
+                    })
+                    .collect::<Result<Vec<ContractStorageSlot>, KvStoreError>>()?;
+
+                let has_next_page = slots.len() > records_to_fetch;
+
+                if has_next_page {
+                    slots.pop();
+                }
+
+                if direction == IterDirection::Reverse {
+                    slots.reverse();
+                }
+
+                let mut connection = Connection::new(started.is_some(), has_next_page);
+                connection.edges.extend(
+                    slots
+                        .into_iter()
+                        .map(|item| Edge::new(item.key.into(), item)),
+                );
+                Ok::<Connection<Bytes32, ContractStorageSlot>, anyhow::Error>(connection)
+            },
+        )
+        .await
+    }
+}
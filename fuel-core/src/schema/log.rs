@@ -0,0 +1,221 @@
+use crate::{
+    database::{receipts::LogIndexCursor, Database},
+    schema::{
+        scalars::{ContractId, HexString, TransactionId, U64},
+        tx::receipt::{Receipt, ReceiptType},
+    },
+    state::IterDirection,
+};
+use async_graphql::{
+    connection::{query, Connection, Edge, EmptyFields},
+    Context, InputObject, Object,
+};
+use fuel_core_interfaces::common::{fuel_tx, fuel_types};
+use itertools::Itertools;
+
+/// A `Log` or `LogData` receipt emitted by a successful transaction.
+pub struct Log {
+    position: LogIndexCursor,
+    tx_id: fuel_types::Bytes32,
+    receipt: fuel_tx::Receipt,
+}
+
+#[Object]
+impl Log {
+    async fn transaction_id(&self) -> TransactionId {
+        self.tx_id.into()
+    }
+
+    async fn block_height(&self) -> U64 {
+        self.position.block_height.into()
+    }
+
+    /// Index of the receipt among the receipts of its transaction.
+    async fn receipt_index(&self) -> U64 {
+        (self.position.receipt_idx as u64).into()
+    }
+
+    async fn receipt(&self) -> Receipt {
+        Receipt(self.receipt.clone())
+    }
+}
+
+#[derive(InputObject)]
+struct LogFilterInput {
+    /// Contract that emitted the logs
+    contract: Option<ContractId>,
+    /// Either `LOG` or `LOG_DATA`
+    receipt_type: Option<ReceiptType>,
+    /// Value of the `ra` register of the log
+    ra: Option<U64>,
+    /// Value of the `rb` register of the log
+    rb: Option<U64>,
+    /// Lowest block height of the logs, inclusive
+    from_height: Option<U64>,
+    /// Highest block height of the logs, inclusive
+    to_height: Option<U64>,
+}
+
+impl LogFilterInput {
+    fn matches(&self, log: &Log) -> bool {
+        let (ra, rb) = match log.receipt {
+            fuel_tx::Receipt::Log { ra, rb, .. } | fuel_tx::Receipt::LogData { ra, rb, .. } => {
+                (ra, rb)
+            }
+            _ => return false,
+        };
+        let type_matches = match self.receipt_type {
+            Some(expected) => expected == ReceiptType::from(&log.receipt),
+            None => true,
+        };
+        let ra_matches = match self.ra {
+            Some(expected) => expected.0 == ra,
+            None => true,
+        };
+        let rb_matches = match self.rb {
+            Some(expected) => expected.0 == rb,
+            None => true,
+        };
+
+        type_matches && ra_matches && rb_matches
+    }
+
+    fn below_range(&self, log: &Log) -> bool {
+        match self.from_height {
+            Some(from) => u64::from(log.position.block_height) < from.0,
+            None => false,
+        }
+    }
+
+    fn above_range(&self, log: &Log) -> bool {
+        match self.to_height {
+            Some(to) => u64::from(log.position.block_height) > to.0,
+            None => false,
+        }
+    }
+}
+
+fn decode_cursor(cursor: HexString) -> async_graphql::Result<LogIndexCursor> {
+    if cursor.0.len() != 12 {
+        return Err(async_graphql::Error::new("Invalid log cursor"));
+    }
+    Ok(cursor.0.into())
+}
+
+#[derive(Default)]
+pub struct LogQuery;
+
+#[Object]
+impl LogQuery {
+    /// Logs sorted by their position on chain, emitted by successful transactions.
+    async fn logs(
+        &self,
+        ctx: &Context<'_>,
+        filter: LogFilterInput,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+    ) -> async_graphql::Result<Connection<HexString, Log, EmptyFields, EmptyFields>> {
+        let db = ctx.data_unchecked::<Database>();
+        if let Some(receipt_type) = filter.receipt_type {
+            if receipt_type != ReceiptType::Log && receipt_type != ReceiptType::LogData {
+                return Err(async_graphql::Error::new(
+                    "Only LOG and LOG_DATA receipts are indexed",
+                ));
+            }
+        }
+        let contract = filter.contract.map(fuel_types::ContractId::from);
+
+        query(
+            after,
+            before,
+            first,
+            last,
+            |after: Option<HexString>, before: Option<HexString>, first, last| async move {
+                let (records_to_fetch, direction) = if let Some(first) = first {
+                    (first, IterDirection::Forward)
+                } else if let Some(last) = last {
+                    (last, IterDirection::Reverse)
+                } else {
+                    (0, IterDirection::Forward)
+                };
+
+                let after = after.map(decode_cursor).transpose()?;
+                let before = before.map(decode_cursor).transpose()?;
+
+                let (start, end) = if direction == IterDirection::Forward {
+                    (after, before)
+                } else {
+                    (before, after)
+                };
+
+                // seek straight to the start of the requested height range, unless the cursor
+                // is already past it
+                let range_start = match direction {
+                    IterDirection::Forward => filter.from_height.map(|from| LogIndexCursor {
+                        block_height: from.0.try_into().unwrap_or(u32::MAX).into(),
+                        tx_idx: 0,
+                        receipt_idx: 0,
+                    }),
+                    IterDirection::Reverse => filter.to_height.map(|to| LogIndexCursor {
+                        block_height: to.0.try_into().unwrap_or(u32::MAX).into(),
+                        tx_idx: u32::MAX,
+                        receipt_idx: u32::MAX,
+                    }),
+                };
+                let cursor_in_range = match (start, range_start) {
+                    (Some(start), Some(range_start)) => match direction {
+                        IterDirection::Forward => start >= range_start,
+                        IterDirection::Reverse => start <= range_start,
+                    },
+                    (start, _) => start.is_some(),
+                };
+                let seek = if cursor_in_range { start } else { range_start };
+
+                let mut logs = db.logs(contract.as_ref(), seek.as_ref(), Some(direction));
+                let mut started = None;
+                if cursor_in_range {
+                    // skip initial result
+                    started = logs.next();
+                }
+
+                let logs = logs
+                    .map(|res| {
+                        res.map(|(position, tx_id, receipt)| Log {
+                            position,
+                            tx_id,
+                            receipt,
+                        })
+                    })
+                    .take_while(|res| match res {
+                        Ok(log) => {
+                            Some(log.position) != end
+                                && !filter.below_range(log)
+                                && !filter.above_range(log)
+                        }
+                        Err(_) => true,
+                    })
+                    .filter(|res| match res {
+                        Ok(log) => filter.matches(log),
+                        Err(_) => true,
+                    })
+                    .take(records_to_fetch);
+                let mut logs: Vec<Log> = logs.try_collect()?;
+                if direction == IterDirection::Reverse {
+                    logs.reverse();
+                }
+
+                let mut connection =
+                    Connection::new(started.is_some(), records_to_fetch <= logs.len());
+                connection.edges.extend(
+                    logs.into_iter()
+                        .map(|log| Edge::new(HexString::from(log.position), log)),
+                );
+
+                Ok::<Connection<HexString, Log>, async_graphql::Error>(connection)
+            },
+        )
+        .await
+    }
+}
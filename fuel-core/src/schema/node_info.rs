@@ -9,6 +9,7 @@ pub struct NodeInfo {
     min_gas_price: U64,
     min_byte_price: U64,
     max_tx: U64,
+    max_tx_per_owner: U64,
     max_depth: U64,
     node_version: String,
 }
@@ -39,6 +40,10 @@ impl NodeInfo {
         self.max_tx
     }
 
+    async fn max_tx_per_owner(&self) -> U64 {
+        self.max_tx_per_owner
+    }
+
     async fn max_depth(&self) -> U64 {
         self.max_depth
     }
@@ -71,6 +76,7 @@ impl NodeQuery {
             min_gas_price: txpool.min_gas_price.into(),
             min_byte_price: txpool.min_byte_price.into(),
             max_tx: (txpool.max_tx as u64).into(),
+            max_tx_per_owner: (txpool.max_tx_per_owner as u64).into(),
             max_depth: (txpool.max_depth as u64).into(),
             node_version: VERSION.to_owned(),
         })
@@ -1,4 +1,4 @@
-use crate::database::transaction::OwnedTransactionIndexCursor;
+use crate::database::{receipts::LogIndexCursor, transaction::OwnedTransactionIndexCursor};
 use crate::model::BlockHeight;
 use async_graphql::{
     connection::CursorType, InputValueError, InputValueResult, Scalar, ScalarType, Value,
@@ -125,6 +125,12 @@ impl From<OwnedTransactionIndexCursor> for HexString {
     }
 }
 
+impl From<LogIndexCursor> for HexString {
+    fn from(cursor: LogIndexCursor) -> Self {
+        HexString(cursor.into())
+    }
+}
+
 impl FromStr for HexString {
     type Err = String;
 
@@ -1,28 +1,45 @@
 use crate::config::Config;
-use crate::database::{transaction::OwnedTransactionIndexCursor, Database, KvStoreError};
-use crate::executor::Executor;
-use crate::model::{BlockHeight, FuelBlockDb};
-use crate::schema::scalars::{Address, Bytes32, HexString, SortedTxCursor, TransactionId};
-use crate::state::IterDirection;
+use crate::database::{columns, transaction::OwnedTransactionIndexCursor, Database, KvStoreError};
+use crate::executor::{self, Executor};
+use crate::model::{BlockHeight, Coin as CoinModel, CoinStatus, FuelBlockDb};
+use crate::schema::{
+    broadcast_stream,
+    scalars::{
+        Address, AssetId, Bytes32, ContractId, HexString, SortedTxCursor, TransactionId, UtxoId,
+        U64,
+    },
+};
+use crate::state::{ColumnId, IterDirection};
+use crate::tx_pool::TransactionStatus as TxStatusModel;
 use async_graphql::{
     connection::{query, Connection, Edge, EmptyFields},
-    Context, Object,
+    Context, InputObject, Object, Subscription,
 };
+use chrono::Utc;
+use fuel_block_importer::Service as BlockImporterService;
 use fuel_core_interfaces::common::{
+    fuel_asm::Word,
     fuel_storage::Storage,
-    fuel_tx::{Bytes32 as FuelBytes32, Receipt as FuelReceipt, Transaction as FuelTx},
+    fuel_tx::{self, Bytes32 as FuelBytes32, Receipt as FuelReceipt, Transaction as FuelTx},
     fuel_types,
-    fuel_vm::prelude::Deserializable,
+    fuel_vm::prelude::{Deserializable, MerkleStorage},
+};
+use fuel_core_interfaces::{
+    block_importer::{ImportBlockBroadcast, ImportBlockMpsc},
+    txpool::{TxPoolMpsc, TxStatus, TxStatusBroadcast},
 };
-use fuel_core_interfaces::txpool::TxPoolMpsc;
 use fuel_txpool::Service as TxPoolService;
+use futures::{future, stream, Stream, StreamExt};
 use itertools::Itertools;
 use std::borrow::Cow;
 use std::iter;
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 use tokio::sync::{oneshot, Mutex};
-use types::Transaction;
+use types::{
+    DryRunResult, DryRunStateDiff, ExecutionTrace, GasEstimate, SqueezedOutStatus, SubmittedStatus,
+    Transaction, TransactionProof, TransactionStatus,
+};
 
 pub mod input;
 pub mod output;
@@ -57,6 +74,69 @@ impl TxQuery {
         }
     }
 
+    /// Proof that the transaction is included in the `transactionsRoot` of its block.
+    /// Returns null if the transaction isn't part of a block.
+    async fn transaction_proof(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The ID of the transaction")] id: TransactionId,
+    ) -> async_graphql::Result<Option<TransactionProof>> {
+        let db = ctx.data_unchecked::<Database>();
+        let block_id = match db.get_tx_status(&id.0)? {
+            Some(TxStatusModel::Success { block_id, .. })
+            | Some(TxStatusModel::Failed { block_id, .. }) => block_id,
+            _ => return Ok(None),
+        };
+        let block = Storage::<fuel_types::Bytes32, FuelBlockDb>::get(db, &block_id)?
+            .ok_or(KvStoreError::NotFound)?
+            .into_owned();
+        let index = block
+            .transactions
+            .iter()
+            .position(|tx_id| *tx_id == id.0)
+            .ok_or(KvStoreError::NotFound)? as u64;
+        let proof_set = db.transaction_proof(&block, index)?;
+
+        Ok(Some(TransactionProof {
+            block,
+            index,
+            proof_set,
+        }))
+    }
+
+    /// The call tree of an executed transaction. Returns null unless the node stores the traces
+    /// of the transactions it executes.
+    async fn transaction_trace(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The ID of the transaction")] id: TransactionId,
+    ) -> async_graphql::Result<Option<ExecutionTrace>> {
+        let db = ctx.data_unchecked::<Database>();
+        Ok(
+            Storage::<fuel_types::Bytes32, executor::ExecutionTrace>::get(db, &id.0)?
+                .map(|trace| ExecutionTrace(trace.into_owned())),
+        )
+    }
+
+    /// The smallest gas limit the transaction can be executed with without reverting, found by
+    /// running it against the current state. The limit is at most what the inputs of the
+    /// transaction can pay for at its gas price. No changes are committed.
+    async fn estimate_gas(
+        &self,
+        ctx: &Context<'_>,
+        tx: HexString,
+    ) -> async_graphql::Result<GasEstimate> {
+        let mut tx = FuelTx::from_bytes(&tx.0)?;
+        tx.precompute_metadata();
+        let executor = Executor {
+            database: ctx.data_unchecked::<Database>().clone(),
+            config: ctx.data_unchecked::<Config>().clone(),
+        };
+        // the transaction is run many times, off the async runtime
+        let estimate = tokio::task::spawn_blocking(move || executor.estimate_gas(&tx)).await??;
+        Ok(GasEstimate(estimate))
+    }
+
     async fn transactions(
         &self,
         ctx: &Context<'_>,
@@ -178,78 +258,195 @@ impl TxQuery {
     ) -> async_graphql::Result<Connection<HexString, Transaction, EmptyFields, EmptyFields>> {
         let db = ctx.data_unchecked::<Database>();
         let owner = fuel_types::Address::from(owner);
-
-        query(
+        indexed_transactions(
+            db,
+            columns::TRANSACTIONS_BY_OWNER_BLOCK_IDX,
+            *owner,
+            first,
             after,
+            last,
             before,
+        )
+        .await
+    }
+
+    /// Transactions that used the contract as an input or created it.
+    async fn transactions_by_contract(
+        &self,
+        ctx: &Context<'_>,
+        contract: ContractId,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+    ) -> async_graphql::Result<Connection<HexString, Transaction, EmptyFields, EmptyFields>> {
+        let db = ctx.data_unchecked::<Database>();
+        let contract = fuel_types::ContractId::from(contract);
+        indexed_transactions(
+            db,
+            columns::TRANSACTIONS_BY_CONTRACT,
+            *contract,
             first,
+            after,
             last,
-            |after: Option<HexString>, before: Option<HexString>, first, last| async move {
-                let (records_to_fetch, direction) = if let Some(first) = first {
-                    (first, IterDirection::Forward)
-                } else if let Some(last) = last {
-                    (last, IterDirection::Reverse)
-                } else {
-                    (0, IterDirection::Forward)
-                };
-
-                let after = after.map(OwnedTransactionIndexCursor::from);
-                let before = before.map(OwnedTransactionIndexCursor::from);
+            before,
+        )
+        .await
+    }
+}
 
-                let start;
-                let end;
+/// Paginates the transactions of an index column keyed by an owner or a contract, in the order
+/// they were included on chain.
+async fn indexed_transactions(
+    db: &Database,
+    column: ColumnId,
+    key: [u8; 32],
+    first: Option<i32>,
+    after: Option<String>,
+    last: Option<i32>,
+    before: Option<String>,
+) -> async_graphql::Result<Connection<HexString, Transaction, EmptyFields, EmptyFields>> {
+    query(
+        after,
+        before,
+        first,
+        last,
+        |after: Option<HexString>, before: Option<HexString>, first, last| async move {
+            let (records_to_fetch, direction) = if let Some(first) = first {
+                (first, IterDirection::Forward)
+            } else if let Some(last) = last {
+                (last, IterDirection::Reverse)
+            } else {
+                (0, IterDirection::Forward)
+            };
+
+            let after = after.map(OwnedTransactionIndexCursor::from);
+            let before = before.map(OwnedTransactionIndexCursor::from);
+
+            let start;
+            let end;
+
+            if direction == IterDirection::Forward {
+                start = after;
+                end = before;
+            } else {
+                start = before;
+                end = after;
+            }
+
+            let mut txs = db.indexed_transactions(column, &key, start.as_ref(), Some(direction));
+            let mut started = None;
+            if start.is_some() {
+                // skip initial result
+                started = txs.next();
+            }
+
+            // take desired amount of results
+            let txs = txs
+                .take_while(|r| {
+                    // take until we've reached the end
+                    if let (Ok(t), Some(end)) = (r, end.as_ref()) {
+                        if &t.0 == end {
+                            return false;
+                        }
+                    }
+                    true
+                })
+                .take(records_to_fetch)
+                .map(|res| {
+                    res.and_then(|(cursor, tx_id)| {
+                        let tx = Storage::<fuel_types::Bytes32, FuelTx>::get(db, &tx_id)?
+                            .ok_or(KvStoreError::NotFound)?
+                            .into_owned();
+                        Ok((cursor, tx))
+                    })
+                });
+            let mut txs: Vec<(OwnedTransactionIndexCursor, FuelTx)> = txs.try_collect()?;
+            if direction == IterDirection::Reverse {
+                txs.reverse();
+            }
+
+            let mut connection = Connection::new(started.is_some(), records_to_fetch <= txs.len());
+            connection.edges.extend(
+                txs.into_iter()
+                    .map(|item| Edge::new(HexString::from(item.0), Transaction(item.1))),
+            );
+
+            Ok::<Connection<HexString, Transaction>, KvStoreError>(connection)
+        },
+    )
+    .await
+}
 
-                if direction == IterDirection::Forward {
-                    start = after;
-                    end = before;
-                } else {
-                    start = before;
-                    end = after;
-                }
+#[derive(InputObject)]
+struct CoinOverrideInput {
+    utxo_id: UtxoId,
+    owner: Address,
+    amount: U64,
+    asset_id: AssetId,
+    maturity: Option<U64>,
+}
 
-                let mut txs = db.owned_transactions(&owner, start.as_ref(), Some(direction));
-                let mut started = None;
-                if start.is_some() {
-                    // skip initial result
-                    started = txs.next();
-                }
+#[derive(InputObject)]
+struct ContractBalanceOverrideInput {
+    contract: ContractId,
+    asset_id: AssetId,
+    amount: U64,
+}
 
-                // take desired amount of results
-                let txs = txs
-                    .take_while(|r| {
-                        // take until we've reached the end
-                        if let (Ok(t), Some(end)) = (r, end.as_ref()) {
-                            if &t.0 == end {
-                                return false;
-                            }
-                        }
-                        true
-                    })
-                    .take(records_to_fetch)
-                    .map(|res| {
-                        res.and_then(|(cursor, tx_id)| {
-                            let tx = Storage::<fuel_types::Bytes32, FuelTx>::get(db, &tx_id)?
-                                .ok_or(KvStoreError::NotFound)?
-                                .into_owned();
-                            Ok((cursor, tx))
-                        })
-                    });
-                let mut txs: Vec<(OwnedTransactionIndexCursor, FuelTx)> = txs.try_collect()?;
-                if direction == IterDirection::Reverse {
-                    txs.reverse();
-                }
+#[derive(InputObject)]
+struct StorageSlotOverrideInput {
+    contract: ContractId,
+    key: Bytes32,
+    value: Bytes32,
+}
 
-                let mut connection =
-                    Connection::new(started.is_some(), records_to_fetch <= txs.len());
-                connection.edges.extend(
-                    txs.into_iter()
-                        .map(|item| Edge::new(HexString::from(item.0), Transaction(item.1))),
-                );
+/// Temporary changes to the state a dry run is executed against.
+#[derive(InputObject)]
+struct DryRunOverridesInput {
+    /// Unspent coins to add, replacing any coin with the same utxo id.
+    coins: Option<Vec<CoinOverrideInput>>,
+    /// Balances of contracts to set.
+    contract_balances: Option<Vec<ContractBalanceOverrideInput>>,
+    /// Storage slots of contracts to set.
+    storage_slots: Option<Vec<StorageSlotOverrideInput>>,
+}
 
-                Ok::<Connection<HexString, Transaction>, KvStoreError>(connection)
-            },
-        )
-        .await
+impl DryRunOverridesInput {
+    fn apply(self, db: &mut Database) -> Result<(), KvStoreError> {
+        let block_created = db.get_block_height()?.unwrap_or_default();
+        for coin in self.coins.unwrap_or_default() {
+            let coin_model = CoinModel {
+                owner: coin.owner.into(),
+                amount: coin.amount.0,
+                asset_id: coin.asset_id.into(),
+                maturity: coin
+                    .maturity
+                    .map(|maturity| maturity.0)
+                    .unwrap_or_default()
+                    .into(),
+                status: CoinStatus::Unspent,
+                block_created,
+            };
+            Storage::<fuel_tx::UtxoId, CoinModel>::insert(db, &coin.utxo_id.0, &coin_model)?;
+        }
+        for balance in self.contract_balances.unwrap_or_default() {
+            MerkleStorage::<fuel_types::ContractId, fuel_types::AssetId, Word>::insert(
+                db,
+                &balance.contract.into(),
+                &balance.asset_id.into(),
+                &balance.amount.0,
+            )?;
+        }
+        for slot in self.storage_slots.unwrap_or_default() {
+            MerkleStorage::<fuel_types::ContractId, FuelBytes32, FuelBytes32>::insert(
+                db,
+                &slot.contract.into(),
+                &slot.key.into(),
+                &slot.value.into(),
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -285,17 +482,157 @@ impl TxMutation {
             database: transaction.deref().clone(),
             config: cfg.clone(),
         };
-        executor.submit_txs(vec![Arc::new(tx)]).await?;
+        let block = executor.submit_txs(vec![Arc::new(tx)]).await?;
+        if block.transactions.is_empty() {
+            return Err(async_graphql::Error::new(
+                "The transaction exceeds the gas or size limit of a block",
+            ));
+        }
         // get receipts from db transaction
         let receipts = Storage::<FuelBytes32, Vec<FuelReceipt>>::get(transaction.deref(), &id)?
             .unwrap_or_default();
         Ok(receipts.iter().map(Into::into).collect())
     }
 
+    /// Execute a dry-run of the transaction like `dryRun`, also returning the coins it spends and
+    /// creates and the changes it makes to contract balances and storage.
+    async fn dry_run_state_diff(
+        &self,
+        ctx: &Context<'_>,
+        tx: HexString,
+        // If set to false, disable input utxo validation, overriding the configuration of the node.
+        utxo_validation: Option<bool>,
+    ) -> async_graphql::Result<DryRunStateDiff> {
+        let db = ctx.data_unchecked::<Database>();
+        let transaction = db.transaction();
+        let mut cfg = ctx.data_unchecked::<Config>().clone();
+        if let Some(utxo_validation) = utxo_validation {
+            cfg.utxo_validation = utxo_validation;
+        }
+        let mut tx = FuelTx::from_bytes(&tx.0)?;
+        tx.precompute_metadata();
+        let id = tx.id();
+
+        let executor = Executor {
+            database: transaction.deref().clone(),
+            config: cfg,
+        };
+        let block = executor.submit_txs(vec![Arc::new(tx)]).await?;
+        if block.transactions.is_empty() {
+            return Err(async_graphql::Error::new(
+                "The transaction exceeds the gas or size limit of a block",
+            ));
+        }
+        let receipts = Storage::<FuelBytes32, Vec<FuelReceipt>>::get(transaction.deref(), &id)?
+            .unwrap_or_default()
+            .into_owned();
+        let mut diff = transaction.state_diff(db)?;
+        // the fees credited to the producer of the dry run block aren't a change of the transaction
+        let coinbase = executor::coinbase_utxo_id(&block.id());
+        diff.created_coins
+            .retain(|(utxo_id, _)| *utxo_id != coinbase);
+        Ok(DryRunStateDiff { receipts, diff })
+    }
+
+    /// Execute a dry-run of the transaction like `dryRun`, returning the tree of the calls it
+    /// made. If `instructions` is set, every executed instruction is traced as well, along with
+    /// the gas used by each call.
+    async fn dry_run_trace(
+        &self,
+        ctx: &Context<'_>,
+        tx: HexString,
+        // If set to false, disable input utxo validation, overriding the configuration of the node.
+        utxo_validation: Option<bool>,
+        instructions: Option<bool>,
+    ) -> async_graphql::Result<ExecutionTrace> {
+        let db = ctx.data_unchecked::<Database>();
+        let transaction = db.transaction();
+        let mut cfg = ctx.data_unchecked::<Config>().clone();
+        if let Some(utxo_validation) = utxo_validation {
+            cfg.utxo_validation = utxo_validation;
+        }
+        let mut tx = FuelTx::from_bytes(&tx.0)?;
+        tx.precompute_metadata();
+        let id = tx.id();
+
+        let executor = Executor {
+            database: transaction.deref().clone(),
+            config: cfg.clone(),
+        };
+        let block = executor.submit_txs(vec![Arc::new(tx.clone())]).await?;
+        if block.transactions.is_empty() {
+            return Err(async_graphql::Error::new(
+                "The transaction exceeds the gas or size limit of a block",
+            ));
+        }
+        let receipts = Storage::<FuelBytes32, Vec<FuelReceipt>>::get(transaction.deref(), &id)?
+            .unwrap_or_default();
+
+        // instructions are traced over the state the transaction was executed on
+        let executor = Executor {
+            database: db.clone(),
+            config: cfg,
+        };
+        let trace = executor.trace(&tx, &receipts, instructions.unwrap_or(false))?;
+        Ok(ExecutionTrace(trace))
+    }
+
+    /// Execute a dry-run of an ordered bundle of transactions in a single block using a fork of
+    /// current state, so later transactions see the effects of earlier ones. The overrides are
+    /// applied to the fork first. No changes are committed.
+    async fn dry_run_bundle(
+        &self,
+        ctx: &Context<'_>,
+        txs: Vec<HexString>,
+        // If set to false, disable input utxo validation, overriding the configuration of the node.
+        utxo_validation: Option<bool>,
+        overrides: Option<DryRunOverridesInput>,
+    ) -> async_graphql::Result<Vec<DryRunResult>> {
+        let mut transaction = ctx.data_unchecked::<Database>().transaction();
+        let mut cfg = ctx.data_unchecked::<Config>().clone();
+        if let Some(utxo_validation) = utxo_validation {
+            cfg.utxo_validation = utxo_validation;
+        }
+        if let Some(overrides) = overrides {
+            overrides.apply(transaction.deref_mut())?;
+        }
+        let txs = txs
+            .into_iter()
+            .map(|tx| {
+                let mut tx = FuelTx::from_bytes(&tx.0)?;
+                tx.precompute_metadata();
+                Ok(Arc::new(tx))
+            })
+            .collect::<Result<Vec<_>, std::io::Error>>()?;
+        let ids = txs.iter().map(|tx| tx.id()).collect::<Vec<_>>();
+
+        let executor = Executor {
+            database: transaction.deref().clone(),
+            config: cfg,
+        };
+        let block = executor.submit_txs(txs).await?;
+        if block.transactions.len() != ids.len() {
+            return Err(async_graphql::Error::new(
+                "The transactions of the bundle exceed the gas or size limit of a block",
+            ));
+        }
+
+        ids.into_iter()
+            .map(|id| {
+                let receipts =
+                    Storage::<FuelBytes32, Vec<FuelReceipt>>::get(transaction.deref(), &id)?
+                        .unwrap_or_default()
+                        .into_owned();
+                Ok(DryRunResult { id, receipts })
+            })
+            .collect()
+    }
+
     /// Submits transaction to the txpool
     async fn submit(&self, ctx: &Context<'_>, tx: HexString) -> async_graphql::Result<Transaction> {
         let db = ctx.data_unchecked::<Database>();
         let txpool = ctx.data_unchecked::<Arc<TxPoolService>>();
+        let block_importer = ctx.data_unchecked::<Arc<BlockImporterService>>();
         let cfg = ctx.data_unchecked::<Config>().clone();
         let mut tx = FuelTx::from_bytes(&tx.0)?;
         tx.precompute_metadata();
@@ -308,15 +645,22 @@ impl TxMutation {
             let ret = txpool.sender().insert(vec![Arc::new(tx.clone())]).await?;
             ret.get(0).unwrap().as_ref()?;
 
-            // get includable transactions
-            let txs = txpool.sender().includable().await?;
-
+            // get the transactions that fit in the block, the rest stay in the pool
             txpool
                 .sender()
-                .remove(txs.iter().map(|tx| tx.id()).collect())
-                .await?;
-            txs
+                .includable_within(
+                    cfg.chain_conf.max_gas_per_block,
+                    cfg.chain_conf.max_block_size.try_into()?,
+                )
+                .await?
         } else {
+            if tx.gas_limit() > cfg.chain_conf.max_gas_per_block
+                || tx.metered_bytes_size() as u64 > cfg.chain_conf.max_block_size
+            {
+                return Err(async_graphql::Error::new(
+                    "The transaction exceeds the gas or size limit of a block",
+                ));
+            }
             vec![Arc::new(tx.clone())]
         };
 
@@ -327,10 +671,119 @@ impl TxMutation {
             database: db.clone(),
             config: cfg.clone(),
         };
-        executor.submit_txs(includable).await?;
+        let selected: Vec<_> = includable.iter().map(|tx| tx.id()).collect();
+        let block = match executor.submit_txs(includable).await {
+            Ok(block) => block,
+            Err(e) => {
+                // a transaction the block can't be produced with would otherwise be selected
+                // again by every later submission, evict it along with its dependents. If the
+                // error isn't tied to one of them, evict all of the selected ones.
+                if cfg.utxo_validation {
+                    let evicted = match e.transaction_id() {
+                        Some(id) if selected.contains(&id) => vec![id],
+                        _ => selected,
+                    };
+                    txpool.sender().remove(evicted).await?;
+                }
+                return Err(e.into());
+            }
+        };
+
+        // transactions are removed from the pool only once their execution status is
+        // committed, so status subscribers never observe a gap between the two.
+        if cfg.utxo_validation {
+            txpool
+                .sender()
+                .remove_committed(block.transactions.iter().map(|tx| tx.id()).collect())
+                .await?;
+        }
+
+        // announce the new block to subscribers
+        let _ = block_importer
+            .sender()
+            .send(ImportBlockMpsc::ImportFuelBlock {
+                block: Arc::new(block),
+            })
+            .await;
 
         // probably need to fetch executed tx that is now in db.
         let tx = Transaction(tx);
         Ok(tx)
     }
 }
+
+enum StatusEvent {
+    Pool(TxStatusBroadcast),
+    Block(ImportBlockBroadcast),
+}
+
+#[derive(Default)]
+pub struct TxStatusSubscription;
+
+#[Subscription]
+impl TxStatusSubscription {
+    /// Status changes of the transaction, starting with its current status if it is known.
+    /// The stream ends once the transaction is executed in a block or removed from the pool.
+    async fn status_change(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The ID of the transaction")] id: TransactionId,
+    ) -> async_graphql::Result<impl Stream<Item = async_graphql::Result<TransactionStatus>>> {
+        let db = ctx.data_unchecked::<Database>().clone();
+        let txpool = ctx.data_unchecked::<Arc<TxPoolService>>();
+        let block_importer = ctx.data_unchecked::<Arc<BlockImporterService>>();
+        let id = id.0;
+
+        // subscribe before looking up the current status to not miss updates in between
+        let events = stream::select(
+            broadcast_stream(txpool.subscribe_ch()).map(StatusEvent::Pool),
+            broadcast_stream(block_importer.subscribe()).map(StatusEvent::Block),
+        );
+        let current = types::get_tx_status(id, &db, txpool).await?;
+
+        let updates = events.filter_map(move |event| {
+            let status = match event {
+                StatusEvent::Pool(TxStatusBroadcast { tx, status }) if tx.id() == id => {
+                    match status {
+                        TxStatus::Submitted => Some(Ok(TransactionStatus::Submitted(
+                            SubmittedStatus(Utc::now()),
+                        ))),
+                        // the execution result is reported with the block
+                        TxStatus::Executed => None,
+                        TxStatus::SqueezedOut { reason } => match db.get_tx_status(&id) {
+                            // executed transactions are removed from the pool as well
+                            Ok(Some(TxStatusModel::Success { .. }))
+                            | Ok(Some(TxStatusModel::Failed { .. })) => None,
+                            Ok(_) => Some(Ok(TransactionStatus::SqueezedOut(SqueezedOutStatus {
+                                reason: reason.to_string(),
+                            }))),
+                            Err(e) => Some(Err(e.into())),
+                        },
+                    }
+                }
+                StatusEvent::Block(event)
+                    if event.block().transactions.iter().any(|tx| tx.id() == id) =>
+                {
+                    db.get_tx_status(&id)
+                        .map_err(Into::into)
+                        .transpose()
+                        .map(|status| status.map(Into::into))
+                }
+                _ => None,
+            };
+            future::ready(status)
+        });
+
+        let statuses = stream::iter(current.map(Ok)).chain(updates).boxed();
+        Ok(stream::unfold(Some(statuses), |statuses| async move {
+            let mut statuses = statuses?;
+            let status = statuses.next().await?;
+            let finished = match &status {
+                Ok(status) => status.is_final(),
+                Err(_) => true,
+            };
+            let statuses = if finished { None } else { Some(statuses) };
+            Some((status, statuses))
+        }))
+    }
+}
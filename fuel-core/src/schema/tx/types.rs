@@ -1,11 +1,16 @@
 use super::{input::Input, output::Output, receipt::Receipt};
 use crate::{
-    database::Database,
+    database::{
+        state_diff::{self, StateDiff},
+        Database,
+    },
+    executor,
     model::FuelBlockDb,
     schema::{
         block::Block,
+        coin::Coin,
         contract::Contract,
-        scalars::{AssetId, Bytes32, HexString, Salt, TransactionId, U64},
+        scalars::{AssetId, BlockId, Bytes32, ContractId, HexString, Salt, TransactionId, U64},
     },
     tx_pool::TransactionStatus as TxStatus,
 };
@@ -74,9 +79,17 @@ pub enum TransactionStatus {
     Submitted(SubmittedStatus),
     Success(SuccessStatus),
     Failed(FailureStatus),
+    SqueezedOut(SqueezedOutStatus),
 }
 
-pub struct SubmittedStatus(DateTime<Utc>);
+impl TransactionStatus {
+    /// Whether the transaction can't change its status anymore.
+    pub fn is_final(&self) -> bool {
+        !matches!(self, TransactionStatus::Submitted(_))
+    }
+}
+
+pub struct SubmittedStatus(pub(crate) DateTime<Utc>);
 
 #[Object]
 impl SubmittedStatus {
@@ -142,6 +155,273 @@ impl FailureStatus {
     }
 }
 
+/// Proof that a transaction is a leaf of the binary Merkle tree whose root is the
+/// `transactionsRoot` of the block.
+pub struct TransactionProof {
+    pub(crate) block: FuelBlockDb,
+    pub(crate) index: u64,
+    pub(crate) proof_set: Vec<fuel_types::Bytes32>,
+}
+
+#[Object]
+impl TransactionProof {
+    async fn block_id(&self) -> BlockId {
+        self.block.id().into()
+    }
+
+    async fn transactions_root(&self) -> Bytes32 {
+        self.block.headers.transactions_root.into()
+    }
+
+    /// Index of the transaction's leaf.
+    async fn index(&self) -> U64 {
+        self.index.into()
+    }
+
+    /// Number of leaves in the tree.
+    async fn transactions_count(&self) -> U64 {
+        (self.block.transactions.len() as u64).into()
+    }
+
+    /// Hashes of the sibling nodes from the leaf up to the root.
+    async fn proof_set(&self) -> Vec<Bytes32> {
+        self.proof_set.iter().copied().map(Into::into).collect()
+    }
+}
+
+/// The outcome of a transaction of a dry run bundle.
+pub struct DryRunResult {
+    pub(crate) id: fuel_types::Bytes32,
+    pub(crate) receipts: Vec<fuel_tx::Receipt>,
+}
+
+#[Object]
+impl DryRunResult {
+    async fn id(&self) -> TransactionId {
+        self.id.into()
+    }
+
+    async fn receipts(&self) -> Vec<Receipt> {
+        self.receipts.iter().map(Into::into).collect()
+    }
+}
+
+/// The outcome of a dry run along with the changes it made to the state.
+pub struct DryRunStateDiff {
+    pub(crate) receipts: Vec<fuel_tx::Receipt>,
+    pub(crate) diff: StateDiff,
+}
+
+#[Object]
+impl DryRunStateDiff {
+    async fn receipts(&self) -> Vec<Receipt> {
+        self.receipts.iter().map(Into::into).collect()
+    }
+
+    /// Coins spent by the transaction.
+    async fn spent_coins(&self) -> Vec<Coin> {
+        self.diff
+            .spent_coins
+            .iter()
+            .map(|(utxo_id, coin)| Coin(*utxo_id, coin.clone()))
+            .collect()
+    }
+
+    /// Coins created by the transaction, including the coin the block fees are credited to.
+    async fn created_coins(&self) -> Vec<Coin> {
+        self.diff
+            .created_coins
+            .iter()
+            .map(|(utxo_id, coin)| Coin(*utxo_id, coin.clone()))
+            .collect()
+    }
+
+    async fn contract_balance_changes(&self) -> Vec<ContractBalanceChange> {
+        self.diff
+            .contract_balances
+            .iter()
+            .cloned()
+            .map(ContractBalanceChange)
+            .collect()
+    }
+
+    async fn storage_slot_changes(&self) -> Vec<StorageSlotChange> {
+        self.diff
+            .storage_slots
+            .iter()
+            .cloned()
+            .map(StorageSlotChange)
+            .collect()
+    }
+}
+
+pub struct ContractBalanceChange(state_diff::ContractBalanceChange);
+
+#[Object]
+impl ContractBalanceChange {
+    async fn contract(&self) -> ContractId {
+        self.0.contract.into()
+    }
+
+    async fn asset_id(&self) -> AssetId {
+        self.0.asset_id.into()
+    }
+
+    async fn before(&self) -> U64 {
+        self.0.before.into()
+    }
+
+    async fn after(&self) -> U64 {
+        self.0.after.into()
+    }
+}
+
+pub struct StorageSlotChange(state_diff::StorageSlotChange);
+
+#[Object]
+impl StorageSlotChange {
+    async fn contract(&self) -> ContractId {
+        self.0.contract.into()
+    }
+
+    async fn key(&self) -> Bytes32 {
+        self.0.key.into()
+    }
+
+    /// The value of the slot before, null if it was unset.
+    async fn before(&self) -> Option<Bytes32> {
+        self.0.before.map(Into::into)
+    }
+
+    /// The value of the slot after, null if it was removed.
+    async fn after(&self) -> Option<Bytes32> {
+        self.0.after.map(Into::into)
+    }
+}
+
+pub struct ExecutionTrace(pub(crate) executor::ExecutionTrace);
+
+#[Object]
+impl ExecutionTrace {
+    /// The gas used by the script of the transaction.
+    async fn gas_used(&self) -> U64 {
+        self.0.gas_used.into()
+    }
+
+    /// The calls made by the transaction, depth first in the order they were made.
+    async fn calls(&self) -> Vec<TraceCall> {
+        self.0.calls.iter().cloned().map(TraceCall).collect()
+    }
+
+    /// The executed instructions, empty unless instructions were traced.
+    async fn steps(&self) -> Vec<TraceStep> {
+        self.0.steps.iter().cloned().map(TraceStep).collect()
+    }
+}
+
+pub struct TraceCall(executor::TraceCall);
+
+#[Object]
+impl TraceCall {
+    /// The number of calls enclosing this one, 0 for calls made by the script.
+    async fn depth(&self) -> u32 {
+        self.0.depth
+    }
+
+    async fn call(&self) -> Receipt {
+        (&self.0.call).into()
+    }
+
+    /// The receipt ending the call, null if the transaction reverted in a nested call.
+    async fn outcome(&self) -> Option<Receipt> {
+        self.0.outcome.as_ref().map(Into::into)
+    }
+
+    /// The gas used by the call, only known if the node was built with the `debug` feature.
+    async fn gas_used(&self) -> Option<U64> {
+        self.0.gas_used.map(Into::into)
+    }
+}
+
+pub struct TraceStep(executor::TraceStep);
+
+#[Object]
+impl TraceStep {
+    /// The contract the instruction belongs to, zeroed for the script.
+    async fn contract(&self) -> ContractId {
+        self.0.contract.into()
+    }
+
+    /// The offset of the instruction from the start of the script or contract code.
+    async fn pc(&self) -> U64 {
+        self.0.pc.into()
+    }
+
+    async fn instruction(&self) -> String {
+        format!("{:?}", self.0.instruction)
+    }
+
+    /// The registers the instruction changed, with their new values.
+    async fn registers(&self) -> Vec<TraceRegister> {
+        self.0
+            .registers
+            .iter()
+            .map(|(index, value)| TraceRegister {
+                index: *index,
+                value: *value,
+            })
+            .collect()
+    }
+}
+
+pub struct TraceRegister {
+    index: u8,
+    value: fuel_types::Word,
+}
+
+#[Object]
+impl TraceRegister {
+    async fn index(&self) -> u8 {
+        self.index
+    }
+
+    async fn value(&self) -> U64 {
+        self.value.into()
+    }
+}
+
+pub struct GasEstimate(pub(crate) executor::GasEstimate);
+
+#[Object]
+impl GasEstimate {
+    /// The smallest gas limit the transaction doesn't revert with.
+    async fn gas_limit(&self) -> U64 {
+        self.0.gas_limit.into()
+    }
+
+    /// The gas used by the transaction with that limit.
+    async fn gas_used(&self) -> U64 {
+        self.0.gas_used.into()
+    }
+
+    /// The fee paid by the transaction with that limit.
+    async fn fee(&self) -> U64 {
+        self.0.fee.into()
+    }
+}
+
+/// The transaction was removed from the pool without being included into a block.
+pub struct SqueezedOutStatus {
+    pub(crate) reason: String,
+}
+
+#[Object]
+impl SqueezedOutStatus {
+    async fn reason(&self) -> String {
+        self.reason.clone()
+    }
+}
+
 impl From<TxStatus> for TransactionStatus {
     fn from(s: TxStatus) -> Self {
         match s {
@@ -229,21 +509,7 @@ impl Transaction {
     async fn status(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<TransactionStatus>> {
         let db = ctx.data_unchecked::<Database>();
         let txpool = ctx.data_unchecked::<Arc<TxPoolService>>();
-        let id = self.0.id();
-
-        let (response, receiver) = oneshot::channel();
-        let _ = txpool
-            .sender()
-            .send(TxPoolMpsc::FindOne { id, response })
-            .await;
-
-        if let Ok(Some(transaction_in_pool)) = receiver.await {
-            let time = transaction_in_pool.submited_time();
-            Ok(Some(TransactionStatus::Submitted(SubmittedStatus(time))))
-        } else {
-            let status = db.get_tx_status(&self.0.id())?;
-            Ok(status.map(Into::into))
-        }
+        get_tx_status(self.0.id(), db, txpool).await
     }
 
     async fn receipts(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Vec<Receipt>>> {
@@ -330,3 +596,24 @@ impl Transaction {
         HexString(self.0.clone().to_bytes())
     }
 }
+
+/// The status of a transaction inside the pool, or the status recorded once it was executed.
+pub(crate) async fn get_tx_status(
+    id: fuel_types::Bytes32,
+    db: &Database,
+    txpool: &TxPoolService,
+) -> async_graphql::Result<Option<TransactionStatus>> {
+    let (response, receiver) = oneshot::channel();
+    let _ = txpool
+        .sender()
+        .send(TxPoolMpsc::FindOne { id, response })
+        .await;
+
+    if let Ok(Some(transaction_in_pool)) = receiver.await {
+        let time = transaction_in_pool.submited_time();
+        Ok(Some(TransactionStatus::Submitted(SubmittedStatus(time))))
+    } else {
+        let status = db.get_tx_status(&id)?;
+        Ok(status.map(Into::into))
+    }
+}
@@ -0,0 +1,227 @@
+use crate::schema::{
+    scalars::{TransactionId, U64},
+    tx::types::Transaction,
+};
+use anyhow::anyhow;
+use async_graphql::{
+    connection::{query, Connection, Edge, EmptyFields},
+    Context, Object,
+};
+use chrono::{DateTime, Utc};
+use fuel_core_interfaces::{
+    common::{fuel_tx, fuel_types},
+    model::TxInfo,
+};
+use fuel_txpool::Service as TxPoolService;
+use std::sync::Arc;
+
+pub struct PendingTransaction(TxInfo);
+
+#[Object]
+impl PendingTransaction {
+    async fn id(&self) -> TransactionId {
+        TransactionId(self.0.id())
+    }
+
+    async fn transaction(&self) -> Transaction {
+        Transaction(self.0.tx().as_ref().clone())
+    }
+
+    async fn gas_price(&self) -> U64 {
+        self.0.gas_price().into()
+    }
+
+    async fn byte_price(&self) -> U64 {
+        self.0.byte_price().into()
+    }
+
+    async fn submitted_time(&self) -> DateTime<Utc> {
+        self.0.submited_time()
+    }
+
+    /// Pending transactions that create coins spent by this transaction.
+    async fn dependencies(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<TransactionId>> {
+        let txpool = ctx.data_unchecked::<Arc<TxPoolService>>();
+
+        let mut parents: Vec<fuel_types::Bytes32> = self
+            .0
+            .inputs()
+            .iter()
+            .filter_map(|input| match input {
+                fuel_tx::Input::CoinSigned { utxo_id, .. }
+                | fuel_tx::Input::CoinPredicate { utxo_id, .. } => Some(*utxo_id.tx_id()),
+                fuel_tx::Input::Contract { .. } => None,
+            })
+            .collect();
+        parents.sort();
+        parents.dedup();
+
+        let found = txpool.sender().find(parents).await?;
+        Ok(found
+            .into_iter()
+            .flatten()
+            .map(|info| TransactionId(info.id()))
+            .collect())
+    }
+
+    /// Pending transactions that depend on outputs of this transaction, directly or
+    /// through other pending transactions.
+    async fn dependents(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<TransactionId>> {
+        let txpool = ctx.data_unchecked::<Arc<TxPoolService>>();
+        let id = self.0.id();
+
+        let dependent = txpool.sender().find_dependent(vec![id]).await?;
+        Ok(dependent
+            .into_iter()
+            .map(|tx| tx.id())
+            .filter(|tx_id| *tx_id != id)
+            .map(TransactionId)
+            .collect())
+    }
+}
+
+pub struct TxPoolStats {
+    count: U64,
+    total_bytes: U64,
+    min_gas_price: Option<U64>,
+    max_gas_price: Option<U64>,
+}
+
+#[Object]
+impl TxPoolStats {
+    /// Number of transactions inside the pool.
+    async fn count(&self) -> U64 {
+        self.count
+    }
+
+    /// Sum of metered bytes of all transactions inside the pool.
+    async fn total_bytes(&self) -> U64 {
+        self.total_bytes
+    }
+
+    async fn min_gas_price(&self) -> Option<U64> {
+        self.min_gas_price
+    }
+
+    async fn max_gas_price(&self) -> Option<U64> {
+        self.max_gas_price
+    }
+}
+
+pub struct TxPool;
+
+#[Object]
+impl TxPool {
+    async fn stats(&self, ctx: &Context<'_>) -> async_graphql::Result<TxPoolStats> {
+        let txpool = ctx.data_unchecked::<Arc<TxPoolService>>();
+        let txs = txpool.sender().pending().await?;
+
+        let total_bytes: usize = txs.iter().map(|tx| tx.metered_bytes_size()).sum();
+        // pending transactions are sorted by gas price, highest first
+        Ok(TxPoolStats {
+            count: (txs.len() as u64).into(),
+            total_bytes: (total_bytes as u64).into(),
+            min_gas_price: txs.last().map(|tx| tx.gas_price().into()),
+            max_gas_price: txs.first().map(|tx| tx.gas_price().into()),
+        })
+    }
+
+    async fn transaction(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The ID of the transaction")] id: TransactionId,
+    ) -> async_graphql::Result<Option<PendingTransaction>> {
+        let txpool = ctx.data_unchecked::<Arc<TxPoolService>>();
+        let tx = txpool.sender().find_one(id.0).await?;
+        Ok(tx.map(PendingTransaction))
+    }
+
+    /// Pending transactions sorted by gas price, highest first. Fails if a cursor refers to a
+    /// transaction that isn't in the pool anymore.
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<TransactionId, PendingTransaction, EmptyFields, EmptyFields>,
+    > {
+        let txpool = ctx.data_unchecked::<Arc<TxPoolService>>();
+
+        query(
+            after,
+            before,
+            first,
+            last,
+            |after: Option<TransactionId>, before: Option<TransactionId>, first, last| async move {
+                let (records_to_fetch, forward) = if let Some(first) = first {
+                    (first, true)
+                } else if let Some(last) = last {
+                    (last, false)
+                } else {
+                    (0, true)
+                };
+
+                let (start, end) = if forward {
+                    (after.map(|id| id.0), before.map(|id| id.0))
+                } else {
+                    (before.map(|id| id.0), after.map(|id| id.0))
+                };
+
+                let mut txs = txpool.sender().pending().await?;
+                if !forward {
+                    txs.reverse();
+                }
+
+                // the pool changes between requests, a cursor to a transaction that left it
+                // has no position to resume from
+                for cursor in start.iter().chain(end.iter()) {
+                    if !txs.iter().any(|tx| tx.id() == *cursor) {
+                        return Err(anyhow!(
+                            "Transaction {:#x} of the cursor is not in the pool anymore",
+                            cursor
+                        ));
+                    }
+                }
+
+                let mut txs = txs.into_iter();
+                let mut started = None;
+                if let Some(start) = start {
+                    // skip until the cursor, including it
+                    started = txs.by_ref().find(|tx| tx.id() == start);
+                }
+
+                let mut txs: Vec<TxInfo> = txs
+                    .take_while(|tx| Some(tx.id()) != end)
+                    .take(records_to_fetch)
+                    .collect();
+                if !forward {
+                    txs.reverse();
+                }
+
+                let mut connection =
+                    Connection::new(started.is_some(), records_to_fetch <= txs.len());
+                connection.edges.extend(
+                    txs.into_iter()
+                        .map(|tx| Edge::new(TransactionId(tx.id()), PendingTransaction(tx))),
+                );
+
+                Ok::<Connection<TransactionId, PendingTransaction>, anyhow::Error>(connection)
+            },
+        )
+        .await
+    }
+}
+
+#[derive(Default)]
+pub struct TxPoolQuery;
+
+#[Object]
+impl TxPoolQuery {
+    /// Transactions that are waiting inside the pool to be included into a block.
+    async fn txpool(&self) -> TxPool {
+        TxPool
+    }
+}
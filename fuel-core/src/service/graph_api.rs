@@ -5,23 +5,34 @@ use crate::schema::{build_schema, dap, CoreSchema};
 use crate::service::metrics::metrics;
 use anyhow::Result;
 use async_graphql::{
-    extensions::Tracing, http::playground_source, http::GraphQLPlaygroundConfig, Request, Response,
+    extensions::Tracing,
+    http::{
+        playground_source, GraphQLPlaygroundConfig, WebSocket, WebSocketProtocols, WsMessage,
+        ALL_WEBSOCKET_PROTOCOLS,
+    },
+    Request, Response,
 };
 use axum::{
-    extract::Extension,
+    extract::{
+        ws::{CloseFrame, Message, WebSocketUpgrade},
+        Extension,
+    },
     http::{
         header::{
-            ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN,
+            ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+            ACCESS_CONTROL_ALLOW_ORIGIN, SEC_WEBSOCKET_PROTOCOL,
         },
-        HeaderValue,
+        HeaderMap, HeaderValue,
     },
     response::Html,
     response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
+use futures::{future, SinkExt, StreamExt};
 use serde_json::json;
 use std::net::{SocketAddr, TcpListener};
+use std::str::FromStr;
 use tokio::signal::unix::SignalKind;
 use tokio::task::JoinHandle;
 use tower_http::{set_header::SetResponseHeaderLayer, trace::TraceLayer};
@@ -43,11 +54,13 @@ pub async fn start_server(
         .data(modules.block_producer.clone())
         .data(modules.sync.clone())
         .data(modules.bft.clone());
+    // debug sessions run with the genesis parameters, the scheduled upgrades don't apply to them
     let schema = dap::init(schema, params).extension(Tracing).finish();
 
     let router = Router::new()
         .route("/playground", get(graphql_playground))
         .route("/graphql", post(graphql_handler).options(ok))
+        .route("/graphql-ws", get(graphql_subscription_handler))
         .route("/metrics", get(metrics))
         .route("/health", get(health))
         .layer(Extension(schema))
@@ -117,7 +130,9 @@ async fn shutdown_signal() {
 }
 
 async fn graphql_playground() -> impl IntoResponse {
-    Html(playground_source(GraphQLPlaygroundConfig::new("/graphql")))
+    Html(playground_source(
+        GraphQLPlaygroundConfig::new("/graphql").subscription_endpoint("/graphql-ws"),
+    ))
 }
 
 async fn health() -> Json<serde_json::Value> {
@@ -128,6 +143,53 @@ async fn graphql_handler(schema: Extension<CoreSchema>, req: Json<Request>) -> J
     schema.execute(req.0).await.into()
 }
 
+/// Serves subscriptions over a WebSocket, using the protocol requested by the client.
+async fn graphql_subscription_handler(
+    schema: Extension<CoreSchema>,
+    ws: WebSocketUpgrade,
+    // must come after `WebSocketUpgrade`, which can't be extracted once the headers are taken
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let protocol = headers
+        .get(SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|protocols| {
+            protocols
+                .split(',')
+                .find_map(|protocol| WebSocketProtocols::from_str(protocol.trim()).ok())
+        })
+        .unwrap_or(WebSocketProtocols::SubscriptionsTransportWS);
+    let schema = schema.0;
+
+    ws.protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |socket| async move {
+            let (mut sink, stream) = socket.split();
+            let input = stream
+                .take_while(|message| future::ready(message.is_ok()))
+                .filter_map(|message| {
+                    future::ready(match message {
+                        Ok(message @ (Message::Text(_) | Message::Binary(_))) => {
+                            Some(message.into_data())
+                        }
+                        _ => None,
+                    })
+                });
+
+            let mut output = WebSocket::new(schema, input, protocol).map(|message| match message {
+                WsMessage::Text(text) => Message::Text(text),
+                WsMessage::Close(code, reason) => Message::Close(Some(CloseFrame {
+                    code,
+                    reason: reason.into(),
+                })),
+            });
+            while let Some(message) = output.next().await {
+                if sink.send(message).await.is_err() {
+                    break;
+                }
+            }
+        })
+}
+
 async fn ok() -> Result<(), ()> {
     Ok(())
 }
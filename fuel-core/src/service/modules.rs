@@ -41,9 +41,17 @@ pub async fn start_modules(config: &Config, database: &Database) -> Result<Modul
     let sync = fuel_sync::Service::new(&config.sync).await?;
     // let mut relayer = FuelRelayer::new(FuelRelayerConfig::default());
     // let mut p2p = FuelP2P::new(FuelP2PConfig::default());
+    // txpool validates transactions with the same settings as the executor
+    let txpool_config = fuel_txpool::Config {
+        utxo_validation: config.utxo_validation,
+        predicates: config.predicates,
+        transaction_parameters: config.chain_conf.transaction_parameters,
+        upgrades: config.chain_conf.upgrades.clone(),
+        ..config.txpool.clone()
+    };
     let txpool = fuel_txpool::Service::new(
         Box::new(database.clone()) as Box<dyn TxPoolDb>,
-        config.txpool.clone(),
+        txpool_config,
     )?;
 
     let p2p_mpsc = ();
@@ -76,9 +76,14 @@ impl KeyValueStore for MemoryStore {
         }
 
         if let Some(start) = start {
+            // seek to the first key at or after the start in the iteration direction, which
+            // doesn't have to exist
             Box::new(
                 copy.into_iter()
-                    .skip_while(move |(key, _)| key.as_slice() != start.as_slice()),
+                    .skip_while(move |(key, _)| match direction {
+                        IterDirection::Forward => key.as_slice() < start.as_slice(),
+                        IterDirection::Reverse => key.as_slice() > start.as_slice(),
+                    }),
             )
         } else {
             Box::new(copy.into_iter())
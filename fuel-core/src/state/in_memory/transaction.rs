@@ -25,6 +25,28 @@ impl MemoryTransactionView {
         }
     }
 
+    /// The column and key of every write made through this view.
+    pub fn changed_keys(&self) -> Vec<(ColumnId, Vec<u8>)> {
+        self.changes
+            .lock()
+            .expect("poisoned lock")
+            .values()
+            .map(|op| match op {
+                WriteOperation::Insert(key, column, _) | WriteOperation::Remove(key, column) => {
+                    (*column, key.clone())
+                }
+            })
+            .collect()
+    }
+
+    /// Every write made through this view, ordered by column and key.
+    pub fn writes(&self) -> Vec<WriteOperation> {
+        let changes = self.changes.lock().expect("poisoned lock");
+        let mut writes = changes.iter().collect::<Vec<_>>();
+        writes.sort_by_key(|(key, _)| *key);
+        writes.into_iter().map(|(_, op)| op.clone()).collect()
+    }
+
     pub fn commit(&self) -> crate::state::Result<()> {
         self.data_source.batch_write(
             &mut self
@@ -411,6 +433,33 @@ mod tests {
         assert_eq!(ret, vec![9, 8, 6, 4, 3, 2, 0])
     }
 
+    #[test]
+    fn iter_all_seeks_to_start_key_missing_from_a_layer() {
+        // setup
+        let store = Arc::new(MemoryStore::default());
+        (0..10).step_by(2).for_each(|i| {
+            store.put(vec![i], 0, vec![1]).unwrap();
+        });
+
+        let view = MemoryTransactionView::new(store);
+        // test
+        (0..10).step_by(3).for_each(|i| {
+            view.put(vec![i], 0, vec![2]).unwrap();
+        });
+
+        let forward = view
+            .iter_all(0, None, Some(vec![5]), IterDirection::Forward)
+            .map(|(k, _)| k[0])
+            .collect_vec();
+        let reverse = view
+            .iter_all(0, None, Some(vec![5]), IterDirection::Reverse)
+            .map(|(k, _)| k[0])
+            .collect_vec();
+        // verify
+        assert_eq!(forward, vec![6, 8, 9]);
+        assert_eq!(reverse, vec![4, 3, 2, 0]);
+    }
+
     #[test]
     fn iter_all_overrides_data_source_keys() {
         // setup
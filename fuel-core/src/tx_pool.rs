@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use fuel_core_interfaces::common::{fuel_tx::Bytes32, fuel_vm::prelude::ProgramState};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum TransactionStatus {
     Submitted {
         time: DateTime<Utc>,
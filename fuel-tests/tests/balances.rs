@@ -106,6 +106,63 @@ async fn balance() {
         .await
         .unwrap();
     assert_eq!(balance, 299);
+
+    // the balance before the transaction was included is still available
+    let balance = client
+        .balance_at_height(
+            format!("{:#x}", owner).as_str(),
+            Some(format!("{:#x}", asset_id).as_str()),
+            Some(0),
+        )
+        .await
+        .unwrap();
+    assert_eq!(balance, 300);
+    let balance = client
+        .balance_at_height(
+            format!("{:#x}", owner).as_str(),
+            Some(format!("{:#x}", asset_id).as_str()),
+            Some(1),
+        )
+        .await
+        .unwrap();
+    assert_eq!(balance, 299);
+
+    // so are the listings of coins and balances
+    let page = || PaginationRequest {
+        cursor: None,
+        results: 10,
+        direction: PageDirection::Forward,
+    };
+    let coins = client
+        .coins_at_height(
+            format!("{:#x}", owner).as_str(),
+            Some(format!("{:#x}", asset_id).as_str()),
+            page(),
+            Some(0),
+        )
+        .await
+        .unwrap()
+        .results;
+    let mut amounts: Vec<u64> = coins.iter().map(|coin| coin.amount.0).collect();
+    amounts.sort_unstable();
+    assert_eq!(amounts, vec![50, 100, 150]);
+    let balances = client
+        .balances_at_height(format!("{:#x}", owner).as_str(), page(), Some(0))
+        .await
+        .unwrap()
+        .results;
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].amount.0, 300);
+
+    // heights that haven't been produced yet are rejected
+    let result = client
+        .balance_at_height(
+            format!("{:#x}", owner).as_str(),
+            Some(format!("{:#x}", asset_id).as_str()),
+            Some(2),
+        )
+        .await;
+    assert!(result.is_err());
 }
 
 #[tokio::test]
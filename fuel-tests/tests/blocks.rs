@@ -2,13 +2,23 @@ use chrono::{TimeZone, Utc};
 use fuel_core::{
     config::Config,
     database::Database,
+    executor::Executor,
     model::{FuelBlockDb, FuelBlockHeader},
     schema::scalars::BlockId,
     service::FuelService,
 };
-use fuel_core_interfaces::common::{fuel_storage::Storage, fuel_types};
-use fuel_gql_client::client::{FuelClient, PageDirection, PaginationRequest};
+use fuel_core_interfaces::common::{
+    fuel_crypto::SecretKey,
+    fuel_storage::Storage,
+    fuel_tx::TransactionBuilder,
+    fuel_types,
+    fuel_vm::{consts::*, prelude::*},
+};
+use fuel_gql_client::client::{
+    types::TransactionStatus, FuelClient, PageDirection, PaginationRequest,
+};
 use itertools::{rev, Itertools};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 #[tokio::test]
 async fn block() {
@@ -121,3 +131,72 @@ async fn block_connection_last_5() {
         rev(5..10).collect_vec()
     );
 }
+
+#[tokio::test]
+async fn block_header_proofs_verify_against_prev_root() {
+    let db = Database::default();
+    let executor = Executor {
+        database: db.clone(),
+        config: Config::local_node(),
+    };
+    let mut block_ids = vec![];
+    for _ in 0..6 {
+        let block = executor.submit_txs(vec![]).await.unwrap();
+        block_ids.push(format!("{:#x}", block.id()));
+    }
+
+    let srv = FuelService::from_database(db, Config::local_node())
+        .await
+        .unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    for (root, root_id) in block_ids.iter().enumerate() {
+        let root_block = client.block(root_id).await.unwrap().unwrap();
+        for id in &block_ids[..root] {
+            let proof = client.block_header_proof(id, root_id).await.unwrap();
+            assert_eq!(proof.prev_root.0 .0, root_block.prev_root.0 .0);
+            assert_eq!(proof.leaves_count.0, root as u64);
+            assert!(proof.verify());
+        }
+        // a block isn't part of its own prev_root
+        assert!(client.block_header_proof(root_id, root_id).await.is_err());
+    }
+}
+
+#[tokio::test]
+async fn block_fees_are_credited_to_the_coinbase_recipient() {
+    let mut rng = StdRng::seed_from_u64(2322);
+    let recipient: Address = rng.gen();
+    let mut config = Config::local_node();
+    config.block_producer.coinbase_recipient = recipient;
+    let srv = FuelService::new_node(config).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let secret = SecretKey::random(&mut rng);
+    let tx = TransactionBuilder::script(Opcode::RET(REG_ONE).to_bytes().to_vec(), vec![])
+        .gas_limit(1_000)
+        .gas_price(1)
+        .byte_price(1)
+        .add_unsigned_coin_input(rng.gen(), &secret, 10_000, Default::default(), 0)
+        .add_output(Output::change(rng.gen(), 0, Default::default()))
+        .finalize();
+    let tx_id = client.submit(&tx).await.unwrap();
+    let block_id = match client.transaction_status(&tx_id.to_string()).await.unwrap() {
+        TransactionStatus::Success { block_id, .. } => block_id,
+        status => panic!("unexpected status {:?}", status),
+    };
+
+    let block = client.block(&block_id).await.unwrap().unwrap();
+    assert_eq!(block.producer.0 .0, recipient);
+    assert!(block.fees.0 > 0);
+    let coinbase = block.coinbase.unwrap();
+    assert_eq!(coinbase.owner.0 .0, recipient);
+    assert_eq!(coinbase.amount.0, block.fees.0);
+    assert_eq!(
+        client
+            .balance(&format!("{:#x}", recipient), None)
+            .await
+            .unwrap(),
+        block.fees.0
+    );
+}
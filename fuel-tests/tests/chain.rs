@@ -1,5 +1,5 @@
-use fuel_core::{config::Config, service::FuelService};
-use fuel_gql_client::client::FuelClient;
+use fuel_core::{config::Config, model::ParameterUpgrade, service::FuelService};
+use fuel_gql_client::{client::FuelClient, fuel_tx::ConsensusParameters};
 
 #[tokio::test]
 async fn chain_info() {
@@ -15,3 +15,27 @@ async fn chain_info() {
         chain_info.consensus_parameters.into()
     );
 }
+
+#[tokio::test]
+async fn consensus_parameters_follow_the_upgrade_schedule() {
+    let mut node_config = Config::local_node();
+    let upgraded = ConsensusParameters {
+        max_inputs: 2,
+        ..node_config.chain_conf.transaction_parameters
+    };
+    node_config.chain_conf.upgrades = vec![ParameterUpgrade {
+        height: 5u32.into(),
+        transaction_parameters: Some(upgraded),
+        predicates: None,
+    }];
+    let srv = FuelService::new_node(node_config.clone()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    // the next block is the first one
+    let next: ConsensusParameters = client.consensus_parameters(None).await.unwrap().into();
+    assert_eq!(next, node_config.chain_conf.transaction_parameters);
+    let before: ConsensusParameters = client.consensus_parameters(Some(4)).await.unwrap().into();
+    assert_eq!(before, node_config.chain_conf.transaction_parameters);
+    let after: ConsensusParameters = client.consensus_parameters(Some(5)).await.unwrap().into();
+    assert_eq!(after, upgraded);
+}
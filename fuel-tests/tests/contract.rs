@@ -1,6 +1,11 @@
 use crate::helpers::{TestContext, TestSetupBuilder};
-use fuel_core_interfaces::common::fuel_vm::prelude::*;
-use fuel_gql_client::client::{PageDirection, PaginationRequest};
+use fuel_core_interfaces::common::{
+    fuel_tx::TransactionBuilder,
+    fuel_vm::{consts::*, prelude::*},
+};
+use fuel_crypto::SecretKey;
+use fuel_gql_client::client::{types::TransactionStatus, PageDirection, PaginationRequest};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use rstest::rstest;
 
 const SEED: u64 = 2322;
@@ -81,3 +86,169 @@ async fn test_first_5_contract_balances(#[case] direction: PageDirection) {
     assert_eq!(contract_balances.results[1].amount.0, 400);
     assert_eq!(contract_balances.results[2].amount.0, 700);
 }
+
+#[tokio::test]
+async fn transactions_by_contract() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let mut test_builder = TestSetupBuilder::new(SEED);
+    let (_, contract_id) = test_builder.setup_contract(vec![], None);
+    let secret = SecretKey::random(&mut rng);
+
+    // use the genesis contract as an input
+    let call = TransactionBuilder::script(Opcode::RET(REG_ONE).to_bytes().to_vec(), vec![])
+        .gas_limit(1_000_000)
+        .add_unsigned_coin_input(rng.gen(), &secret, 1000, Default::default(), 0)
+        .add_input(Input::contract(
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            contract_id,
+        ))
+        .add_output(Output::contract(1, Default::default(), Default::default()))
+        .finalize();
+
+    // deploy a new contract
+    let bytecode: Witness = Opcode::RET(REG_ONE).to_bytes().to_vec().into();
+    let salt: Salt = rng.gen();
+    let contract = Contract::from(bytecode.as_ref());
+    let state_root = Contract::default_state_root();
+    let created_id = contract.id(&salt, &contract.root(), &state_root);
+    let create = TransactionBuilder::create(bytecode, salt, vec![], vec![])
+        .gas_limit(1_000_000)
+        .add_unsigned_coin_input(rng.gen(), &secret, 1000, Default::default(), 0)
+        .add_output(Output::contract_created(created_id, state_root))
+        .finalize();
+
+    test_builder.config_coin_inputs_from_transactions(&[&call, &create]);
+    let TestContext { client, .. } = test_builder.finalize().await;
+    client.submit(&call).await.unwrap();
+    client.submit(&create).await.unwrap();
+
+    let page = PaginationRequest {
+        cursor: None,
+        results: 10,
+        direction: PageDirection::Forward,
+    };
+    let used = client
+        .transactions_by_contract(&format!("{:#x}", contract_id), page.clone())
+        .await
+        .unwrap()
+        .results;
+    assert_eq!(used.len(), 1);
+    assert_eq!(used[0].transaction.id(), call.id());
+
+    let created = client
+        .transactions_by_contract(&format!("{:#x}", created_id), page)
+        .await
+        .unwrap()
+        .results;
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].transaction.id(), create.id());
+}
+
+#[tokio::test]
+async fn contract_storage() {
+    let mut test_builder = TestSetupBuilder::new(SEED);
+    let (_, contract_id) = test_builder.setup_contract(vec![], None);
+    let slots: Vec<(Bytes32, Bytes32)> = (1..=3u8)
+        .map(|i| (Bytes32::new([i; 32]), Bytes32::new([i * 10; 32])))
+        .collect();
+    test_builder.contracts.get_mut(&contract_id).unwrap().state = Some(slots.clone());
+    let TestContext { client, .. } = test_builder.finalize().await;
+    let contract = format!("{:#x}", contract_id);
+
+    let value = client
+        .contract_storage_slot(&contract, &format!("{:#x}", slots[1].0))
+        .await
+        .unwrap();
+    assert_eq!(value, Some(slots[1].1));
+    let missing = client
+        .contract_storage_slot(&contract, &format!("{:#x}", Bytes32::new([9; 32])))
+        .await
+        .unwrap();
+    assert_eq!(missing, None);
+
+    let first_page = client
+        .contract_storage(
+            &contract,
+            PaginationRequest {
+                cursor: None,
+                results: 2,
+                direction: PageDirection::Forward,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(first_page.has_next_page);
+    let second_page = client
+        .contract_storage(
+            &contract,
+            PaginationRequest {
+                cursor: first_page.cursor,
+                results: 2,
+                direction: PageDirection::Forward,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(!second_page.has_next_page);
+
+    let keys: Vec<Bytes32> = first_page
+        .results
+        .into_iter()
+        .chain(second_page.results)
+        .map(|slot| slot.key.into())
+        .collect();
+    assert_eq!(keys, slots.iter().map(|slot| slot.0).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn contract_storage_slot_proofs_verify_against_state_root() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let mut test_builder = TestSetupBuilder::new(SEED);
+    let (_, contract_id) = test_builder.setup_contract(vec![], None);
+    let slots: Vec<(Bytes32, Bytes32)> = (1..=3u8)
+        .map(|i| (Bytes32::new([i; 32]), Bytes32::new([i * 10; 32])))
+        .collect();
+    test_builder.contracts.get_mut(&contract_id).unwrap().state = Some(slots.clone());
+    let secret = SecretKey::random(&mut rng);
+    let script = TransactionBuilder::script(Opcode::RET(REG_ONE).to_bytes().to_vec(), vec![])
+        .gas_limit(1_000_000)
+        .add_unsigned_coin_input(rng.gen(), &secret, 1000, Default::default(), 0)
+        .finalize();
+    test_builder.config_coin_inputs_from_transactions(&[&script]);
+    let TestContext { client, .. } = test_builder.finalize().await;
+    let contract = format!("{:#x}", contract_id);
+
+    // the genesis state is committed to by the first block
+    let tx_id = client.submit(&script).await.unwrap();
+    let block_id = match client.transaction_status(&tx_id.to_string()).await.unwrap() {
+        TransactionStatus::Success { block_id, .. } => block_id,
+        status => panic!("unexpected status {:?}", status),
+    };
+    let block = client.block(&block_id).await.unwrap().unwrap();
+
+    let proof = client
+        .contract_storage_slot_proof(&contract, &format!("{:#x}", slots[1].0))
+        .await
+        .unwrap();
+    assert_eq!(
+        proof.value.as_ref().map(|value| value.0 .0),
+        Some(slots[1].1)
+    );
+    assert_eq!(proof.state_root.0 .0, block.state_root.0 .0);
+    assert_eq!(proof.block_height.0, block.height.0);
+    assert!(proof.verify());
+
+    let missing = client
+        .contract_storage_slot_proof(&contract, &format!("{:#x}", Bytes32::new([9; 32])))
+        .await
+        .unwrap();
+    assert!(missing.value.is_none());
+    assert!(missing.verify());
+
+    // a proof doesn't hold for another value
+    let mut forged = proof;
+    forged.value = Some(slots[0].1.into());
+    assert!(!forged.verify());
+}
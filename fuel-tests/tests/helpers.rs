@@ -1,6 +1,6 @@
 use fuel_core::config::{
     chain_config::{ChainConfig, CoinConfig, ContractConfig, StateConfig},
-    Config,
+    Config, VMConfig,
 };
 use fuel_core::service::FuelService;
 use fuel_core_interfaces::common::{fuel_tx::Transaction, fuel_vm::prelude::*};
@@ -32,6 +32,7 @@ pub struct TestSetupBuilder {
     pub min_gas_price: u64,
     pub min_byte_price: u64,
     pub predicates: bool,
+    pub store_traces: bool,
 }
 
 impl TestSetupBuilder {
@@ -110,11 +111,15 @@ impl TestSetupBuilder {
         self
     }
 
-    // setup chainspec and spin up a fuel-node
-    pub async fn finalize(&mut self) -> TestContext {
-        let config = Config {
+    /// the node config with the chainspec set up so far
+    pub fn config(&self) -> Config {
+        Config {
             utxo_validation: true,
             predicates: self.predicates,
+            vm: VMConfig {
+                store_traces: self.store_traces,
+                ..Default::default()
+            },
             txpool: fuel_txpool::Config {
                 min_byte_price: self.min_byte_price,
                 min_gas_price: self.min_gas_price,
@@ -129,9 +134,12 @@ impl TestSetupBuilder {
                 ..ChainConfig::local_testnet()
             },
             ..Config::local_node()
-        };
+        }
+    }
 
-        let srv = FuelService::new_node(config).await.unwrap();
+    // setup chainspec and spin up a fuel-node
+    pub async fn finalize(&mut self) -> TestContext {
+        let srv = FuelService::new_node(self.config()).await.unwrap();
         let client = FuelClient::from(srv.bound_address);
 
         TestContext {
@@ -150,6 +158,7 @@ impl Default for TestSetupBuilder {
             min_gas_price: 0,
             min_byte_price: 0,
             predicates: false,
+            store_traces: false,
         }
     }
 }
@@ -7,5 +7,8 @@ mod dap;
 mod debugger;
 mod health;
 mod helpers;
+mod logs;
 mod node_info;
+mod subscriptions;
 mod tx;
+mod txpool;
@@ -0,0 +1,210 @@
+use fuel_core::{config::Config, service::FuelService};
+use fuel_core_interfaces::common::{
+    fuel_tx,
+    fuel_vm::{consts::*, prelude::*},
+};
+use fuel_gql_client::client::{
+    schema::{
+        log::{Log, LogFilterInput},
+        tx::ReceiptType,
+    },
+    FuelClient, PageDirection, PaginationRequest,
+};
+
+#[tokio::test]
+async fn logs_are_indexed_in_chain_order() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+    submit_log_scripts(&client, 3).await;
+
+    let logs = client
+        .logs(LogFilterInput::default(), page(None, 10))
+        .await
+        .unwrap()
+        .results;
+
+    assert_eq!(logs.len(), 3);
+    assert!(logs
+        .windows(2)
+        .all(|pair| pair[0].block_height.0 < pair[1].block_height.0));
+    let receipts: Vec<Receipt> = logs
+        .into_iter()
+        .map(|log| log.receipt.try_into().unwrap())
+        .collect();
+    assert!(matches!(receipts[1], Receipt::Log { ra, rb, .. } if ra == 1 && rb == 0xba));
+}
+
+#[tokio::test]
+async fn logs_are_filtered() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+    submit_log_scripts(&client, 3).await;
+
+    let by_register = client
+        .logs(
+            LogFilterInput {
+                ra: Some(2u64.into()),
+                ..Default::default()
+            },
+            page(None, 10),
+        )
+        .await
+        .unwrap()
+        .results;
+    assert_eq!(by_register.len(), 1);
+
+    let by_type = client
+        .logs(
+            LogFilterInput {
+                receipt_type: Some(ReceiptType::LogData),
+                ..Default::default()
+            },
+            page(None, 10),
+        )
+        .await
+        .unwrap()
+        .results;
+    assert!(by_type.is_empty());
+
+    let all = client
+        .logs(LogFilterInput::default(), page(None, 10))
+        .await
+        .unwrap()
+        .results;
+    let by_height = client
+        .logs(
+            LogFilterInput {
+                from_height: Some(all[1].block_height.clone()),
+                to_height: Some(all[1].block_height.clone()),
+                ..Default::default()
+            },
+            page(None, 10),
+        )
+        .await
+        .unwrap()
+        .results;
+    assert_eq!(by_height.len(), 1);
+    assert_eq!(by_height[0].transaction_id.0 .0, all[1].transaction_id.0 .0);
+}
+
+#[tokio::test]
+async fn logs_are_paginated() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+    submit_log_scripts(&client, 3).await;
+
+    let first_page = client
+        .logs(LogFilterInput::default(), page(None, 2))
+        .await
+        .unwrap();
+    assert_eq!(first_page.results.len(), 2);
+    assert!(first_page.has_next_page);
+
+    let second_page = client
+        .logs(LogFilterInput::default(), page(first_page.cursor, 2))
+        .await
+        .unwrap();
+    assert_eq!(second_page.results.len(), 1);
+    assert!(second_page.has_previous_page);
+    assert!(!second_page.has_next_page);
+}
+
+#[tokio::test]
+async fn logs_are_paginated_within_height_range() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+    submit_log_scripts(&client, 3).await;
+
+    let all = client
+        .logs(LogFilterInput::default(), page(None, 10))
+        .await
+        .unwrap()
+        .results;
+    let heights =
+        |logs: &[Log]| -> Vec<u64> { logs.iter().map(|log| log.block_height.0).collect() };
+
+    // a cursor below the range resumes from the start of the range
+    let first = client
+        .logs(LogFilterInput::default(), page(None, 1))
+        .await
+        .unwrap();
+    let from_second = client
+        .logs(
+            LogFilterInput {
+                from_height: Some(all[1].block_height.clone()),
+                ..Default::default()
+            },
+            page(first.cursor, 10),
+        )
+        .await
+        .unwrap();
+    assert_eq!(heights(&from_second.results), heights(&all[1..]));
+
+    let up_to_second = client
+        .logs(
+            LogFilterInput {
+                to_height: Some(all[1].block_height.clone()),
+                ..Default::default()
+            },
+            PaginationRequest {
+                cursor: None,
+                results: 10,
+                direction: PageDirection::Backward,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(heights(&up_to_second.results), heights(&all[..2]));
+}
+
+#[tokio::test]
+async fn logs_reject_non_log_receipt_types() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let result = client
+        .logs(
+            LogFilterInput {
+                receipt_type: Some(ReceiptType::Call),
+                ..Default::default()
+            },
+            page(None, 10),
+        )
+        .await;
+    assert!(result.is_err());
+}
+
+fn page(cursor: Option<String>, results: usize) -> PaginationRequest<String> {
+    PaginationRequest {
+        cursor,
+        results,
+        direction: PageDirection::Forward,
+    }
+}
+
+/// Submit `count` scripts, each in its own block, that log their index in `ra` and `0xba` in `rb`
+async fn submit_log_scripts(client: &FuelClient, count: u16) {
+    for i in 0..count {
+        let script: Vec<u8> = [
+            Opcode::ADDI(0x10, REG_ZERO, i),
+            Opcode::ADDI(0x11, REG_ZERO, 0xba),
+            Opcode::LOG(0x10, 0x11, REG_ZERO, REG_ZERO),
+            Opcode::RET(REG_ONE),
+        ]
+        .iter()
+        .flat_map(|op| u32::from(*op).to_be_bytes())
+        .collect();
+        let tx = fuel_tx::Transaction::script(
+            0,
+            1_000_000,
+            0,
+            0,
+            script,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+        );
+        client.submit(&tx).await.unwrap();
+    }
+}
@@ -15,6 +15,7 @@ async fn node_info() {
         min_gas_price,
         max_depth,
         max_tx,
+        max_tx_per_owner,
         ..
     } = client.node_info().await.unwrap();
 
@@ -25,4 +26,5 @@ async fn node_info() {
     assert_eq!(min_byte_price, node_config.txpool.min_byte_price.into());
     assert_eq!(max_depth, node_config.txpool.max_depth.into());
     assert_eq!(max_tx, node_config.txpool.max_tx.into());
+    assert_eq!(max_tx_per_owner, node_config.txpool.max_tx_per_owner.into());
 }
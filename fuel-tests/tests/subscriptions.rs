@@ -0,0 +1,92 @@
+use fuel_core::{config::Config, service::FuelService};
+use fuel_core_interfaces::common::{
+    fuel_tx::{self, Input, Output, Transaction, TransactionBuilder, UtxoId},
+    fuel_vm::prelude::{Address, AssetId},
+};
+use fuel_gql_client::client::{types::TransactionStatus, FuelClient};
+use futures::StreamExt;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+#[tokio::test]
+async fn subscribe_transaction_status_until_executed() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let transaction = fuel_tx::Transaction::default();
+    let id = format!("{:#x}", transaction.id());
+
+    let statuses = client.subscribe_transaction_status(&id).await.unwrap();
+    client.submit(&transaction).await.unwrap();
+
+    // the stream ends once the transaction is executed
+    let statuses: Vec<TransactionStatus> = statuses.map(Result::unwrap).collect().await;
+    assert!(matches!(
+        statuses.last(),
+        Some(TransactionStatus::Success { .. })
+    ));
+}
+
+#[tokio::test]
+async fn subscribe_blocks() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let mut blocks = client.subscribe_blocks().await.unwrap();
+    let producer = produce_blocks(client.clone(), |i| {
+        TransactionBuilder::script(vec![], i.to_be_bytes().to_vec()).finalize()
+    });
+
+    let block = blocks.next().await.unwrap().unwrap();
+    producer.abort();
+    assert!(block.height.0 > 0);
+    assert_eq!(block.transactions.len(), 1);
+}
+
+#[tokio::test]
+async fn subscribe_coins_received_by_owner() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let owner = Address::new([7; 32]);
+    let mut coins = client
+        .subscribe_coins(&format!("{:#x}", owner))
+        .await
+        .unwrap();
+    let producer = produce_blocks(client.clone(), move |i| {
+        let mut utxo_tx_id = [0u8; 32];
+        utxo_tx_id[..8].copy_from_slice(&i.to_be_bytes());
+        TransactionBuilder::script(vec![], vec![])
+            .add_input(Input::coin_signed(
+                UtxoId::new(utxo_tx_id.into(), 0),
+                Address::new([8; 32]),
+                30,
+                AssetId::default(),
+                0,
+                0,
+            ))
+            .add_output(Output::coin(owner, 10, AssetId::default()))
+            .add_output(Output::coin(Address::new([8; 32]), 20, AssetId::default()))
+            .add_witness(Default::default())
+            .finalize()
+    });
+
+    let coin = coins.next().await.unwrap().unwrap();
+    producer.abort();
+    assert_eq!(coin.owner.0 .0, owner);
+    assert_eq!(coin.amount.0, 10);
+}
+
+/// Keep submitting transactions, since the subscription may only become active on the node
+/// after the first ones were executed.
+fn produce_blocks<F>(client: FuelClient, make_tx: F) -> JoinHandle<()>
+where
+    F: Fn(u64) -> Transaction + Send + 'static,
+{
+    tokio::spawn(async move {
+        for i in 0u64.. {
+            client.submit(&make_tx(i)).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+}
@@ -1,4 +1,4 @@
-use crate::helpers::TestContext;
+use crate::helpers::{TestContext, TestSetupBuilder};
 use chrono::Utc;
 use fuel_core::{
     config::Config,
@@ -8,14 +8,24 @@ use fuel_core::{
     service::FuelService,
 };
 use fuel_core_interfaces::common::{
-    fuel_tx,
-    fuel_vm::{consts::*, prelude::*},
+    fuel_tx::{self, TransactionBuilder},
+    fuel_vm::{consts::*, prelude::*, script_with_data_offset},
 };
+use fuel_crypto::SecretKey;
 use fuel_gql_client::client::{
-    types::TransactionStatus, FuelClient, PageDirection, PaginationRequest,
+    schema::{
+        coin::CoinStatus,
+        tx::{
+            CoinOverrideInput, ContractBalanceOverrideInput, DryRunOverridesInput,
+            StorageSlotOverrideInput,
+        },
+        U64,
+    },
+    types::TransactionStatus,
+    FuelClient, PageDirection, PaginationRequest,
 };
 use itertools::Itertools;
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::io;
 
 mod predicates;
@@ -85,6 +95,333 @@ async fn dry_run() {
         } if val == 1));
 }
 
+#[tokio::test]
+async fn dry_run_bundle_sees_earlier_transactions_and_overrides() {
+    let mut rng = StdRng::seed_from_u64(2322);
+    let mut test_builder = TestSetupBuilder::new(2322);
+    // logs the word in the storage slot keyed by the contract id along with the balance of the
+    // asset forwarded by the call, then increments the word
+    let code: Vec<u8> = vec![
+        Opcode::SRW(0x10, REG_FP),
+        Opcode::ADDI(0x11, REG_FP, 32),
+        Opcode::BAL(0x12, 0x11, REG_FP),
+        Opcode::LOG(0x10, 0x12, REG_ZERO, REG_ZERO),
+        Opcode::ADDI(0x10, 0x10, 1),
+        Opcode::SWW(REG_FP, 0x10),
+        Opcode::RET(REG_ONE),
+    ]
+    .into_iter()
+    .collect();
+    let (_, contract_id) = test_builder.setup_contract(code, None);
+    let TestContext { client, .. } = test_builder.finalize().await;
+
+    let secret = SecretKey::random(&mut rng);
+    let call_data = Call::new(contract_id, 0, 0).to_bytes();
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            Opcode::MOVI(0x10, data_offset),
+            Opcode::MOVI(0x11, data_offset + call_data.len() as Immediate18),
+            Opcode::CALL(0x10, REG_ZERO, 0x11, REG_CGAS),
+            Opcode::RET(REG_ONE),
+        ],
+        ConsensusParameters::DEFAULT.tx_offset()
+    );
+    let script: Vec<u8> = script.into_iter().collect();
+    let script_data: Vec<u8> = call_data
+        .into_iter()
+        .chain(AssetId::default().iter().copied())
+        .collect();
+    let call = |rng: &mut StdRng| {
+        TransactionBuilder::script(script.clone(), script_data.clone())
+            .gas_limit(1_000_000)
+            .add_unsigned_coin_input(rng.gen(), &secret, 1000, Default::default(), 0)
+            .add_input(Input::contract(
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                contract_id,
+            ))
+            .add_output(Output::contract(1, Default::default(), Default::default()))
+            .finalize()
+    };
+    let txs = vec![call(&mut rng), call(&mut rng)];
+    let coins = txs
+        .iter()
+        .flat_map(|tx| tx.inputs())
+        .filter_map(|input| match input {
+            Input::CoinSigned {
+                utxo_id,
+                owner,
+                amount,
+                asset_id,
+                ..
+            } => Some(CoinOverrideInput {
+                utxo_id: (*utxo_id).into(),
+                owner: (*owner).into(),
+                amount: U64(*amount),
+                asset_id: (*asset_id).into(),
+                maturity: None,
+            }),
+            _ => None,
+        })
+        .collect_vec();
+
+    // the coins of the transactions don't exist without the overrides
+    assert!(client.dry_run_bundle(&txs, None, None).await.is_err());
+
+    let logged = |receipts: &[Receipt]| {
+        receipts.iter().find_map(|receipt| match receipt {
+            Receipt::Log { ra, rb, .. } => Some((*ra, *rb)),
+            _ => None,
+        })
+    };
+    let overrides = DryRunOverridesInput {
+        coins: Some(coins.clone()),
+        ..Default::default()
+    };
+    let receipts = client
+        .dry_run_bundle(&txs, None, Some(overrides))
+        .await
+        .unwrap();
+    assert_eq!(logged(&receipts[0]), Some((0, 0)));
+    // the second transaction sees the slot written by the first
+    assert_eq!(logged(&receipts[1]), Some((1, 0)));
+
+    let mut counter = Bytes32::zeroed();
+    counter[..8].copy_from_slice(&5u64.to_be_bytes());
+    let overrides = DryRunOverridesInput {
+        coins: Some(coins),
+        contract_balances: Some(vec![ContractBalanceOverrideInput {
+            contract: contract_id.into(),
+            asset_id: AssetId::default().into(),
+            amount: U64(1000),
+        }]),
+        storage_slots: Some(vec![StorageSlotOverrideInput {
+            contract: contract_id.into(),
+            key: Bytes32::new(*contract_id).into(),
+            value: counter.into(),
+        }]),
+    };
+    let receipts = client
+        .dry_run_bundle(&txs, None, Some(overrides))
+        .await
+        .unwrap();
+    assert_eq!(logged(&receipts[0]), Some((5, 1000)));
+    assert_eq!(logged(&receipts[1]), Some((6, 1000)));
+
+    // nothing was committed
+    let balance = client
+        .contract_balance(&format!("{:#x}", contract_id), None)
+        .await
+        .unwrap();
+    assert_eq!(balance, 0);
+}
+
+#[tokio::test]
+async fn dry_run_state_diff_lists_spent_and_created_coins() {
+    let mut rng = StdRng::seed_from_u64(2322);
+    let secret = SecretKey::random(&mut rng);
+    let recipient: Address = rng.gen();
+    let change: Address = rng.gen();
+    let tx = TransactionBuilder::script(Opcode::RET(REG_ONE).to_bytes().to_vec(), vec![])
+        .gas_limit(1_000_000)
+        .gas_price(1)
+        .add_unsigned_coin_input(rng.gen(), &secret, 1000, Default::default(), 0)
+        .add_output(Output::coin(recipient, 300, Default::default()))
+        .add_output(Output::change(change, 0, Default::default()))
+        .finalize();
+    let mut test_builder = TestSetupBuilder::new(2322);
+    test_builder.config_coin_inputs_from_transactions(&[&tx]);
+    let TestContext { client, .. } = test_builder.finalize().await;
+
+    let diff = client.dry_run_state_diff(&tx, None).await.unwrap();
+    assert!(matches!(
+        diff.receipts.into_iter().last().map(Receipt::try_from),
+        Some(Ok(Receipt::ScriptResult { result, .. })) if result == ScriptExecutionResult::Success
+    ));
+
+    let spent = *tx.inputs()[0].utxo_id();
+    assert_eq!(diff.spent_coins.len(), 1);
+    assert_eq!(UtxoId::from(diff.spent_coins[0].utxo_id.clone()), spent);
+    assert_eq!(diff.spent_coins[0].amount.0, 1000);
+
+    let created = diff
+        .created_coins
+        .iter()
+        .map(|coin| (Address::from(coin.owner.clone()), coin.amount.0))
+        .sorted()
+        .collect_vec();
+    // the fees go to the coinbase coin of the dry run block, which isn't listed
+    let change_amount = created
+        .iter()
+        .find_map(|(owner, amount)| (*owner == change).then_some(*amount))
+        .unwrap();
+    assert!(change_amount < 700);
+    let expected = [(recipient, 300), (change, change_amount)]
+        .into_iter()
+        .sorted()
+        .collect_vec();
+    assert_eq!(created, expected);
+    assert!(diff.contract_balance_changes.is_empty());
+    assert!(diff.storage_slot_changes.is_empty());
+
+    // nothing was committed
+    let coin = client
+        .coin(&format!("{:#x}", spent))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(coin.status, CoinStatus::Unspent);
+}
+
+#[tokio::test]
+async fn dry_runs_reject_transactions_over_the_block_limits() {
+    let mut config = Config::local_node();
+    config.chain_conf.max_gas_per_block = 1_000;
+    let srv = FuelService::new_node(config).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let tx = TransactionBuilder::script(Opcode::RET(REG_ONE).to_bytes().to_vec(), vec![])
+        .gas_limit(1_001)
+        .finalize();
+    let errors = [
+        client.dry_run(&tx).await.unwrap_err(),
+        client.dry_run_state_diff(&tx, None).await.unwrap_err(),
+        client.dry_run_trace(&tx, None, None).await.unwrap_err(),
+    ];
+    for err in errors {
+        assert!(err
+            .to_string()
+            .contains("exceeds the gas or size limit of a block"));
+    }
+}
+
+#[tokio::test]
+async fn transaction_traces_hold_the_calls_of_the_transaction() {
+    let mut rng = StdRng::seed_from_u64(2322);
+    let mut test_builder = TestSetupBuilder::new(2322);
+    test_builder.store_traces = true;
+    let code = vec![Opcode::ADDI(0x10, REG_ZERO, 5), Opcode::RET(0x10)]
+        .into_iter()
+        .collect();
+    let (_, contract_id) = test_builder.setup_contract(code, None);
+
+    let secret = SecretKey::random(&mut rng);
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            Opcode::MOVI(0x10, data_offset),
+            Opcode::CALL(0x10, REG_ZERO, REG_ZERO, REG_CGAS),
+            Opcode::RET(REG_ONE),
+        ],
+        ConsensusParameters::DEFAULT.tx_offset()
+    );
+    let script: Vec<u8> = script.into_iter().collect();
+    let call = |rng: &mut StdRng| {
+        TransactionBuilder::script(script.clone(), Call::new(contract_id, 0, 0).to_bytes())
+            .gas_limit(1_000_000)
+            .add_unsigned_coin_input(rng.gen(), &secret, 1000, Default::default(), 0)
+            .add_input(Input::contract(
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                contract_id,
+            ))
+            .add_output(Output::contract(1, Default::default(), Default::default()))
+            .finalize()
+    };
+    let (submitted, dry_run) = (call(&mut rng), call(&mut rng));
+    test_builder.config_coin_inputs_from_transactions(&[&submitted, &dry_run]);
+    let TestContext { client, .. } = test_builder.finalize().await;
+
+    let assert_calls_contract = |trace: fuel_gql_client::client::schema::tx::ExecutionTrace| {
+        assert!(trace.gas_used.0 > 0);
+        assert!(trace.steps.is_empty());
+        assert_eq!(trace.calls.len(), 1);
+        let call = trace.calls.into_iter().next().unwrap();
+        assert_eq!(call.depth, 0);
+        assert!(matches!(
+            Receipt::try_from(call.call),
+            Ok(Receipt::Call { to, .. }) if to == contract_id
+        ));
+        assert!(matches!(
+            call.outcome.map(Receipt::try_from),
+            Some(Ok(Receipt::Return { id, val: 5, .. })) if id == contract_id
+        ));
+    };
+
+    let id = client.submit(&submitted).await.unwrap();
+    let trace = client
+        .transaction_trace(&id.0.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_calls_contract(trace);
+
+    let trace = client.dry_run_trace(&dry_run, None, None).await.unwrap();
+    assert_calls_contract(trace);
+    // nothing is stored for dry runs
+    let trace = client
+        .transaction_trace(&format!("{:#x}", dry_run.id()))
+        .await
+        .unwrap();
+    assert!(trace.is_none());
+}
+
+#[tokio::test]
+async fn estimate_gas() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let script = vec![
+        Opcode::ADDI(0x10, REG_ZERO, 0xca),
+        Opcode::ADDI(0x11, REG_ZERO, 0xba),
+        Opcode::LOG(0x10, 0x11, REG_ZERO, REG_ZERO),
+        Opcode::RET(REG_ONE),
+    ];
+    let script: Vec<u8> = script
+        .iter()
+        .flat_map(|op| u32::from(*op).to_be_bytes())
+        .collect();
+    let mut tx =
+        fuel_tx::Transaction::script(0, 1_000_000, 0, 0, script, vec![], vec![], vec![], vec![]);
+
+    let estimate = client.estimate_gas(&tx).await.unwrap();
+    assert_eq!(estimate.fee.0, 0);
+
+    // the estimated limit is enough
+    tx.set_gas_limit(estimate.gas_limit.0);
+    let receipts = client.dry_run(&tx).await.unwrap();
+    assert!(matches!(
+        receipts.last(),
+        Some(Receipt::ScriptResult { result, gas_used })
+            if *result == ScriptExecutionResult::Success && *gas_used == estimate.gas_used.0
+    ));
+
+    // but any lower limit runs out of gas
+    tx.set_gas_limit(estimate.gas_limit.0 - 1);
+    let receipts = client.dry_run(&tx).await.unwrap();
+    assert!(receipts.iter().any(|receipt| matches!(
+        receipt,
+        Receipt::Panic { reason, .. } if *reason.reason() == PanicReason::OutOfGas
+    )));
+
+    let reverts = fuel_tx::Transaction::script(
+        0,
+        1_000_000,
+        0,
+        0,
+        Opcode::RVRT(REG_ONE).to_bytes().to_vec(),
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+    );
+    let err = client.estimate_gas(&reverts).await.unwrap_err();
+    assert!(err.to_string().contains("Revert(1)"));
+}
+
 #[tokio::test]
 async fn submit() {
     let srv = FuelService::new_node(Config::local_node()).await.unwrap();
@@ -403,6 +740,47 @@ async fn get_transactions_from_manual_blocks() {
     );
 }
 
+#[tokio::test]
+async fn transaction_proofs_verify_against_transactions_root() {
+    let (executor, db) = get_executor_and_db();
+    let client = initialize_client(db).await;
+
+    let mut block = FuelBlock {
+        header: FuelBlockHeader {
+            height: 1u32.into(),
+            time: Utc::now(),
+            ..Default::default()
+        },
+        transactions: (0..5).map(create_mock_tx).collect(),
+    };
+    executor
+        .execute(&mut block, ExecutionMode::Production)
+        .await
+        .unwrap();
+
+    for (index, tx) in block.transactions.iter().enumerate() {
+        let proof = client
+            .transaction_proof(&format!("{:#x}", tx.id()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(proof.block_id.0 .0, block.id());
+        assert_eq!(proof.transactions_root.0 .0, block.header.transactions_root);
+        assert_eq!(proof.index.0, index as u64);
+        assert_eq!(proof.transactions_count.0, 5);
+        assert!(proof.verify(tx));
+        // the proof doesn't hold for any other transaction
+        assert!(!proof.verify(&block.transactions[(index + 1) % 5]));
+    }
+
+    // transactions that aren't part of a block have no proof
+    let proof = client
+        .transaction_proof(&format!("{:#x}", create_mock_tx(5).id()))
+        .await
+        .unwrap();
+    assert!(proof.is_none());
+}
+
 #[tokio::test]
 async fn get_owned_transactions() {
     let alice = Address::from([0; 32]);
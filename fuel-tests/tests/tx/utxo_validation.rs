@@ -1,12 +1,16 @@
 //! Tests involving client behavior when utxo-validation is enabled
 
 use crate::helpers::{TestContext, TestSetupBuilder};
+use fuel_core::{database::Database, service::FuelService};
 use fuel_core_interfaces::common::{
+    fuel_storage::Storage,
     fuel_tx::TransactionBuilder,
     fuel_vm::{consts::*, prelude::*},
 };
 use fuel_crypto::SecretKey;
-use fuel_gql_client::client::{types::TransactionStatus, PageDirection, PaginationRequest};
+use fuel_gql_client::client::{
+    types::TransactionStatus, FuelClient, PageDirection, PaginationRequest,
+};
 use futures::future::join_all;
 use itertools::Itertools;
 use rand::{rngs::StdRng, Rng, SeedableRng};
@@ -87,6 +91,164 @@ async fn submit_utxo_verified_tx_with_min_gas_price() {
     }
 }
 
+#[tokio::test]
+async fn failed_block_evicts_only_its_failing_transaction_from_the_pool() {
+    let mut rng = StdRng::seed_from_u64(2322);
+    let mut test_builder = TestSetupBuilder::new(2322);
+    let transactions = (1..=4)
+        .map(|i| {
+            let secret = SecretKey::random(&mut rng);
+            TransactionBuilder::script(
+                Opcode::RET(REG_ONE).to_bytes().into_iter().collect(),
+                vec![],
+            )
+            .gas_limit(100)
+            .add_unsigned_coin_input(rng.gen(), &secret, 1000 + i, Default::default(), 0)
+            .add_output(Output::Change {
+                amount: 0,
+                asset_id: Default::default(),
+                to: rng.gen(),
+            })
+            .finalize()
+        })
+        .collect_vec();
+    test_builder.config_coin_inputs_from_transactions(&transactions.iter().collect_vec());
+    let journal = tempfile::tempdir().unwrap();
+    let mut config = test_builder.config();
+    config.txpool.journal_path = Some(journal.path().join("txpool.journal"));
+
+    // a node producing empty blocks journals the first two transactions
+    config.chain_conf.max_gas_per_block = 0;
+    let srv = FuelService::new_node(config.clone()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+    client.submit(&transactions[0]).await.unwrap();
+    client.submit(&transactions[1]).await.unwrap();
+    srv.stop().await;
+    drop(srv);
+
+    // the node restoring them finds the id of the first one already used on chain
+    config.chain_conf.max_gas_per_block = 1_000_000;
+    let mut db = Database::default();
+    let colliding = &transactions[0];
+    Storage::<Bytes32, Transaction>::insert(&mut db, &colliding.id(), colliding).unwrap();
+    let srv = FuelService::from_database(db, config).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+    let is_pending = |tx: &Transaction| {
+        let id = format!("{:#x}", tx.id());
+        let client = &client;
+        async move { client.pending_transaction(&id).await.unwrap().is_some() }
+    };
+    for _ in 0..100 {
+        if is_pending(&transactions[1]).await {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    let result = client.submit(&transactions[2]).await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("Transaction id was already used"));
+    assert!(!is_pending(&transactions[0]).await);
+    assert!(is_pending(&transactions[1]).await);
+    assert!(is_pending(&transactions[2]).await);
+
+    // the next submission produces a block with the rest of them
+    client.submit(&transactions[3]).await.unwrap();
+    for tx in &transactions[1..] {
+        let status = client
+            .transaction_status(&format!("{:#x}", tx.id()))
+            .await
+            .unwrap();
+        assert!(matches!(status, TransactionStatus::Success { .. }));
+    }
+}
+
+#[tokio::test]
+async fn child_that_does_not_fit_the_block_stays_pending_after_its_parent() {
+    let mut rng = StdRng::seed_from_u64(2322);
+    let (parent_secret, child_secret) = (SecretKey::random(&mut rng), SecretKey::random(&mut rng));
+    let script = || Opcode::RET(REG_ONE).to_bytes().into_iter().collect_vec();
+    let parent = TransactionBuilder::script(script(), vec![])
+        .gas_limit(100)
+        .add_unsigned_coin_input(rng.gen(), &parent_secret, 1000, Default::default(), 0)
+        .add_output(Output::coin(
+            Address::from(*child_secret.public_key().hash()),
+            500,
+            Default::default(),
+        ))
+        .add_output(Output::change(rng.gen(), 0, Default::default()))
+        .finalize();
+    let child = TransactionBuilder::script(script(), vec![])
+        .gas_limit(100)
+        .add_unsigned_coin_input(
+            UtxoId::new(parent.id(), 0),
+            &child_secret,
+            500,
+            Default::default(),
+            0,
+        )
+        .add_output(Output::change(rng.gen(), 0, Default::default()))
+        .finalize();
+    // too big for any block, its submission only triggers block production
+    let trigger = TransactionBuilder::script(script(), vec![])
+        .gas_limit(1000)
+        .add_unsigned_coin_input(rng.gen(), &parent_secret, 1000, Default::default(), 0)
+        .add_output(Output::change(rng.gen(), 0, Default::default()))
+        .finalize();
+    let mut test_builder = TestSetupBuilder::new(2322);
+    test_builder.config_coin_inputs_from_transactions(&[&parent, &trigger]);
+    let journal = tempfile::tempdir().unwrap();
+    let mut config = test_builder.config();
+    config.txpool.journal_path = Some(journal.path().join("txpool.journal"));
+
+    // a node producing empty blocks journals both transactions
+    config.chain_conf.max_gas_per_block = 0;
+    let srv = FuelService::new_node(config.clone()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+    client.submit(&parent).await.unwrap();
+    client.submit(&child).await.unwrap();
+    srv.stop().await;
+    drop(srv);
+
+    // a node restoring them has only room for the parent
+    config.chain_conf.max_gas_per_block = parent.gas_limit();
+    let srv = FuelService::new_node(config).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+    let pending_count = || async {
+        client
+            .pending_transactions(PaginationRequest {
+                cursor: None,
+                results: 10,
+                direction: PageDirection::Forward,
+            })
+            .await
+            .unwrap()
+            .results
+            .len()
+    };
+    for _ in 0..100 {
+        if pending_count().await == 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(pending_count().await, 2);
+
+    client.submit(&trigger).await.unwrap();
+    let status = client
+        .transaction_status(&format!("{:#x}", parent.id()))
+        .await
+        .unwrap();
+    assert!(matches!(status, TransactionStatus::Success { .. }));
+    let status = client
+        .transaction_status(&format!("{:#x}", child.id()))
+        .await
+        .unwrap();
+    assert!(matches!(status, TransactionStatus::Submitted { .. }));
+}
+
 #[tokio::test]
 async fn submit_utxo_verified_tx_below_min_gas_price_fails() {
     // initialize transaction
@@ -0,0 +1,55 @@
+use fuel_core::{config::Config, service::FuelService};
+use fuel_core_interfaces::common::fuel_tx;
+use fuel_gql_client::client::{FuelClient, PageDirection, PaginationRequest};
+
+#[tokio::test]
+async fn txpool_is_empty_after_submitted_tx_is_executed() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let transaction = fuel_tx::Transaction::default();
+    let id = transaction.id();
+    client.submit(&transaction).await.unwrap();
+
+    let stats = client.txpool_stats().await.unwrap();
+    assert_eq!(stats.count.0, 0);
+    assert_eq!(stats.total_bytes.0, 0);
+    assert!(stats.min_gas_price.is_none());
+    assert!(stats.max_gas_price.is_none());
+
+    let pending = client
+        .pending_transaction(&format!("{:#x}", id))
+        .await
+        .unwrap();
+    assert!(pending.is_none());
+
+    let pending = client
+        .pending_transactions(PaginationRequest {
+            cursor: None,
+            results: 10,
+            direction: PageDirection::Forward,
+        })
+        .await
+        .unwrap();
+    assert!(pending.results.is_empty());
+    assert!(!pending.has_next_page);
+}
+
+#[tokio::test]
+async fn pending_transactions_reject_stale_cursor() {
+    let srv = FuelService::new_node(Config::local_node()).await.unwrap();
+    let client = FuelClient::from(srv.bound_address);
+
+    let transaction = fuel_tx::Transaction::default();
+    client.submit(&transaction).await.unwrap();
+
+    // the transaction has been included into a block and left the pool
+    let result = client
+        .pending_transactions(PaginationRequest {
+            cursor: Some(format!("{:#x}", transaction.id())),
+            results: 10,
+            direction: PageDirection::Forward,
+        })
+        .await;
+    assert!(result.is_err());
+}
@@ -1,22 +1,51 @@
+use fuel_core_interfaces::{common::fuel_tx::ConsensusParameters, model::ParameterUpgrade};
+use std::{path::PathBuf, time::Duration};
+
 #[derive(Debug, Clone)]
 pub struct Config {
     /// Maximum number of transactions inside the pool
     pub max_tx: usize,
+    /// Maximum number of transactions inside the pool that spend coins of a single owner
+    pub max_tx_per_owner: usize,
     /// max depth of connected UTXO excluding contracts
     pub max_depth: usize,
     /// The minimum allowed gas price
     pub min_gas_price: u64,
     /// The minimum allowed byte price
     pub min_byte_price: u64,
+    /// Maximum time a transaction can stay inside the pool before it is pruned
+    pub transaction_ttl: Duration,
+    /// How often the pool is checked for expired transactions
+    pub ttl_check_interval: Duration,
+    /// Path of the journal used to restore pending transactions after restart.
+    /// Transactions are kept only in memory if not set.
+    pub journal_path: Option<PathBuf>,
+    /// Verify signatures, coin state, fees and consensus parameter limits on insertion,
+    /// the same way the executor does when utxo validation is enabled.
+    pub utxo_validation: bool,
+    /// Will reject any transactions with predicates if set to false.
+    pub predicates: bool,
+    /// Consensus parameters that transactions are validated against.
+    pub transaction_parameters: ConsensusParameters,
+    /// Changes to `transaction_parameters` and `predicates` scheduled by block height.
+    pub upgrades: Vec<ParameterUpgrade>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             max_tx: 4064,
+            max_tx_per_owner: 64,
             max_depth: 10,
             min_gas_price: 0,
             min_byte_price: 0,
+            transaction_ttl: Duration::from_secs(60 * 5),
+            ttl_check_interval: Duration::from_secs(60),
+            journal_path: None,
+            utxo_validation: false,
+            predicates: false,
+            transaction_parameters: ConsensusParameters::default(),
+            upgrades: vec![],
         }
     }
 }
@@ -1,2 +1,4 @@
 pub mod dependency;
+pub mod owners;
 pub mod price_sort;
+pub mod time_sort;
@@ -84,37 +84,43 @@ impl Dependency {
             });
             // for every input check if tx_id is inside seen. if not, check coins/contract map.
             if let Some(parent_tx) = parent_tx {
-                for input in parent_tx.inputs() {
-                    // if found and depth is not zero add it to `check`.
-                    match input {
-                        Input::CoinSigned { utxo_id, .. }
-                        | Input::CoinPredicate { utxo_id, .. } => {
-                            let state = self
-                                .coins
-                                .get(utxo_id)
-                                .expect("to find coin inside spend tx");
-                            if !state.is_in_database() {
-                                check.push(*utxo_id.tx_id());
-                            }
-                        }
-                        Input::Contract { contract_id, .. } => {
-                            let state = self
-                                .contracts
-                                .get(contract_id)
-                                .expect("Expect to find contract in dependency");
-
-                            if !state.is_in_database() {
-                                let origin = state
-                                    .origin
-                                    .as_ref()
-                                    .expect("contract origin to be present");
-                                check.push(*origin.tx_id());
-                            }
-                        }
+                check.extend(self.parents(&parent_tx));
+            }
+        }
+    }
+
+    /// Return ids of transactions inside txpool whose outputs are used as inputs of `tx`.
+    pub(crate) fn parents(&self, tx: &Transaction) -> Vec<TxId> {
+        let mut parents = Vec::new();
+        for input in tx.inputs() {
+            // if found and depth is not zero add it to parents.
+            match input {
+                Input::CoinSigned { utxo_id, .. } | Input::CoinPredicate { utxo_id, .. } => {
+                    let state = self
+                        .coins
+                        .get(utxo_id)
+                        .expect("to find coin inside spend tx");
+                    if !state.is_in_database() {
+                        parents.push(*utxo_id.tx_id());
+                    }
+                }
+                Input::Contract { contract_id, .. } => {
+                    let state = self
+                        .contracts
+                        .get(contract_id)
+                        .expect("Expect to find contract in dependency");
+
+                    if !state.is_in_database() {
+                        let origin = state
+                            .origin
+                            .as_ref()
+                            .expect("contract origin to be present");
+                        parents.push(*origin.tx_id());
                     }
                 }
             }
         }
+        parents
     }
 
     fn check_if_coin_input_can_spend_db_coin(coin: &Coin, input: &Input) -> anyhow::Result<()> {
@@ -463,6 +469,97 @@ impl Dependency {
         Ok(removed_tx)
     }
 
+    /// Ids of the transactions inside txpool that inserting `tx` would replace: the ones it
+    /// collides with, together with every transaction depending on them.
+    pub(crate) fn replaced_by(
+        &self,
+        txs: &HashMap<TxId, TxInfo>,
+        db: &dyn TxPoolDb,
+        tx: &ArcTx,
+    ) -> anyhow::Result<HashSet<TxId>> {
+        let (_, _, _, collided) = self.check_for_colision(txs, db, tx)?;
+        let mut replaced = HashSet::new();
+        let mut check = collided;
+        while let Some(tx_id) = check.pop() {
+            if replaced.insert(tx_id) {
+                let tx = txs.get(&tx_id).expect("Tx should be present in txs").tx();
+                check.extend(self.children(tx));
+            }
+        }
+        Ok(replaced)
+    }
+
+    /// Return ids of transactions inside txpool that use outputs of `tx` as their inputs.
+    pub(crate) fn children(&self, tx: &Transaction) -> Vec<TxId> {
+        let mut children = Vec::new();
+        for (index, output) in tx.outputs().iter().enumerate() {
+            match output {
+                Output::Coin { .. } | Output::Change { .. } | Output::Variable { .. } => {
+                    let utxo = UtxoId::new(tx.id(), index as u8);
+                    if let Some(spend_by) = self.coins.get(&utxo).and_then(|c| c.is_spend_by) {
+                        children.push(spend_by);
+                    }
+                }
+                Output::ContractCreated { contract_id, .. } => {
+                    if let Some(contract) = self.contracts.get(contract_id) {
+                        children.extend(contract.used_by.iter().copied());
+                    }
+                }
+                Output::Withdrawal { .. } | Output::Contract { .. } => {}
+            }
+        }
+        children
+    }
+
+    /// Remove a tx that was executed in a block. Its outputs are on chain now, so the txs
+    /// spending them stay and depend on the database instead.
+    pub(crate) fn remove_committed(&mut self, tx: &Transaction) {
+        for (index, output) in tx.outputs().iter().enumerate() {
+            match output {
+                Output::Withdrawal { .. } | Output::Contract { .. } => {}
+                Output::Coin { .. } | Output::Change { .. } | Output::Variable { .. } => {
+                    let utxo = UtxoId::new(tx.id(), index as u8);
+                    match self.coins.get_mut(&utxo) {
+                        Some(state) if state.is_spend_by.is_some() => state.depth = 0,
+                        Some(_) => {
+                            self.coins.remove(&utxo);
+                        }
+                        None => warn!("expected a coin state to be associated with {:?}", &utxo),
+                    }
+                }
+                Output::ContractCreated { contract_id, .. } => {
+                    if let Some(state) = self.contracts.get_mut(contract_id) {
+                        if state.used_by.is_empty() {
+                            self.contracts.remove(contract_id);
+                        } else {
+                            state.depth = 0;
+                            state.origin = None;
+                        }
+                    }
+                }
+            }
+        }
+
+        // the inputs are spent on chain, nothing else in the pool can use them.
+        for input in tx.inputs() {
+            match input {
+                Input::CoinSigned { utxo_id, .. } | Input::CoinPredicate { utxo_id, .. } => {
+                    self.coins.remove(utxo_id);
+                }
+                Input::Contract { contract_id, .. } => {
+                    let mut rem_contract = false;
+                    if let Some(state) = self.contracts.get_mut(contract_id) {
+                        state.used_by.remove(&tx.id());
+                        rem_contract = state.used_by.is_empty() && state.is_in_database();
+                    }
+                    if rem_contract {
+                        self.contracts.remove(contract_id);
+                    }
+                }
+            }
+        }
+    }
+
     /// Remove all pending txs that depend on the outputs of the provided tx
     pub(crate) fn recursively_remove_all_dependencies<'a>(
         &'a mut self,
@@ -0,0 +1,50 @@
+use crate::types::*;
+use fuel_core_interfaces::{common::fuel_tx::Input, model::ArcTx};
+use std::collections::{HashMap, HashSet};
+
+/// Tracks transactions inside the pool by owners of their coin inputs.
+#[derive(Debug, Default, Clone)]
+pub struct Owners {
+    /// all transaction ids that spend coins of an owner
+    pub txs: HashMap<Address, HashSet<TxId>>,
+}
+
+impl Owners {
+    /// Return all distinct owners of coin inputs of the transaction.
+    pub fn owners_of(tx: &Transaction) -> HashSet<Address> {
+        tx.inputs()
+            .iter()
+            .filter_map(|input| match input {
+                Input::CoinSigned { owner, .. } | Input::CoinPredicate { owner, .. } => {
+                    Some(*owner)
+                }
+                Input::Contract { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Number of transactions in the pool that spend coins of this owner, leaving out `excluded`.
+    pub fn count_excluding(&self, owner: &Address, excluded: &HashSet<TxId>) -> usize {
+        self.txs
+            .get(owner)
+            .map(|txs| txs.difference(excluded).count())
+            .unwrap_or_default()
+    }
+
+    pub fn insert(&mut self, tx: &ArcTx) {
+        for owner in Self::owners_of(tx) {
+            self.txs.entry(owner).or_default().insert(tx.id());
+        }
+    }
+
+    pub fn remove(&mut self, tx: &ArcTx) {
+        for owner in Self::owners_of(tx) {
+            if let Some(txs) = self.txs.get_mut(&owner) {
+                txs.remove(&tx.id());
+                if txs.is_empty() {
+                    self.txs.remove(&owner);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::types::*;
+use chrono::{DateTime, Utc};
+use fuel_core_interfaces::model::{ArcTx, TxInfo};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Clone)]
+pub struct TimeSort {
+    /// all transactions sorted by time of submission, oldest first
+    pub sort: BTreeMap<TimeSortKey, ArcTx>,
+}
+
+impl TimeSort {
+    pub fn remove(&mut self, info: &TxInfo) {
+        self.sort.remove(&TimeSortKey::new(info));
+    }
+
+    pub fn insert(&mut self, info: &TxInfo) {
+        self.sort.insert(TimeSortKey::new(info), info.tx().clone());
+    }
+
+    /// Return ids of all transactions submitted before `time`, oldest first.
+    pub fn submitted_before(&self, time: DateTime<Utc>) -> Vec<TxId> {
+        self.sort
+            .keys()
+            .take_while(|key| key.time < time)
+            .map(|key| key.tx_id)
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeSortKey {
+    time: DateTime<Utc>,
+    tx_id: TxId,
+}
+
+impl TimeSortKey {
+    pub fn new(info: &TxInfo) -> Self {
+        Self {
+            time: info.submited_time(),
+            tx_id: info.id(),
+        }
+    }
+}
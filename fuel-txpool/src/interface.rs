@@ -1,17 +1,25 @@
-use crate::{types::*, Config, TxPool as TxPoolImpl};
+use crate::{journal::Journal, types::*, Config, TxPool as TxPoolImpl};
 use fuel_core_interfaces::block_importer::ImportBlockBroadcast;
+use fuel_core_interfaces::common::fuel_types::Word;
 use fuel_core_interfaces::model::{ArcTx, TxInfo};
 use fuel_core_interfaces::txpool::{Error, TxPoolDb, TxPoolMpsc, TxStatus, TxStatusBroadcast};
+use parking_lot::Mutex;
 use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{broadcast, mpsc, RwLock};
+use tracing::{info, warn};
 
 /// Acts as a internal interface between transaction pool Service and implementation inside TxPool.
 pub struct Interface {
     txpool: RwLock<TxPoolImpl>,
     broadcast: broadcast::Sender<TxStatusBroadcast>,
     db: Box<dyn TxPoolDb>,
+    ttl_check_interval: Duration,
+    journal: Option<Journal>,
+    /// transactions read from journal that are waiting to be inserted back into the pool.
+    restored: Mutex<Vec<ArcTx>>,
 }
 
 impl Interface {
@@ -20,6 +28,8 @@ impl Interface {
         mut new_block: broadcast::Receiver<ImportBlockBroadcast>,
         mut receiver: mpsc::Receiver<TxPoolMpsc>,
     ) -> mpsc::Receiver<TxPoolMpsc> {
+        self.restore().await;
+        let mut prune_interval = tokio::time::interval(self.ttl_check_interval);
         loop {
             tokio::select! {
                 event = receiver.recv() => {
@@ -34,9 +44,15 @@ impl Interface {
                         TxPoolMpsc::Includable { response } => {
                             let _ = response.send(interface.includable().await);
                         }
+                        TxPoolMpsc::IncludableWithin { max_gas, max_bytes, response } => {
+                            let _ = response.send(interface.includable_within(max_gas, max_bytes).await);
+                        }
                         TxPoolMpsc::Insert { txs, response } => {
                             let _ = response.send(interface.insert(txs).await);
                         }
+                        TxPoolMpsc::Pending { response } => {
+                            let _ = response.send(interface.pending().await);
+                        }
                         TxPoolMpsc::Find { ids, response } => {
                             let _ = response.send(interface.find(&ids).await);
                         }
@@ -49,8 +65,11 @@ impl Interface {
                         TxPoolMpsc::FilterByNegative { ids, response } => {
                             let _ = response.send(interface.filter_by_negative(&ids).await);
                         }
-                        TxPoolMpsc::Remove { ids } => {
-                            interface.remove(&ids).await;
+                        TxPoolMpsc::Remove { ids, response } => {
+                            let _ = response.send(interface.remove(&ids).await);
+                        }
+                        TxPoolMpsc::RemoveCommitted { ids, response } => {
+                            let _ = response.send(interface.remove_committed(&ids).await);
                         }
                         TxPoolMpsc::Stop => {}
                     }});
@@ -61,6 +80,12 @@ impl Interface {
                         interface.block_update().await;
                     });
                 }
+                _ = prune_interval.tick() => {
+                    let interface = self.clone();
+                    tokio::spawn( async move {
+                        interface.prune_old_txs().await;
+                    });
+                }
             }
         }
         receiver
@@ -70,11 +95,81 @@ impl Interface {
         db: Box<dyn TxPoolDb>,
         broadcast: broadcast::Sender<TxStatusBroadcast>,
         config: Config,
-    ) -> Self {
-        Self {
+    ) -> anyhow::Result<Self> {
+        let (journal, restored) = match &config.journal_path {
+            Some(path) => {
+                let (journal, restored) = Journal::open(path)?;
+                (Some(journal), restored)
+            }
+            None => (None, Vec::new()),
+        };
+        Ok(Self {
+            ttl_check_interval: config.ttl_check_interval,
             txpool: RwLock::new(TxPoolImpl::new(config)),
             broadcast,
             db,
+            journal,
+            restored: Mutex::new(restored.into_iter().map(Arc::new).collect()),
+        })
+    }
+
+    /// insert transactions from journal back into the pool. Transactions that are not valid
+    /// against current state anymore are dropped. The journal is compacted afterwards, which
+    /// replaces the old records only once the restored transactions are journaled again.
+    async fn restore(&self) {
+        let restored = std::mem::take(&mut *self.restored.lock());
+        if !restored.is_empty() {
+            let total = restored.len();
+            let inserted = self
+                .insert(restored)
+                .await
+                .iter()
+                .filter(|res| res.is_ok())
+                .count();
+            info!(
+                "Restored {} of {} transactions from txpool journal",
+                inserted, total
+            );
+        }
+        self.compact_journal().await;
+    }
+
+    /// record changes of the pool inside journal, if journal is enabled. Must be called while
+    /// the pool is still locked for the change, so records are written in the order of changes.
+    fn journal(&self, inserted: Option<&ArcTx>, removed: &[ArcTx]) {
+        if let Some(journal) = &self.journal {
+            for tx in removed {
+                journal.remove(&tx.id());
+            }
+            if let Some(tx) = inserted {
+                journal.insert(tx);
+            }
+        }
+    }
+
+    /// Compact the journal if enough transactions were removed since the last compaction.
+    async fn compact_journal_if_needed(&self) {
+        if let Some(journal) = &self.journal {
+            if journal.needs_compaction() {
+                self.compact_journal().await;
+            }
+        }
+    }
+
+    /// Rewrite the journal with the transactions currently inside the pool.
+    async fn compact_journal(&self) {
+        if let Some(journal) = &self.journal {
+            // the pool stays locked until the compaction is queued, so the records of later
+            // changes are written after it
+            let pool = self.txpool.read().await;
+            let mut txs: Vec<&TxInfo> = pool.txs().values().collect();
+            // parents are always inserted before their children
+            txs.sort_by_key(|info| info.submited_time());
+            journal.compact(
+                txs.into_iter()
+                    .map(|info| info.tx().as_ref().clone())
+                    .collect(),
+            );
         }
     }
 
@@ -87,8 +182,13 @@ impl Interface {
         let mut res = Vec::new();
         for tx in txs.iter() {
             let mut pool = self.txpool.write().await;
-            res.push(pool.insert(tx.clone(), self.db.as_ref()).await)
+            let ret = pool.insert(tx.clone(), self.db.as_ref()).await;
+            if let Ok(removed) = &ret {
+                self.journal(Some(tx), removed);
+            }
+            res.push(ret)
         }
+        self.compact_journal_if_needed().await;
         // announce to subscribers
         for (ret, tx) in res.iter().zip(txs.into_iter()) {
             match ret {
@@ -124,6 +224,10 @@ impl Interface {
         res
     }
 
+    async fn pending(&self) -> Vec<TxInfo> {
+        self.txpool.read().await.pending()
+    }
+
     async fn find_one(&self, hash: &TxId) -> Option<TxInfo> {
         self.txpool.read().await.txs().get(hash).cloned()
     }
@@ -166,23 +270,98 @@ impl Interface {
         pool.sorted_includable()
     }
 
-    /// When block is updated we need to receive all spend outputs and remove them from txpool
-    async fn block_update(&self /*spend_outputs: [Input], added_outputs: [AddedOutputs]*/) {
-        self.txpool.write().await.block_update()
+    /// Return transactions for the next block that fit inside `max_gas` and `max_bytes`.
+    async fn includable_within(&self, max_gas: Word, max_bytes: usize) -> Vec<ArcTx> {
+        let pool = self.txpool.read().await;
+        pool.includable_within(max_gas, max_bytes)
+    }
+
+    /// remove all transactions that an upgrade activating at the next block invalidates,
+    /// together with their dependents.
+    async fn block_update(&self) {
+        let removed = {
+            let mut pool = self.txpool.write().await;
+            match pool.block_update(self.db.as_ref()) {
+                Ok(removed) => {
+                    self.journal(None, &removed);
+                    removed
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to check the txpool against parameter upgrades: {}",
+                        e
+                    );
+                    return;
+                }
+            }
+        };
+        self.compact_journal_if_needed().await;
+        for tx in removed {
+            let _ = self.broadcast.send(TxStatusBroadcast {
+                tx,
+                status: TxStatus::SqueezedOut {
+                    reason: Error::InvalidatedByUpgrade,
+                },
+            });
+        }
     }
 
     /// remove transaction from pool needed on user demand. Low priority
-    async fn remove(&self, tx_ids: &[TxId]) {
+    async fn remove(&self, tx_ids: &[TxId]) -> Vec<ArcTx> {
         let mut removed = Vec::new();
         for tx_id in tx_ids {
-            let rem = { self.txpool.write().await.remove_by_tx_id(tx_id) };
+            let mut pool = self.txpool.write().await;
+            let rem = pool.remove_by_tx_id(tx_id);
+            self.journal(None, &rem);
             removed.extend(rem.into_iter());
         }
+        self.compact_journal_if_needed().await;
+        for tx in removed.iter() {
+            let _ = self.broadcast.send(TxStatusBroadcast {
+                tx: tx.clone(),
+                status: TxStatus::SqueezedOut {
+                    reason: Error::Removed,
+                },
+            });
+        }
+        removed
+    }
+
+    /// remove transactions that were executed in a block. Their dependents stay in the pool.
+    async fn remove_committed(&self, tx_ids: &[TxId]) -> Vec<ArcTx> {
+        let removed = {
+            let mut pool = self.txpool.write().await;
+            let removed: Vec<ArcTx> = tx_ids
+                .iter()
+                .filter_map(|tx_id| pool.remove_committed(tx_id))
+                .collect();
+            self.journal(None, &removed);
+            removed
+        };
+        self.compact_journal_if_needed().await;
+        for tx in removed.iter() {
+            let _ = self.broadcast.send(TxStatusBroadcast {
+                tx: tx.clone(),
+                status: TxStatus::Executed,
+            });
+        }
+        removed
+    }
+
+    /// remove all transactions that outlived their time to live, together with their dependents.
+    async fn prune_old_txs(&self) {
+        let removed = {
+            let mut pool = self.txpool.write().await;
+            let removed = pool.prune_old_txs();
+            self.journal(None, &removed);
+            removed
+        };
+        self.compact_journal_if_needed().await;
         for tx in removed {
             let _ = self.broadcast.send(TxStatusBroadcast {
                 tx,
                 status: TxStatus::SqueezedOut {
-                    reason: Error::Removed,
+                    reason: Error::Expired,
                 },
             });
         }
@@ -0,0 +1,339 @@
+use crate::types::*;
+use fuel_core_interfaces::common::fuel_types::bytes::{Deserializable, SerializableVec};
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread::{self, JoinHandle},
+};
+use tracing::warn;
+
+const INSERT: u8 = 0;
+const REMOVE: u8 = 1;
+
+/// Number of removals after which the journal is rewritten with only the live transactions.
+pub const COMPACTION_THRESHOLD: usize = 1024;
+
+enum Command {
+    Append(Vec<u8>),
+    Compact(Vec<Transaction>),
+}
+
+/// Append only log of txpool inserts and removals, used to recover pending
+/// transactions after the node restarts.
+///
+/// Record layout is one tag byte followed by:
+/// * insert: 8 byte big endian length and serialized transaction.
+/// * remove: 32 byte transaction id.
+///
+/// Records are written in order by a dedicated thread, so recording never blocks the caller.
+/// The file is only replaced by [`Journal::compact`], which writes the live transactions to a
+/// temporary file and renames it over the journal, so records are never lost on a crash.
+#[derive(Debug)]
+pub struct Journal {
+    sender: Option<mpsc::Sender<Command>>,
+    writer: Option<JoinHandle<()>>,
+    removals: Arc<AtomicUsize>,
+}
+
+impl Journal {
+    /// Open journal at `path` and return transactions that were inserted and not removed,
+    /// in order of their insertion. The journal is kept as it is, new records are appended
+    /// to it until it is compacted.
+    pub fn open(path: &Path) -> io::Result<(Self, Vec<Transaction>)> {
+        let txs = if path.exists() {
+            let mut bytes = Vec::new();
+            File::open(path)?.read_to_end(&mut bytes)?;
+            let (txs, valid_len) = Self::replay(&bytes);
+            // drop the malformed tail, so new records aren't appended after it
+            if valid_len < bytes.len() {
+                OpenOptions::new()
+                    .write(true)
+                    .open(path)?
+                    .set_len(valid_len as u64)?;
+            }
+            txs
+        } else {
+            Vec::new()
+        };
+
+        let file = Self::append_to(path)?;
+        let (sender, receiver) = mpsc::channel();
+        let path = path.to_path_buf();
+        let writer = thread::Builder::new()
+            .name("txpool-journal".to_string())
+            .spawn(move || Self::write_records(file, path, receiver))?;
+
+        Ok((
+            Self {
+                sender: Some(sender),
+                writer: Some(writer),
+                removals: Default::default(),
+            },
+            txs,
+        ))
+    }
+
+    pub fn insert(&self, tx: &Transaction) {
+        self.send(Command::Append(Self::insert_record(tx.clone())));
+    }
+
+    pub fn remove(&self, tx_id: &TxId) {
+        self.removals.fetch_add(1, Ordering::Relaxed);
+        self.send(Command::Append(Self::remove_record(tx_id)));
+    }
+
+    /// Replace the journal with the insertion of `txs`, the live transactions of the pool,
+    /// ordered so parents come before their children.
+    pub fn compact(&self, txs: Vec<Transaction>) {
+        self.removals.store(0, Ordering::Relaxed);
+        self.send(Command::Compact(txs));
+    }
+
+    /// Whether enough transactions were removed since the last compaction to compact again.
+    pub fn needs_compaction(&self) -> bool {
+        self.removals.load(Ordering::Relaxed) >= COMPACTION_THRESHOLD
+    }
+
+    fn send(&self, command: Command) {
+        let sent = self
+            .sender
+            .as_ref()
+            .map(|sender| sender.send(command).is_ok())
+            .unwrap_or_default();
+        if !sent {
+            warn!("TxPool journal writer has stopped, record is lost");
+        }
+    }
+
+    fn append_to(path: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    fn write_records(mut file: File, path: PathBuf, receiver: mpsc::Receiver<Command>) {
+        for command in receiver {
+            let result = match command {
+                Command::Append(record) => file.write_all(&record),
+                Command::Compact(txs) => Self::rewrite(&path, txs).map(|new| file = new),
+            };
+            if let Err(e) = result {
+                warn!("Failed to write txpool journal: {:?}", e);
+            }
+        }
+    }
+
+    /// Write `txs` to a temporary file next to the journal and move it over the journal,
+    /// returning the new journal to append to.
+    fn rewrite(path: &Path, txs: Vec<Transaction>) -> io::Result<File> {
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let mut tmp = File::create(&tmp_path)?;
+        for tx in txs {
+            tmp.write_all(&Self::insert_record(tx))?;
+        }
+        tmp.sync_all()?;
+        fs::rename(&tmp_path, path)?;
+        Self::append_to(path)
+    }
+
+    fn insert_record(mut tx: Transaction) -> Vec<u8> {
+        let bytes = tx.to_bytes();
+        let mut record = Vec::with_capacity(1 + 8 + bytes.len());
+        record.push(INSERT);
+        record.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+        record.extend_from_slice(&bytes);
+        record
+    }
+
+    fn remove_record(tx_id: &TxId) -> Vec<u8> {
+        let mut record = Vec::with_capacity(1 + TxId::LEN);
+        record.push(REMOVE);
+        record.extend_from_slice(tx_id.as_ref());
+        record
+    }
+
+    /// Apply all records and return live transactions sorted by their latest insertion,
+    /// together with the length of the well formed records. Reading stops at the first
+    /// malformed record, which can be left by a crash mid-write.
+    fn replay(journal: &[u8]) -> (Vec<Transaction>, usize) {
+        let mut bytes = journal;
+        let mut live: HashMap<TxId, (usize, Transaction)> = HashMap::new();
+        let mut seq = 0;
+        while let Some((tag, rest)) = bytes.split_first() {
+            match *tag {
+                INSERT if rest.len() >= 8 => {
+                    let (len, rest) = rest.split_at(8);
+                    let len = u64::from_be_bytes(len.try_into().expect("length is 8 bytes"));
+                    if (rest.len() as u64) < len {
+                        warn!("TxPool journal ends with incomplete transaction");
+                        break;
+                    }
+                    let (tx, rest) = rest.split_at(len as usize);
+                    match Transaction::from_bytes(tx) {
+                        Ok(mut tx) => {
+                            tx.precompute_metadata();
+                            live.insert(tx.id(), (seq, tx));
+                        }
+                        Err(e) => {
+                            warn!("TxPool journal contains malformed transaction: {:?}", e);
+                            break;
+                        }
+                    }
+                    bytes = rest;
+                }
+                REMOVE if rest.len() >= TxId::LEN => {
+                    let (tx_id, rest) = rest.split_at(TxId::LEN);
+                    let tx_id = TxId::try_from(tx_id).expect("tx id is 32 bytes");
+                    live.remove(&tx_id);
+                    bytes = rest;
+                }
+                _ => {
+                    warn!("TxPool journal contains malformed record");
+                    break;
+                }
+            }
+            seq += 1;
+        }
+
+        let mut txs: Vec<(usize, Transaction)> = live.into_values().collect();
+        txs.sort_by_key(|(seq, _)| *seq);
+        (
+            txs.into_iter().map(|(_, tx)| tx).collect(),
+            journal.len() - bytes.len(),
+        )
+    }
+}
+
+impl Drop for Journal {
+    /// Wait for the records that are still queued to be written.
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script_tx(gas_price: u64) -> Transaction {
+        let mut tx = Transaction::script(
+            gas_price,
+            1_000_000,
+            0,
+            0,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+        );
+        tx.precompute_metadata();
+        tx
+    }
+
+    #[test]
+    fn reopened_journal_returns_not_removed_txs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("txpool.journal");
+
+        let tx1 = script_tx(1);
+        let tx2 = script_tx(2);
+        let tx4 = script_tx(4);
+
+        let (journal, txs) = Journal::open(&path).unwrap();
+        assert!(txs.is_empty(), "New journal should be empty:{:?}", txs);
+        journal.insert(&tx1);
+        journal.insert(&tx2);
+        journal.insert(&tx4);
+        journal.remove(&tx2.id());
+        drop(journal);
+
+        let (_, txs) = Journal::open(&path).unwrap();
+        let ids: Vec<TxId> = txs.iter().map(|tx| tx.id()).collect();
+        assert_eq!(
+            ids,
+            vec![tx1.id(), tx4.id()],
+            "Tx1 and Tx4 should be restored"
+        );
+
+        // journal is kept until it is compacted, so a crash before restored
+        // transactions are journaled again doesn't lose them
+        let (journal, txs) = Journal::open(&path).unwrap();
+        assert_eq!(txs.len(), 2, "Journal should be kept:{:?}", txs);
+        journal.compact(vec![tx4.clone()]);
+        drop(journal);
+
+        let (_, txs) = Journal::open(&path).unwrap();
+        let ids: Vec<TxId> = txs.iter().map(|tx| tx.id()).collect();
+        assert_eq!(
+            ids,
+            vec![tx4.id()],
+            "Only Tx4 should be left after compaction"
+        );
+    }
+
+    #[test]
+    fn compaction_is_needed_after_threshold_removals() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("txpool.journal");
+
+        let (journal, _) = Journal::open(&path).unwrap();
+        for _ in 0..COMPACTION_THRESHOLD - 1 {
+            journal.remove(&TxId::default());
+        }
+        assert!(!journal.needs_compaction());
+        journal.remove(&TxId::default());
+        assert!(journal.needs_compaction());
+        journal.compact(vec![]);
+        assert!(!journal.needs_compaction());
+    }
+
+    #[test]
+    fn incomplete_record_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("txpool.journal");
+
+        let tx1 = script_tx(1);
+        let tx4 = script_tx(4);
+
+        let (journal, _) = Journal::open(&path).unwrap();
+        journal.insert(&tx1);
+        journal.insert(&tx4);
+        drop(journal);
+
+        // simulate crash in the middle of the last write
+        let len = std::fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(len - 1)
+            .unwrap();
+
+        let (journal, txs) = Journal::open(&path).unwrap();
+        let ids: Vec<TxId> = txs.iter().map(|tx| tx.id()).collect();
+        assert_eq!(ids, vec![tx1.id()], "Only Tx1 should be restored");
+
+        // records appended after the incomplete one are read again
+        journal.insert(&tx4);
+        drop(journal);
+        let (_, txs) = Journal::open(&path).unwrap();
+        let ids: Vec<TxId> = txs.iter().map(|tx| tx.id()).collect();
+        assert_eq!(
+            ids,
+            vec![tx1.id(), tx4.id()],
+            "Tx1 and Tx4 should be restored"
+        );
+    }
+}
@@ -3,6 +3,7 @@ mod containers;
 pub use fuel_core_interfaces::txpool::Error;
 
 mod interface;
+mod journal;
 pub mod service;
 pub mod txpool;
 pub mod types;
@@ -19,7 +19,7 @@ impl Service {
         let (sender, receiver) = mpsc::channel(100);
         let (broadcast, _receiver) = broadcast::channel(100);
         Ok(Self {
-            interface: Arc::new(Interface::new(db, broadcast.clone(), config)),
+            interface: Arc::new(Interface::new(db, broadcast.clone(), config)?),
             sender: Sender::new(sender),
             broadcast,
             join: Mutex::new(None),
@@ -240,12 +240,12 @@ pub mod tests {
         );
 
         // remove them
-        let _ = service
+        let removed = service
             .sender()
-            .send(TxPoolMpsc::Remove {
-                ids: vec![tx1_hash, tx2_hash],
-            })
-            .await;
+            .remove(vec![tx1_hash, tx2_hash])
+            .await
+            .unwrap();
+        assert_eq!(removed.len(), 2, "Should remove both txs:{:?}", removed);
 
         assert_eq!(
             tokio::time::timeout(std::time::Duration::from_secs(2), subscribe.recv()).await,
@@ -269,4 +269,48 @@ pub mod tests {
             "Second removed should be tx2"
         );
     }
+
+    #[tokio::test]
+    async fn expired_tx_removal_subscription() {
+        let config = Config {
+            transaction_ttl: std::time::Duration::from_millis(100),
+            ttl_check_interval: std::time::Duration::from_millis(50),
+            ..Config::default()
+        };
+        let db = Box::new(DummyDb::filled());
+        let (_bs, br) = broadcast::channel(10);
+
+        let tx1_hash = *TX_ID1;
+        let tx1 = Arc::new(DummyDb::dummy_tx(tx1_hash));
+
+        let service = Service::new(db, config).unwrap();
+        service.start(br).await;
+        let mut subscribe = service.subscribe_ch();
+
+        let out = service.sender().insert(vec![tx1.clone()]).await.unwrap();
+        assert!(out[0].is_ok(), "Tx1 should be OK, got err:{:?}", out);
+        assert_eq!(
+            subscribe.try_recv(),
+            Ok(TxStatusBroadcast {
+                tx: tx1.clone(),
+                status: TxStatus::Submitted,
+            }),
+            "Tx1 should be submitted"
+        );
+
+        assert_eq!(
+            tokio::time::timeout(std::time::Duration::from_secs(2), subscribe.recv()).await,
+            Ok(Ok(TxStatusBroadcast {
+                tx: tx1,
+                status: TxStatus::SqueezedOut {
+                    reason: TxpoolError::Expired
+                }
+            })),
+            "Tx1 should expire"
+        );
+
+        let out = service.sender().find_one(tx1_hash).await.unwrap();
+        assert!(out.is_none(), "Tx1 should not be found:{:?}", out);
+        service.stop().await.unwrap().await.unwrap();
+    }
 }
@@ -1,20 +1,32 @@
 use crate::{
-    containers::{dependency::Dependency, price_sort::PriceSort},
+    containers::{
+        dependency::Dependency, owners::Owners, price_sort::PriceSort, time_sort::TimeSort,
+    },
     types::*,
     Config, Error,
 };
+use chrono::Utc;
 use fuel_core_interfaces::{
-    model::{ArcTx, TxInfo},
+    common::{fuel_tx::Input, fuel_types::Word},
+    model::{ArcTx, BlockHeight, ParameterUpgrade, TxInfo},
     txpool::TxPoolDb,
+    validation::TransactionValidator,
+};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
 };
-use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub struct TxPool {
     by_hash: HashMap<TxId, TxInfo>,
     by_gas_price: PriceSort,
+    by_time: TimeSort,
+    by_owner: Owners,
     by_dependency: Dependency,
     config: Config,
+    /// The height of the next block when the pool was last checked against the upgrades.
+    upgrades_checked_at: Option<BlockHeight>,
 }
 
 impl TxPool {
@@ -23,8 +35,11 @@ impl TxPool {
         Self {
             by_hash: HashMap::new(),
             by_gas_price: PriceSort::default(),
+            by_time: TimeSort::default(),
+            by_owner: Owners::default(),
             by_dependency: Dependency::new(max_depth),
             config,
+            upgrades_checked_at: None,
         }
     }
     pub fn txs(&self) -> &HashMap<TxId, TxInfo> {
@@ -50,6 +65,14 @@ impl TxPool {
             return Err(Error::NotInsertedTxKnown.into());
         }
 
+        // verify the transaction the same way the executor would
+        self.verify_tx_validity(&tx, db)?;
+
+        // verify that owners of coin inputs didn't hit their limit, not counting the
+        // transactions this one replaces
+        let replaced = self.by_dependency.replaced_by(&self.by_hash, db, &tx)?;
+        self.verify_tx_owner_limit(&tx, &replaced)?;
+
         let mut max_limit_hit = false;
         // check if we are hiting limit of pool
         if self.by_hash.len() >= self.config.max_tx {
@@ -62,8 +85,11 @@ impl TxPool {
         }
         // check and insert dependency
         let rem = self.by_dependency.insert(&self.by_hash, db, &tx).await?;
-        self.by_hash.insert(tx.id(), TxInfo::new(tx.clone()));
+        let info = TxInfo::new(tx.clone());
+        self.by_time.insert(&info);
+        self.by_hash.insert(tx.id(), info);
         self.by_gas_price.insert(&tx);
+        self.by_owner.insert(&tx);
 
         // if some transaction were removed so we dont need to check limit
         if rem.is_empty() {
@@ -75,12 +101,10 @@ impl TxPool {
             }
             Ok(Vec::new())
         } else {
-            // remove ret from by_hash and from by_price
+            // remove ret from by_hash and from other sorted collections
             for rem in rem.iter() {
-                self.by_hash
-                    .remove(&rem.id())
+                self.remove_from_collections(&rem.id())
                     .expect("Expect to hash of tx to be present");
-                self.by_gas_price.remove(rem);
             }
 
             Ok(rem)
@@ -97,8 +121,133 @@ impl TxPool {
             .collect()
     }
 
-    // When block is updated we need to receive all spend outputs and remove them from txpool
-    pub fn block_update(&mut self /*spend_outputs: [Input], added_outputs: [AddedOutputs]*/) {}
+    /// Return all transactions together with their submit time, sorted by gas price.
+    pub fn pending(&self) -> Vec<TxInfo> {
+        self.by_gas_price
+            .sort
+            .iter()
+            .rev()
+            .filter_map(|(_, tx)| self.by_hash.get(&tx.id()).cloned())
+            .collect()
+    }
+
+    /// Return transactions for the next block that fit inside `max_gas` and `max_bytes`.
+    /// Transaction is selected only together with all of its parents from the pool, and
+    /// parents are always returned before their children. Groups of transactions
+    /// are selected greedily by the highest fee per unit of gas, so a low priced parent is
+    /// included when its child pays enough.
+    ///
+    /// Packages are kept in a max-heap. Selecting a package only changes the packages of
+    /// the descendants of its transactions, so only those are scored again.
+    pub fn includable_within(&self, max_gas: Word, max_bytes: usize) -> Vec<ArcTx> {
+        let mut selected: HashSet<TxId> = HashSet::new();
+        let mut includable = Vec::new();
+        let mut gas_left = max_gas;
+        let mut bytes_left = max_bytes;
+
+        // heap entries of a transaction with an older version have been scored again
+        let mut versions: HashMap<TxId, usize> = HashMap::new();
+        let mut heap: BinaryHeap<Candidate> = self
+            .by_hash
+            .keys()
+            .map(|tx_id| Candidate {
+                package: self.package(tx_id, &selected),
+                tx_id: *tx_id,
+                version: 0,
+            })
+            .collect();
+
+        while let Some(candidate) = heap.pop() {
+            let package = candidate.package;
+            if selected.contains(&candidate.tx_id)
+                || versions.get(&candidate.tx_id).copied().unwrap_or_default() != candidate.version
+                || package.gas > gas_left
+                || package.bytes > bytes_left
+            {
+                continue;
+            }
+
+            gas_left -= package.gas;
+            bytes_left -= package.bytes;
+            let mut changed = Vec::new();
+            for tx in package.txs {
+                selected.insert(tx.id());
+                changed.extend(self.by_dependency.children(&tx));
+                includable.push(tx);
+            }
+
+            // the packages of descendants don't contain the selected transactions anymore
+            let mut rescored = HashSet::new();
+            while let Some(tx_id) = changed.pop() {
+                if selected.contains(&tx_id) || !rescored.insert(tx_id) {
+                    continue;
+                }
+                changed.extend(self.by_dependency.children(self.by_hash[&tx_id].tx()));
+                let version = versions.entry(tx_id).or_default();
+                *version += 1;
+                heap.push(Candidate {
+                    package: self.package(&tx_id, &selected),
+                    tx_id,
+                    version: *version,
+                });
+            }
+        }
+        includable
+    }
+
+    /// Collect transaction with all of its parents that are not already selected,
+    /// ordered from parents to children.
+    fn package(&self, tx_id: &TxId, selected: &HashSet<TxId>) -> Package {
+        let mut package = Package::default();
+        let mut visited = HashSet::new();
+        // (tx_id, are parents already pushed)
+        let mut stack = vec![(*tx_id, false)];
+        while let Some((tx_id, parents_pushed)) = stack.pop() {
+            if parents_pushed {
+                package.push(self.by_hash[&tx_id].tx().clone());
+                continue;
+            }
+            if selected.contains(&tx_id) || !visited.insert(tx_id) {
+                continue;
+            }
+            stack.push((tx_id, true));
+            let tx = self.by_hash[&tx_id].tx();
+            for parent in self.by_dependency.parents(tx) {
+                stack.push((parent, false));
+            }
+        }
+        package
+    }
+
+    /// When a block is imported, remove the transactions that aren't valid under the parameter
+    /// upgrades that activated since the last update, together with their dependents. Return
+    /// removed transactions.
+    pub fn block_update(&mut self, db: &dyn TxPoolDb) -> anyhow::Result<Vec<ArcTx>> {
+        let next_height = db.current_block_height()? + 1u32.into();
+        let checked_at = self.upgrades_checked_at.replace(next_height);
+        let activated = self.config.upgrades.iter().any(|upgrade| {
+            upgrade.height <= next_height
+                && checked_at.map_or(upgrade.height == next_height, |checked_at| {
+                    upgrade.height > checked_at
+                })
+        });
+        if !activated {
+            return Ok(Vec::new());
+        }
+
+        let invalid: Vec<TxId> = self
+            .by_hash
+            .values()
+            .filter(|info| self.verify_tx_validity(info.tx(), db).is_err())
+            .map(|info| info.tx().id())
+            .collect();
+        let mut removed = Vec::new();
+        for tx_id in invalid {
+            // dependents of already removed transactions are gone, so this returns nothing for them.
+            removed.extend(self.remove_by_tx_id(&tx_id));
+        }
+        Ok(removed)
+    }
 
     pub fn remove(&mut self, tx: &ArcTx) -> Vec<ArcTx> {
         self.remove_by_tx_id(&tx.id())
@@ -106,19 +255,55 @@ impl TxPool {
 
     /// remove transaction from pool needed on user demand. Low priority
     pub fn remove_by_tx_id(&mut self, tx_id: &TxId) -> Vec<ArcTx> {
-        if let Some(tx) = self.by_hash.remove(tx_id) {
+        if let Some(tx) = self.remove_from_collections(tx_id) {
             let removed = self
                 .by_dependency
                 .recursively_remove_all_dependencies(&self.by_hash, tx.tx().clone());
             for remove in removed.iter() {
-                self.by_gas_price.remove(remove);
-                self.by_hash.remove(&remove.id());
+                self.remove_from_collections(&remove.id());
             }
             return removed;
         }
         Vec::new()
     }
 
+    /// Remove a transaction that was executed in a block, keeping the transactions that depend
+    /// on it in the pool.
+    pub fn remove_committed(&mut self, tx_id: &TxId) -> Option<ArcTx> {
+        let info = self.remove_from_collections(tx_id)?;
+        self.by_dependency.remove_committed(info.tx());
+        Some(info.tx().clone())
+    }
+
+    /// remove transaction from all collections except dependency graph.
+    fn remove_from_collections(&mut self, tx_id: &TxId) -> Option<TxInfo> {
+        let info = self.by_hash.remove(tx_id)?;
+        self.by_gas_price.remove(info.tx());
+        self.by_time.remove(&info);
+        self.by_owner.remove(info.tx());
+        Some(info)
+    }
+
+    /// Remove all transactions that stayed in the pool longer than `Config::transaction_ttl`,
+    /// together with transactions that depend on them. Return removed transactions.
+    pub fn prune_old_txs(&mut self) -> Vec<ArcTx> {
+        let deadline = match chrono::Duration::from_std(self.config.transaction_ttl)
+            .ok()
+            .and_then(|ttl| Utc::now().checked_sub_signed(ttl))
+        {
+            Some(deadline) => deadline,
+            // ttl is too big to ever be reached
+            None => return Vec::new(),
+        };
+
+        let mut removed = Vec::new();
+        for tx_id in self.by_time.submitted_before(deadline) {
+            // dependents of already pruned transactions are gone, so this returns nothing for them.
+            removed.extend(self.remove_by_tx_id(&tx_id));
+        }
+        removed
+    }
+
     fn verify_tx_min_gas_price(&mut self, tx: &Transaction) -> Result<(), Error> {
         if tx.gas_price() < self.config.min_gas_price {
             return Err(Error::NotInsertedGasPriceTooLow);
@@ -126,6 +311,62 @@ impl TxPool {
         Ok(())
     }
 
+    fn verify_tx_owner_limit(
+        &self,
+        tx: &Transaction,
+        replaced: &HashSet<TxId>,
+    ) -> Result<(), Error> {
+        for owner in Owners::owners_of(tx) {
+            if self.by_owner.count_excluding(&owner, replaced) >= self.config.max_tx_per_owner {
+                return Err(Error::NotInsertedOwnerLimitHit(owner));
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the executor's validation against the next block. Coins that are created by
+    /// transactions in the pool are not in the database yet, their existence is checked by
+    /// the dependency graph instead.
+    fn verify_tx_validity(&self, tx: &Transaction, db: &dyn TxPoolDb) -> anyhow::Result<()> {
+        let block_height = db.current_block_height()? + 1u32.into();
+        let validator = self.validator(block_height);
+        validator.verify_predicates(tx)?;
+        if !self.config.utxo_validation {
+            return Ok(());
+        }
+        validator.verify_has_at_least_one_coin(tx)?;
+        for input in tx.inputs() {
+            if let Input::CoinSigned { utxo_id, .. } | Input::CoinPredicate { utxo_id, .. } = input
+            {
+                if let Some(coin) = db.utxo(utxo_id)? {
+                    validator.verify_coin(utxo_id, &coin, block_height)?;
+                }
+            }
+        }
+        validator.verify_signatures(tx)?;
+        validator.verify_parameters(tx, block_height)?;
+        validator.verify_fee(tx)?;
+        Ok(())
+    }
+
+    /// The validator of the transactions of the block at `block_height`, with the parameter
+    /// upgrades scheduled up to that height applied.
+    fn validator(&self, block_height: BlockHeight) -> TransactionValidator {
+        TransactionValidator::new(
+            ParameterUpgrade::transaction_parameters_at(
+                &self.config.upgrades,
+                block_height,
+                self.config.transaction_parameters,
+            ),
+            self.config.utxo_validation,
+            ParameterUpgrade::predicates_at(
+                &self.config.upgrades,
+                block_height,
+                self.config.predicates,
+            ),
+        )
+    }
+
     fn verify_tx_min_byte_price(&mut self, tx: &Transaction) -> Result<(), Error> {
         if tx.byte_price() < self.config.min_byte_price {
             return Err(Error::NotInsertedBytePriceTooLow);
@@ -134,13 +375,82 @@ impl TxPool {
     }
 }
 
+/// Transaction together with its parents that are not yet selected for the block.
+#[derive(Debug, Default)]
+struct Package {
+    txs: Vec<ArcTx>,
+    gas: Word,
+    bytes: usize,
+    fee: u128,
+}
+
+impl Package {
+    fn push(&mut self, tx: ArcTx) {
+        let bytes = tx.metered_bytes_size();
+        self.gas = self.gas.saturating_add(tx.gas_limit());
+        self.bytes = self.bytes.saturating_add(bytes);
+        self.fee = self
+            .fee
+            .saturating_add(tx.gas_price() as u128 * tx.gas_limit() as u128)
+            .saturating_add(tx.byte_price() as u128 * bytes as u128);
+        self.txs.push(tx);
+    }
+
+    /// Compare fee per gas of two packages, larger fee breaks the tie.
+    fn cmp_score(&self, other: &Package) -> Ordering {
+        let this = self.fee.saturating_mul(other.gas as u128);
+        let that = other.fee.saturating_mul(self.gas as u128);
+        this.cmp(&that).then(self.fee.cmp(&other.fee))
+    }
+}
+
+/// Package of a transaction waiting in the selection heap, ordered by its score.
+#[derive(Debug)]
+struct Candidate {
+    package: Package,
+    tx_id: TxId,
+    version: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.package
+            .cmp_score(&other.package)
+            .then_with(|| self.tx_id.cmp(&other.tx_id))
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
     use crate::Error;
-    use fuel_core_interfaces::{common::fuel_tx::UtxoId, db::helpers::*, model::CoinStatus};
+    use fuel_core_interfaces::{
+        common::{
+            fuel_asm::Opcode,
+            fuel_tx::{Output, UtxoId},
+            fuel_vm::consts::REG_ONE,
+        },
+        db::helpers::*,
+        model::CoinStatus,
+        validation::TransactionValidityError,
+    };
     use std::cmp::Reverse;
     use std::sync::Arc;
+    use std::time::Duration;
 
     #[tokio::test]
     async fn simple_insertion() {
@@ -452,6 +762,36 @@ pub mod tests {
         assert_eq!(txs[2].id(), tx2_hash, "Third should be tx2");
     }
 
+    #[tokio::test]
+    async fn pending_tx1_2_4_sorted_by_price() {
+        let config = Config::default();
+        let db = DummyDb::filled();
+
+        let tx1_hash = *TX_ID1;
+        let tx2_hash = *TX_ID2;
+        let tx4_hash = *TX_ID4;
+        let tx1 = Arc::new(DummyDb::dummy_tx(tx1_hash));
+        let tx2 = Arc::new(DummyDb::dummy_tx(tx2_hash));
+        let tx4 = Arc::new(DummyDb::dummy_tx(tx4_hash));
+        let mut txpool = TxPool::new(config);
+
+        txpool.insert(tx1, &db).await.expect("Tx1 should be OK");
+        txpool.insert(tx2, &db).await.expect("Tx2 should be OK");
+        txpool.insert(tx4, &db).await.expect("Tx4 should be OK");
+
+        let txs = txpool.pending();
+        let ids: Vec<TxId> = txs.iter().map(|info| info.id()).collect();
+        assert_eq!(
+            ids,
+            vec![tx4_hash, tx1_hash, tx2_hash],
+            "Should be sorted by price"
+        );
+        assert!(
+            txs[1].submited_time() <= txs[2].submited_time(),
+            "Tx1 should be submitted before Tx2"
+        );
+    }
+
     #[tokio::test]
     async fn find_dependent_tx1_tx2() {
         let config = Config::default();
@@ -552,4 +892,438 @@ pub mod tests {
             Error::NotInsertedBytePriceTooLow
         ));
     }
+
+    #[tokio::test]
+    async fn tx_not_pruned_before_ttl() {
+        let config = Config {
+            transaction_ttl: Duration::from_secs(60),
+            ..Config::default()
+        };
+        let db = DummyDb::filled();
+
+        let tx1_hash = *TX_ID1;
+        let tx1 = Arc::new(DummyDb::dummy_tx(tx1_hash));
+
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(tx1, &db).await;
+        assert!(out.is_ok(), "Tx1 should be OK, get err:{:?}", out);
+
+        let pruned = txpool.prune_old_txs();
+        assert!(pruned.is_empty(), "Nothing should be pruned:{:?}", pruned);
+        assert!(txpool.txs().contains_key(&tx1_hash), "Tx1 should stay");
+    }
+
+    #[tokio::test]
+    async fn expired_tx1_pruned_with_dependent_tx2() {
+        let config = Config {
+            transaction_ttl: Duration::from_millis(100),
+            ..Config::default()
+        };
+        let db = DummyDb::filled();
+
+        let tx1_hash = *TX_ID1;
+        let tx2_hash = *TX_ID2;
+        let tx4_hash = *TX_ID4;
+        let tx1 = Arc::new(DummyDb::dummy_tx(tx1_hash));
+        let tx2 = Arc::new(DummyDb::dummy_tx(tx2_hash));
+        let tx4 = Arc::new(DummyDb::dummy_tx(tx4_hash));
+
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(tx1, &db).await;
+        assert!(out.is_ok(), "Tx1 should be OK, get err:{:?}", out);
+        let out = txpool.insert(tx2, &db).await;
+        assert!(out.is_ok(), "Tx2 should be OK, get err:{:?}", out);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let out = txpool.insert(tx4, &db).await;
+        assert!(out.is_ok(), "Tx4 should be OK, get err:{:?}", out);
+
+        let pruned = txpool.prune_old_txs();
+        assert_eq!(pruned.len(), 2, "Tx1 and Tx2 should be pruned:{:?}", pruned);
+        assert_eq!(pruned[0].id(), tx1_hash, "Tx1 should be pruned");
+        assert_eq!(pruned[1].id(), tx2_hash, "Tx2 should be pruned");
+
+        let txs = txpool.sorted_includable();
+        assert_eq!(txs.len(), 1, "Only tx4 should stay");
+        assert_eq!(txs[0].id(), tx4_hash, "Tx4 should stay");
+    }
+
+    #[tokio::test]
+    async fn tx4_owner_limit_hit() {
+        let config = Config {
+            max_tx_per_owner: 1,
+            ..Config::default()
+        };
+        let db = DummyDb::filled();
+
+        let tx1_hash = *TX_ID1;
+        let tx4_hash = *TX_ID4;
+        let tx1 = Arc::new(DummyDb::dummy_tx(tx1_hash));
+        let tx4 = Arc::new(DummyDb::dummy_tx(tx4_hash));
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(tx1, &db).await;
+        assert!(out.is_ok(), "Tx1 should be OK, get err:{:?}", out);
+        let out = txpool.insert(tx4, &db).await;
+        let t: Error = out.unwrap_err().downcast().unwrap();
+        assert_eq!(
+            t,
+            Error::NotInsertedOwnerLimitHit(Address::default()),
+            "Tx4 should hit owner limit"
+        );
+    }
+
+    #[tokio::test]
+    async fn more_priced_tx3_replaces_tx1_of_owner_at_limit() {
+        let config = Config {
+            max_tx_per_owner: 1,
+            ..Config::default()
+        };
+        let db = DummyDb::filled();
+
+        let tx1 = Arc::new(DummyDb::dummy_tx(*TX_ID1));
+        let tx3 = Arc::new(DummyDb::dummy_tx(*TX_ID3));
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(tx1, &db).await;
+        assert!(out.is_ok(), "Tx1 should be OK, get err:{:?}", out);
+        let removed = txpool
+            .insert(tx3, &db)
+            .await
+            .expect("Tx3 should replace tx1");
+        assert_eq!(removed.len(), 1, "Tx1 should be removed:{:?}", removed);
+        assert_eq!(removed[0].id(), *TX_ID1);
+    }
+
+    #[tokio::test]
+    async fn tx4_inserted_after_owner_tx1_removed() {
+        let config = Config {
+            max_tx_per_owner: 1,
+            ..Config::default()
+        };
+        let db = DummyDb::filled();
+
+        let tx1_hash = *TX_ID1;
+        let tx4_hash = *TX_ID4;
+        let tx1 = Arc::new(DummyDb::dummy_tx(tx1_hash));
+        let tx4 = Arc::new(DummyDb::dummy_tx(tx4_hash));
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(tx1, &db).await;
+        assert!(out.is_ok(), "Tx1 should be OK, get err:{:?}", out);
+        let removed = txpool.remove_by_tx_id(&tx1_hash);
+        assert_eq!(removed.len(), 1, "Tx1 should be removed:{:?}", removed);
+        let out = txpool.insert(tx4, &db).await;
+        assert!(out.is_ok(), "Tx4 should be OK, get err:{:?}", out);
+    }
+
+    #[tokio::test]
+    async fn includable_within_gas_never_returns_child_without_parent() {
+        let config = Config::default();
+        let db = DummyDb::filled();
+
+        let tx1_hash = *TX_ID1;
+        let tx2_hash = *TX_ID2;
+        let tx4_hash = *TX_ID4;
+        let tx1 = Arc::new(DummyDb::dummy_tx(tx1_hash));
+        let tx2 = Arc::new(DummyDb::dummy_tx(tx2_hash));
+        let tx4 = Arc::new(DummyDb::dummy_tx(tx4_hash));
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(tx1.clone(), &db).await;
+        assert!(out.is_ok(), "Tx1 should be OK, get err:{:?}", out);
+        let out = txpool.insert(tx2.clone(), &db).await;
+        assert!(out.is_ok(), "Tx2 should be OK, get err:{:?}", out);
+        let out = txpool.insert(tx4.clone(), &db).await;
+        assert!(out.is_ok(), "Tx4 should be OK, get err:{:?}", out);
+
+        // only two transactions fit
+        let max_gas = tx4.gas_limit() + tx1.gas_limit();
+        let txs = txpool.includable_within(max_gas, usize::MAX);
+        assert_eq!(txs.len(), 2, "Should have 2 txs:{:?}", txs);
+        assert_eq!(txs[0].id(), tx4_hash, "First should be tx4");
+        assert_eq!(txs[1].id(), tx1_hash, "Second should be tx1");
+
+        // tx2 fits only without its parent
+        let txs = txpool.includable_within(tx2.gas_limit(), usize::MAX);
+        assert_eq!(txs.len(), 1, "Tx2 can't be included without tx1:{:?}", txs);
+        assert_eq!(txs[0].id(), tx4_hash, "Only tx4 should fit");
+
+        let txs = txpool.includable_within(Word::MAX, usize::MAX);
+        assert_eq!(txs.len(), 3, "Should have 3 txs:{:?}", txs);
+        assert_eq!(txs[0].id(), tx4_hash, "First should be tx4");
+        assert_eq!(txs[1].id(), tx1_hash, "Second should be tx1");
+        assert_eq!(txs[2].id(), tx2_hash, "Third should be tx2");
+    }
+
+    #[tokio::test]
+    async fn committed_parent_is_removed_without_its_child() {
+        let config = Config::default();
+        let db = DummyDb::filled();
+
+        let tx1 = Arc::new(DummyDb::dummy_tx(*TX_ID1));
+        let tx2 = Arc::new(DummyDb::dummy_tx(*TX_ID2));
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(tx1.clone(), &db).await;
+        assert!(out.is_ok(), "Tx1 should be OK, get err:{:?}", out);
+        let out = txpool.insert(tx2.clone(), &db).await;
+        assert!(out.is_ok(), "Tx2 should be OK, get err:{:?}", out);
+
+        // only tx1 fits the block, tx2 waits for the next one
+        let txs = txpool.includable_within(tx1.gas_limit(), usize::MAX);
+        assert_eq!(txs, vec![tx1.clone()]);
+        assert_eq!(txpool.remove_committed(&tx1.id()), Some(tx1));
+
+        assert!(txpool.txs().contains_key(&tx2.id()), "Tx2 should stay");
+        assert!(txpool.dependency().parents(&tx2).is_empty());
+        let txs = txpool.includable_within(tx2.gas_limit(), usize::MAX);
+        assert_eq!(txs, vec![tx2.clone()]);
+        assert_eq!(txpool.remove_committed(&tx2.id()), Some(tx2));
+        assert!(txpool.txs().is_empty());
+    }
+
+    #[tokio::test]
+    async fn includable_within_gas_includes_low_priced_parent_of_high_priced_child() {
+        let config = Config::default();
+        let db = DummyDb::filled();
+
+        let tx1_hash = *TX_ID1;
+        let tx4_hash = *TX_ID4;
+        let tx1 = Arc::new(DummyDb::dummy_tx(tx1_hash));
+        let mut tx2 = DummyDb::dummy_tx(*TX_ID2);
+        tx2.set_gas_price(100);
+        tx2.precompute_metadata();
+        let tx2 = Arc::new(tx2);
+        let tx4 = Arc::new(DummyDb::dummy_tx(tx4_hash));
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(tx1.clone(), &db).await;
+        assert!(out.is_ok(), "Tx1 should be OK, get err:{:?}", out);
+        let out = txpool.insert(tx2.clone(), &db).await;
+        assert!(out.is_ok(), "Tx2 should be OK, get err:{:?}", out);
+        let out = txpool.insert(tx4, &db).await;
+        assert!(out.is_ok(), "Tx4 should be OK, get err:{:?}", out);
+
+        let max_gas = tx1.gas_limit() + tx2.gas_limit();
+        let txs = txpool.includable_within(max_gas, usize::MAX);
+        assert_eq!(txs.len(), 2, "Should have 2 txs:{:?}", txs);
+        assert_eq!(txs[0].id(), tx1_hash, "First should be parent tx1");
+        assert_eq!(txs[1].id(), tx2.id(), "Second should be child tx2");
+    }
+
+    #[tokio::test]
+    async fn includable_within_bytes_limit() {
+        let config = Config::default();
+        let db = DummyDb::filled();
+
+        let tx1 = Arc::new(DummyDb::dummy_tx(*TX_ID1));
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(tx1.clone(), &db).await;
+        assert!(out.is_ok(), "Tx1 should be OK, get err:{:?}", out);
+
+        let bytes = tx1.metered_bytes_size();
+        let txs = txpool.includable_within(Word::MAX, bytes - 1);
+        assert!(txs.is_empty(), "Tx1 should not fit:{:?}", txs);
+        let txs = txpool.includable_within(Word::MAX, bytes);
+        assert_eq!(txs.len(), 1, "Tx1 should fit:{:?}", txs);
+    }
+
+    #[tokio::test]
+    async fn tx_with_predicate_not_inserted_when_predicates_disabled() {
+        let config = Config::default();
+        let db = DummyDb::filled();
+
+        let mut tx = Transaction::script(
+            0,
+            1_000_000,
+            0,
+            0,
+            vec![],
+            vec![],
+            vec![Input::coin_predicate(
+                UtxoId::new(*TX_ID_DB1, 0),
+                Address::default(),
+                100,
+                Default::default(),
+                0,
+                vec![0u8; 4],
+                vec![],
+            )],
+            vec![Output::change(Address::default(), 0, Default::default())],
+            vec![],
+        );
+        tx.precompute_metadata();
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(Arc::new(tx), &db).await;
+        let err: TransactionValidityError = out.unwrap_err().downcast().unwrap();
+        assert!(
+            matches!(err, TransactionValidityError::PredicateExecutionDisabled(_)),
+            "Tx with predicate should be rejected:{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn tx_with_predicate_not_inserted_once_upgrade_disables_predicates() {
+        let db = DummyDb::filled();
+        db.data.lock().chain_height = 9u32.into();
+        let disable_at = |height: u32| Config {
+            predicates: true,
+            upgrades: vec![ParameterUpgrade {
+                height: height.into(),
+                transaction_parameters: None,
+                predicates: Some(false),
+            }],
+            ..Config::default()
+        };
+
+        let mut tx = Transaction::script(
+            0,
+            1_000_000,
+            0,
+            0,
+            vec![],
+            vec![],
+            vec![Input::coin_predicate(
+                UtxoId::new(*TX_ID_DB1, 0),
+                Address::default(),
+                100,
+                Default::default(),
+                0,
+                vec![0u8; 4],
+                vec![],
+            )],
+            vec![Output::change(Address::default(), 0, Default::default())],
+            vec![],
+        );
+        tx.precompute_metadata();
+        let tx = Arc::new(tx);
+
+        // the next block is at height 10
+        let out = TxPool::new(disable_at(10)).insert(tx.clone(), &db).await;
+        let err: TransactionValidityError = out.unwrap_err().downcast().unwrap();
+        assert!(
+            matches!(err, TransactionValidityError::PredicateExecutionDisabled(_)),
+            "Tx with predicate should be rejected:{:?}",
+            err
+        );
+
+        let out = TxPool::new(disable_at(11)).insert(tx, &db).await;
+        let err: TransactionValidityError = out.unwrap_err().downcast().unwrap();
+        assert!(
+            matches!(err, TransactionValidityError::InvalidPredicate(_)),
+            "Predicates should still be enabled:{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn tx_removed_once_upgrade_disabling_predicates_activates() {
+        let db = DummyDb::filled();
+        db.data.lock().chain_height = 9u32.into();
+        let mut txpool = TxPool::new(Config {
+            predicates: true,
+            upgrades: vec![ParameterUpgrade {
+                height: 11u32.into(),
+                transaction_parameters: None,
+                predicates: Some(false),
+            }],
+            ..Config::default()
+        });
+
+        let predicate = Opcode::RET(REG_ONE).to_bytes().to_vec();
+        let owner = Input::predicate_owner(&predicate);
+        db.data
+            .lock()
+            .coins
+            .get_mut(&UtxoId::new(*TX_ID_DB1, 0))
+            .unwrap()
+            .owner = owner;
+        let mut tx = Transaction::script(
+            0,
+            1_000_000,
+            0,
+            0,
+            vec![],
+            vec![],
+            vec![Input::coin_predicate(
+                UtxoId::new(*TX_ID_DB1, 0),
+                owner,
+                100,
+                Default::default(),
+                0,
+                predicate,
+                vec![],
+            )],
+            vec![Output::change(Address::default(), 0, Default::default())],
+            vec![],
+        );
+        tx.precompute_metadata();
+        let tx = Arc::new(tx);
+        txpool
+            .insert(tx.clone(), &db)
+            .await
+            .expect("Predicates are enabled at block 10");
+
+        // the upgrade applies from block 11 on, which is next once block 10 is imported
+        assert!(txpool.block_update(&db).unwrap().is_empty());
+        db.data.lock().chain_height = 10u32.into();
+        let removed = txpool.block_update(&db).unwrap();
+        assert_eq!(removed, vec![tx.clone()]);
+        assert!(!txpool.txs().contains_key(&tx.id()));
+    }
+
+    #[tokio::test]
+    async fn tx1_with_immature_coin_not_inserted() {
+        let config = Config {
+            utxo_validation: true,
+            ..Config::default()
+        };
+        let db = DummyDb::filled();
+
+        // coin can be spent only after block 5
+        db.data
+            .lock()
+            .coins
+            .get_mut(&UtxoId::new(*TX_ID_DB1, 0))
+            .unwrap()
+            .maturity = 5u32.into();
+
+        let tx1 = Arc::new(DummyDb::dummy_tx(*TX_ID1));
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(tx1, &db).await;
+        let err: TransactionValidityError = out.unwrap_err().downcast().unwrap();
+        assert!(
+            matches!(err, TransactionValidityError::CoinHasNotMatured(utxo_id) if utxo_id == UtxoId::new(*TX_ID_DB1, 0)),
+            "Tx1 should spend immature coin:{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn tx1_with_invalid_signature_not_inserted() {
+        let config = Config {
+            utxo_validation: true,
+            ..Config::default()
+        };
+        let db = DummyDb::filled();
+
+        let tx1 = Arc::new(DummyDb::dummy_tx(*TX_ID1));
+        let mut txpool = TxPool::new(config);
+
+        let out = txpool.insert(tx1, &db).await;
+        let err: TransactionValidityError = out.unwrap_err().downcast().unwrap();
+        assert!(
+            matches!(err, TransactionValidityError::Validation(_)),
+            "Tx1 has no valid signature:{:?}",
+            err
+        );
+    }
 }
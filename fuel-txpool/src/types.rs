@@ -1,6 +1,6 @@
 use fuel_core_interfaces::common::fuel_types::Word;
 pub use fuel_core_interfaces::common::{
-    fuel_tx::ContractId,
+    fuel_tx::{Address, ContractId},
     fuel_tx::{Transaction, TxId},
 };
 